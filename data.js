@@ -94,18 +94,69 @@ function closeModal(modalId) {
   modal.setAttribute("aria-hidden", "true");
 }
 
+// Modes whose team count is chosen in the create modal, with their minimum size.
+const VARIABLE_SIZE_MODES = { league: 3, knockout: 2, tournament: 8, swiss: 12 };
+// Tournaments grow a whole group of four at a time.
+const TEAM_COUNT_STEP = { tournament: 4 };
+
+// Swiss fields need equal pots (two matches per pot), an even team count and
+// at least three teams in every pot.
+function swissShape() {
+  const pots = Number($("swissMatches").value) / 2;
+  const step = pots % 2 === 0 ? pots : pots * 2;
+  return { min: Math.ceil((pots * 3) / step) * step, step };
+}
+
+function teamCountBounds(mode) {
+  if (mode === "swiss") return swissShape();
+  return { min: VARIABLE_SIZE_MODES[mode], step: TEAM_COUNT_STEP[mode] || 1 };
+}
+
+// Modes with standings tables, where tie-breakers apply.
+const TABLE_MODES = new Set(["league4", "league", "tournament", "swiss"]);
+
 function getModeTeamCount(mode) {
+  if (mode in VARIABLE_SIZE_MODES) return Number($("teamCount").value) || VARIABLE_SIZE_MODES[mode];
   return mode === "single" ? 2 : 4;
 }
 
+function renderTeamCountOptions(mode) {
+  const select = $("teamCount");
+  const { min, step } = teamCountBounds(mode);
+  select.disabled = min === undefined;
+  $("doubleRoundRobinWrap").hidden = !(mode === "league" || mode === "league4");
+  const knockout = mode === "knockout" || mode === "knockout4";
+  $("drawWrap").hidden = !(knockout || mode === "tournament");
+  $("separationWrap").hidden = $("drawWrap").hidden || $("drawSelect").value !== "pots";
+  $("bestThirdsWrap").hidden = mode !== "tournament";
+  $("swissMatchesWrap").hidden = mode !== "swiss";
+  $("tieBreakWrap").hidden = !TABLE_MODES.has(mode);
+  $("twoLeggedWrap").hidden = !knockout;
+  $("awayGoalsWrap").hidden = !knockout || !$("twoLegged").checked;
+
+  if (min === undefined) {
+    const required = getModeTeamCount(mode);
+    select.innerHTML = `<option value="${required}">${required}</option>`;
+    return;
+  }
+
+  const previous = Number(select.value);
+  const current = previous >= min && (previous - min) % step === 0 ? previous : Math.max(min, 4);
+  const max = Math.max(state.teams.length, current);
+  const options = [];
+  for (let n = min; n <= max; n += step) {
+    options.push(`<option value="${n}" ${n === current ? "selected" : ""}>${n}</option>`);
+  }
+  select.innerHTML = options.join("");
+}
+
 function renderTeamSelectors() {
   const wrap = $("teamSelectWrap");
   const mode = $("modeSelect").value;
+  renderTeamCountOptions(mode);
   const required = getModeTeamCount(mode);
   const autoFill = $("autoFill").checked;
 
-  $("teamCount").innerHTML = `<option value="${required}">${required}</option>`;
-
   if (state.teams.length === 0) {
     wrap.innerHTML = "<p>Loading teams...</p>";
     return;
@@ -130,13 +181,46 @@ function renderTeamSelectors() {
   wrap.innerHTML = fields.join("");
 }
 
+// Teams in the selected pool; manual picks and auto-fill both draw from it.
+async function loadTeams() {
+  const pool = $("teamPool").value;
+  state.teams = await request(pool ? `api/teams?${pool}` : "api/teams");
+  renderTeamSelectors();
+}
+
 function getCreatePayload() {
   const mode = $("modeSelect").value;
   const autoFill = $("autoFill").checked;
   const required = getModeTeamCount(mode);
+  const extra = mode in VARIABLE_SIZE_MODES ? { team_count: required } : {};
+  if (mode === "league" || mode === "league4") {
+    extra.double_round_robin = $("doubleRoundRobin").checked;
+  }
+  if (mode === "knockout" || mode === "knockout4") {
+    extra.draw = $("drawSelect").value;
+    extra.separation = $("separation").value;
+    extra.two_legged = $("twoLegged").checked;
+    extra.away_goals = $("twoLegged").checked && $("awayGoals").checked;
+  }
+  if (TABLE_MODES.has(mode)) {
+    extra.tie_break = $("tieBreak").value;
+  }
+  if (mode === "swiss") {
+    extra.swiss_matches = Number($("swissMatches").value);
+  }
+  if (mode === "tournament") {
+    extra.draw = $("drawSelect").value;
+    extra.separation = $("separation").value;
+    extra.best_thirds = $("bestThirds").checked;
+  }
+
+  const pool = $("teamPool").value;
+  if (pool) {
+    extra.pool = Object.fromEntries(new URLSearchParams(pool));
+  }
 
   if (autoFill) {
-    return { mode, auto_fill: true };
+    return { mode, auto_fill: true, ...extra };
   }
 
   const picks = [...$("teamSelectWrap").querySelectorAll("select")].map((s) => s.value);
@@ -145,7 +229,7 @@ function getCreatePayload() {
     throw new Error("Please select unique teams for this mode");
   }
 
-  return { mode, auto_fill: false, teams: picks };
+  return { mode, auto_fill: false, teams: picks, ...extra };
 }
 
 function cardActions(sim) {
@@ -300,12 +384,32 @@ function textOrPlaceholder(lines, fallback) {
   return lines.join("\n");
 }
 
+function pct(value) {
+  return `${Math.round(value * 100)}%`;
+}
+
+function renderWinProbability(prob) {
+  const wrap = $("detailWinProb");
+  if (!prob) {
+    wrap.hidden = true;
+    return;
+  }
+  wrap.hidden = false;
+  const minute = prob.minute.added > 0 ? `${prob.minute.base}+${prob.minute.added}` : `${prob.minute.base}`;
+  $("detailWinProbLabel").textContent =
+    `Win probability ${minute}' - ${prob.home} ${pct(prob.home_win)} | Draw ${pct(prob.draw)} | ${prob.away} ${pct(prob.away_win)}`;
+  $("detailWinProbHome").style.width = pct(prob.home_win);
+  $("detailWinProbDraw").style.width = pct(prob.draw);
+  $("detailWinProbAway").style.width = pct(prob.away_win);
+}
+
 async function loadDetail(id) {
   const detail = await request(`api/simulations/${id}`);
   state.selectedDetailId = id;
 
   $("detailTitle").textContent = `sim-${detail.id} - ${detail.title}`;
   $("detailScoreboard").textContent = `${detail.scoreboard} | ${detail.outcome}`;
+  renderWinProbability(detail.win_probability);
   $("detailLogs").textContent = textOrPlaceholder(detail.logs, "No events yet.");
   $("detailStats").textContent = textOrPlaceholder(detail.stats_lines, "No stats available yet.");
   $("detailCompetition").textContent = textOrPlaceholder(detail.competition_lines, "No standings/bracket available yet.");
@@ -370,8 +474,15 @@ function exportSimulation(id) {
 function bindEvents() {
   $("openCreateBtn").addEventListener("click", () => openModal("createModal"));
   $("createBtn").addEventListener("click", createSimulation);
+  $("drawSelect").addEventListener("change", renderTeamSelectors);
+  $("teamPool").addEventListener("change", () => {
+    loadTeams().catch((error) => setStatus(`Could not load teams: ${error.message}`));
+  });
   $("modeSelect").addEventListener("change", renderTeamSelectors);
   $("autoFill").addEventListener("change", renderTeamSelectors);
+  $("twoLegged").addEventListener("change", renderTeamSelectors);
+  $("swissMatches").addEventListener("change", renderTeamSelectors);
+  $("teamCount").addEventListener("change", renderTeamSelectors);
 
   document.querySelectorAll("[data-close]").forEach((button) => {
     button.addEventListener("click", () => {
@@ -434,8 +545,7 @@ async function boot() {
       }
     }
     
-    state.teams = await request("api/teams");
-    renderTeamSelectors();
+    await loadTeams();
     await refreshSimulations();
     setStatus(`Connected to SoccerCloud backend on port 9009.${state.demo ? " (Demo mode)" : ""}`);
   } catch (error) {
@@ -1,6 +1,9 @@
 use std::collections::HashSet;
 use std::fs::File;
 use std::io::{self, Write};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
 use std::time::{Duration, Instant};
 
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
@@ -11,9 +14,18 @@ use crossterm::terminal::{
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 
-use crate::data::TEAMS;
+use crate::bracket::DrawKind;
+use crate::data::{Confederation, TeamFilter, TeamKind, TEAMS};
+use crate::draw::Separation;
+use crate::elo::{EloStore, RatingsConfig};
+use crate::engine::EngineKind;
+use crate::groups::{GROUP_SIZE, MIN_TOURNAMENT_TEAMS};
 use crate::instance::{SimStatus, SimulationInstance};
-use crate::sim::SimulationType;
+use crate::sim::{
+    PreparedSimulation, SimConfig, SimulationType, TieFormat, MIN_KNOCKOUT_TEAMS, MIN_LEAGUE_TEAMS,
+};
+use crate::swiss::SwissFormat;
+use crate::tiebreak::TieBreakPreset;
 use crate::ui;
 use crate::utils::{derive_seed, Rng};
 
@@ -57,7 +69,11 @@ pub struct App {
     pub overlay_modal: Option<OverlayModal>,
     pub overlay_scroll: usize,
     pub status_line: String,
+    pub ratings: RatingsConfig,
     next_id: usize,
+    /// Simulations finished on worker threads, keyed by instance id.
+    prepared_tx: Sender<(usize, PreparedSimulation)>,
+    prepared_rx: Receiver<(usize, PreparedSimulation)>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -88,6 +104,18 @@ pub struct CreateDraft {
     pub mode: SimulationType,
     pub slots: Vec<TeamSlotDraft>,
     pub selected_slot: usize,
+    pub neutral_final: bool,
+    pub double_round_robin: bool,
+    pub draw: DrawKind,
+    pub separation: Separation,
+    pub tie_format: TieFormat,
+    pub best_thirds: bool,
+    pub swiss: SwissFormat,
+    pub tie_break: TieBreakPreset,
+    /// Teams CPU auto-fill slots are drawn from.
+    pub pool: TeamFilter,
+    pub engine: EngineKind,
+    pub use_elo: bool,
 }
 
 impl CreateDraft {
@@ -104,6 +132,17 @@ impl CreateDraft {
             mode,
             slots,
             selected_slot: 0,
+            neutral_final: false,
+            double_round_robin: false,
+            draw: DrawKind::default(),
+            separation: Separation::default(),
+            tie_format: TieFormat::default(),
+            best_thirds: false,
+            swiss: SwissFormat::default(),
+            tie_break: TieBreakPreset::default(),
+            pool: TeamFilter::default(),
+            engine: EngineKind::default(),
+            use_elo: false,
         }
     }
 
@@ -111,13 +150,80 @@ impl CreateDraft {
         match self.mode {
             SimulationType::Single => "Single Match",
             SimulationType::League4 => "4-Team League",
+            SimulationType::League => "Round-Robin League",
             SimulationType::Knockout4 => "4-Team Knockout",
+            SimulationType::Knockout => "Knockout Bracket",
+            SimulationType::Tournament => "Group Stage Tournament",
+            SimulationType::Swiss => "Swiss League Phase",
+        }
+    }
+
+    pub fn is_league(&self) -> bool {
+        matches!(self.mode, SimulationType::League4 | SimulationType::League)
+    }
+
+    pub fn is_knockout(&self) -> bool {
+        matches!(
+            self.mode,
+            SimulationType::Knockout4 | SimulationType::Knockout
+        )
+    }
+
+    pub fn is_tournament(&self) -> bool {
+        self.mode == SimulationType::Tournament
+    }
+
+    pub fn is_swiss(&self) -> bool {
+        self.mode == SimulationType::Swiss
+    }
+
+    /// Slot range for modes whose team count can change.
+    fn slot_bounds(&self) -> Option<(usize, usize)> {
+        match self.mode {
+            SimulationType::League => Some((MIN_LEAGUE_TEAMS, TEAMS.len())),
+            SimulationType::Knockout => Some((MIN_KNOCKOUT_TEAMS, TEAMS.len())),
+            SimulationType::Tournament => {
+                Some((MIN_TOURNAMENT_TEAMS, TEAMS.len() / GROUP_SIZE * GROUP_SIZE))
+            }
+            SimulationType::Swiss => Some((
+                self.swiss.min_teams(),
+                TEAMS.len() / self.swiss.team_step() * self.swiss.team_step(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Slots added or removed at once: a whole group for tournaments.
+    fn slot_step(&self) -> usize {
+        match self.mode {
+            SimulationType::Tournament => GROUP_SIZE,
+            SimulationType::Swiss => self.swiss.team_step(),
+            _ => 1,
+        }
+    }
+
+    /// Grow or shrink the slots to the nearest team count the mode accepts.
+    fn fit_slots(&mut self) {
+        let Some((min, max)) = self.slot_bounds() else {
+            return;
+        };
+        let step = self.slot_step();
+        let target = self.slots.len().div_ceil(step) * step;
+        let target = target.clamp(min, max / step * step);
+        while self.slots.len() < target {
+            self.slots.push(TeamSlotDraft {
+                is_cpu: true,
+                team_idx: self.slots.len() % TEAMS.len(),
+            });
         }
+        self.slots.truncate(target);
+        self.selected_slot = self.selected_slot.min(self.slots.len() - 1);
     }
 }
 
 impl App {
-    pub fn new(base_seed: u64, speed: Speed) -> Self {
+    pub fn new(base_seed: u64, speed: Speed, ratings: RatingsConfig) -> Self {
+        let (prepared_tx, prepared_rx) = mpsc::channel();
         Self {
             base_seed,
             speed,
@@ -128,7 +234,10 @@ impl App {
             overlay_modal: None,
             overlay_scroll: 0,
             status_line: format!("Ready. Seed={base_seed}, Speed={}", speed.label()),
+            ratings,
             next_id: 0,
+            prepared_tx,
+            prepared_rx,
         }
     }
 
@@ -228,6 +337,156 @@ impl App {
         slot.team_idx = idx as usize;
     }
 
+    pub fn draft_toggle_neutral_final(&mut self) {
+        if let Some(draft) = self.create_draft.as_mut() {
+            if draft.is_knockout() {
+                draft.neutral_final = !draft.neutral_final;
+            }
+        }
+    }
+
+    pub fn draft_cycle_engine(&mut self) {
+        if let Some(draft) = self.create_draft.as_mut() {
+            let all = EngineKind::ALL;
+            let idx = all.iter().position(|e| *e == draft.engine).unwrap_or(0);
+            draft.engine = all[(idx + 1) % all.len()];
+        }
+    }
+
+    pub fn draft_add_slot(&mut self) {
+        if let Some(draft) = self.create_draft.as_mut() {
+            let Some((_, max)) = draft.slot_bounds() else {
+                return;
+            };
+            let step = draft.slot_step();
+            if draft.slots.len() + step <= max {
+                for _ in 0..step {
+                    draft.slots.push(TeamSlotDraft {
+                        is_cpu: true,
+                        team_idx: draft.slots.len() % TEAMS.len(),
+                    });
+                }
+                draft.selected_slot = draft.slots.len() - 1;
+            }
+        }
+    }
+
+    pub fn draft_remove_slot(&mut self) {
+        if let Some(draft) = self.create_draft.as_mut() {
+            let Some((min, _)) = draft.slot_bounds() else {
+                return;
+            };
+            let step = draft.slot_step();
+            if draft.slots.len() >= min + step {
+                draft.slots.truncate(draft.slots.len() - step);
+                draft.selected_slot = draft.selected_slot.min(draft.slots.len() - 1);
+            }
+        }
+    }
+
+    pub fn draft_toggle_double_round_robin(&mut self) {
+        if let Some(draft) = self.create_draft.as_mut() {
+            if draft.is_league() {
+                draft.double_round_robin = !draft.double_round_robin;
+            }
+        }
+    }
+
+    pub fn draft_cycle_draw(&mut self) {
+        if let Some(draft) = self.create_draft.as_mut() {
+            if draft.is_knockout() || draft.is_tournament() {
+                let all = DrawKind::ALL;
+                let idx = all.iter().position(|d| *d == draft.draw).unwrap_or(0);
+                draft.draw = all[(idx + 1) % all.len()];
+            }
+        }
+    }
+
+    /// Pot draws only.
+    pub fn draft_cycle_separation(&mut self) {
+        if let Some(draft) = self.create_draft.as_mut() {
+            if draft.draw == DrawKind::Pots {
+                let all = Separation::ALL;
+                let idx = all.iter().position(|s| *s == draft.separation).unwrap_or(0);
+                draft.separation = all[(idx + 1) % all.len()];
+            }
+        }
+    }
+
+    pub fn draft_cycle_tie_format(&mut self) {
+        if let Some(draft) = self.create_draft.as_mut() {
+            if draft.is_knockout() {
+                draft.tie_format = match draft.tie_format {
+                    TieFormat::SingleMatch => TieFormat::TwoLegs { away_goals: false },
+                    TieFormat::TwoLegs { away_goals: false } => {
+                        TieFormat::TwoLegs { away_goals: true }
+                    }
+                    TieFormat::TwoLegs { away_goals: true } => TieFormat::SingleMatch,
+                };
+            }
+        }
+    }
+
+    pub fn draft_toggle_best_thirds(&mut self) {
+        if let Some(draft) = self.create_draft.as_mut() {
+            if draft.is_tournament() {
+                draft.best_thirds = !draft.best_thirds;
+            }
+        }
+    }
+
+    pub fn draft_cycle_swiss_matches(&mut self) {
+        if let Some(draft) = self.create_draft.as_mut() {
+            if draft.is_swiss() {
+                draft.swiss.matches = if draft.swiss.matches >= 10 {
+                    4
+                } else {
+                    draft.swiss.matches + 2
+                };
+                draft.fit_slots();
+            }
+        }
+    }
+
+    /// Tables only: league, tournament groups and the Swiss phase.
+    pub fn draft_cycle_tie_break(&mut self) {
+        if let Some(draft) = self.create_draft.as_mut() {
+            if draft.is_league() || draft.is_tournament() || draft.is_swiss() {
+                let all = TieBreakPreset::ALL;
+                let idx = all.iter().position(|p| *p == draft.tie_break).unwrap_or(0);
+                draft.tie_break = all[(idx + 1) % all.len()];
+            }
+        }
+    }
+
+    /// All teams, clubs, national teams, then each confederation's national teams.
+    pub fn draft_cycle_pool(&mut self) {
+        if let Some(draft) = self.create_draft.as_mut() {
+            let kind = |kind| TeamFilter {
+                kind: Some(kind),
+                ..TeamFilter::default()
+            };
+            let mut all = vec![
+                TeamFilter::default(),
+                kind(TeamKind::Club),
+                kind(TeamKind::National),
+            ];
+            all.extend(Confederation::ALL.into_iter().map(|confed| TeamFilter {
+                confederation: Some(confed),
+                ..kind(TeamKind::National)
+            }));
+            all.retain(|pool| !pool.teams().is_empty());
+            let idx = all.iter().position(|p| *p == draft.pool).unwrap_or(0);
+            draft.pool = all[(idx + 1) % all.len()].clone();
+        }
+    }
+
+    pub fn draft_toggle_elo(&mut self) {
+        if let Some(draft) = self.create_draft.as_mut() {
+            draft.use_elo = !draft.use_elo;
+        }
+    }
+
     pub fn confirm_create_draft(&mut self) {
         if self.instances.len() >= MAX_INSTANCES {
             self.status_line = format!("Instance limit reached ({MAX_INSTANCES})");
@@ -241,7 +500,7 @@ impl App {
         let id = self.next_id;
         let seed = derive_seed(self.base_seed, id as u64 + 1);
 
-        let teams = match resolve_teams_from_slots(&draft.slots, seed) {
+        let teams = match resolve_teams_from_slots(&draft.slots, &draft.pool, seed) {
             Ok(v) => v,
             Err(e) => {
                 self.status_line = e;
@@ -249,7 +508,37 @@ impl App {
             }
         };
 
-        let instance = SimulationInstance::new(id, draft.mode, teams, seed);
+        let elo = if draft.use_elo {
+            match EloStore::load(&self.ratings.path) {
+                Ok(store) => Some(Arc::new(store.strength())),
+                Err(e) => {
+                    self.status_line = format!("Could not load ratings: {e}");
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+
+        let instance = SimulationInstance::new(
+            id,
+            draft.mode,
+            teams,
+            seed,
+            SimConfig {
+                neutral_final: draft.neutral_final,
+                double_round_robin: draft.double_round_robin,
+                draw: draft.draw,
+                separation: draft.separation,
+                tie_format: draft.tie_format,
+                best_thirds: draft.best_thirds,
+                swiss: draft.swiss,
+                tie_breakers: draft.tie_break.rules().to_vec(),
+                engine: draft.engine,
+                elo,
+                ..SimConfig::default()
+            },
+        );
         self.instances.push(instance);
         self.selected = self.instances.len().saturating_sub(1);
         self.next_id += 1;
@@ -259,7 +548,14 @@ impl App {
 
     pub fn start_selected(&mut self) {
         if let Some(inst) = self.instances.get_mut(self.selected) {
-            inst.start();
+            // Large competitions take seconds to simulate; keep the UI responsive meanwhile.
+            if let Some(job) = inst.start() {
+                let id = inst.id;
+                let tx = self.prepared_tx.clone();
+                thread::spawn(move || {
+                    let _ = tx.send((id, job.run()));
+                });
+            }
             self.status_line = format!("Started sim-{}", inst.id);
         }
     }
@@ -320,11 +616,22 @@ impl App {
     }
 
     pub fn tick(&mut self) {
+        while let Ok((id, prepared)) = self.prepared_rx.try_recv() {
+            if let Some(inst) = self.instances.iter_mut().find(|inst| inst.id == id) {
+                inst.begin(prepared);
+            }
+        }
+
         let frames = self.speed.frames_per_tick();
         for inst in &mut self.instances {
             if matches!(inst.status, SimStatus::Running { .. }) {
                 inst.tick(frames);
             }
+            if let Some(results) = inst.take_results_for_ratings() {
+                if let Err(e) = self.ratings.record(&results) {
+                    self.status_line = format!("Ratings update failed: {e}");
+                }
+            }
         }
     }
 
@@ -407,6 +714,19 @@ pub fn run_tui(mut app: App) -> io::Result<()> {
                             }
                             KeyCode::Char('m') => app.draft_set_selected_manual(),
                             KeyCode::Char('p') => app.draft_set_selected_cpu(),
+                            KeyCode::Char('f') => app.draft_toggle_neutral_final(),
+                            KeyCode::Char('e') => app.draft_cycle_engine(),
+                            KeyCode::Char('r') => app.draft_toggle_elo(),
+                            KeyCode::Char('d') => app.draft_toggle_double_round_robin(),
+                            KeyCode::Char('s') => app.draft_cycle_draw(),
+                            KeyCode::Char('c') => app.draft_cycle_separation(),
+                            KeyCode::Char('t') => app.draft_cycle_tie_format(),
+                            KeyCode::Char('b') => app.draft_toggle_best_thirds(),
+                            KeyCode::Char('x') => app.draft_cycle_swiss_matches(),
+                            KeyCode::Char('g') => app.draft_cycle_tie_break(),
+                            KeyCode::Char('o') => app.draft_cycle_pool(),
+                            KeyCode::Char('+') | KeyCode::Char('=') => app.draft_add_slot(),
+                            KeyCode::Char('-') => app.draft_remove_slot(),
                             _ => {}
                         }
                         continue;
@@ -427,8 +747,10 @@ pub fn run_tui(mut app: App) -> io::Result<()> {
                         KeyCode::Up | KeyCode::Char('k') => app.select_prev(),
                         KeyCode::Down | KeyCode::Char('j') => app.select_next(),
                         KeyCode::Char('n') => app.open_create_draft(SimulationType::Single),
-                        KeyCode::Char('l') => app.open_create_draft(SimulationType::League4),
-                        KeyCode::Char('o') => app.open_create_draft(SimulationType::Knockout4),
+                        KeyCode::Char('l') => app.open_create_draft(SimulationType::League),
+                        KeyCode::Char('o') => app.open_create_draft(SimulationType::Knockout),
+                        KeyCode::Char('w') => app.open_create_draft(SimulationType::Tournament),
+                        KeyCode::Char('x') => app.open_create_draft(SimulationType::Swiss),
                         KeyCode::Char('s') => app.start_selected(),
                         KeyCode::Char('c') => app.clone_selected(),
                         KeyCode::Char('d') => app.delete_selected(),
@@ -462,11 +784,21 @@ pub fn run_tui(mut app: App) -> io::Result<()> {
 fn slot_count_for_mode(sim_type: SimulationType) -> usize {
     match sim_type {
         SimulationType::Single => 2,
-        SimulationType::League4 | SimulationType::Knockout4 => 4,
+        SimulationType::League4
+        | SimulationType::League
+        | SimulationType::Knockout4
+        | SimulationType::Knockout => 4,
+        SimulationType::Tournament => MIN_TOURNAMENT_TEAMS,
+        // Four teams in each pot.
+        SimulationType::Swiss => SwissFormat::default().pots() * 4,
     }
 }
 
-fn resolve_teams_from_slots(slots: &[TeamSlotDraft], seed: u64) -> Result<Vec<String>, String> {
+fn resolve_teams_from_slots(
+    slots: &[TeamSlotDraft],
+    pool: &TeamFilter,
+    seed: u64,
+) -> Result<Vec<String>, String> {
     let mut seen = HashSet::new();
     let mut cpu_count = 0usize;
 
@@ -483,14 +815,17 @@ fn resolve_teams_from_slots(slots: &[TeamSlotDraft], seed: u64) -> Result<Vec<St
         }
     }
 
-    let mut remaining: Vec<&str> = TEAMS
-        .iter()
-        .copied()
+    let mut remaining: Vec<&str> = pool
+        .teams()
+        .into_iter()
         .filter(|team| !seen.contains(team))
         .collect();
 
     if remaining.len() < cpu_count {
-        return Err("Not enough teams left for CPU auto-fill".to_string());
+        return Err(format!(
+            "Not enough {} left for CPU auto-fill",
+            pool.label()
+        ));
     }
 
     let mut rng = Rng::new(seed);
@@ -514,6 +849,7 @@ fn resolve_teams_from_slots(slots: &[TeamSlotDraft], seed: u64) -> Result<Vec<St
 pub fn resolve_quick_single_teams(
     home: Option<&str>,
     away: Option<&str>,
+    pool: &TeamFilter,
     selection_seed: u64,
 ) -> Result<Vec<String>, String> {
     let mut slots = vec![
@@ -542,5 +878,5 @@ pub fn resolve_quick_single_teams(
         slots[1].team_idx = idx;
     }
 
-    resolve_teams_from_slots(&slots, selection_seed)
+    resolve_teams_from_slots(&slots, pool, selection_seed)
 }
@@ -0,0 +1,524 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::thread;
+
+use serde::Serialize;
+
+use crate::data::display_name;
+use crate::export::write_row;
+use crate::sim::{run_simulation, MatchResult, SimConfig, SimOutcome, SimulationType};
+use crate::utils::{derive_seed, Rng};
+
+/// Scorelines shown in the text table; CSV and JSON list all of them.
+const TABLE_SCORELINES: usize = 10;
+
+/// Running totals for one team across every batch run.
+#[derive(Debug, Clone, Default)]
+pub struct TeamTally {
+    pub team: String,
+    pub matches: u32,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub goals_for: u32,
+    pub goals_against: u32,
+    pub xg_for: f64,
+    pub xg_against: f64,
+    pub titles: u32,
+}
+
+impl TeamTally {
+    fn record(&mut self, scored: u8, conceded: u8, xg_for: f64, xg_against: f64) {
+        self.matches += 1;
+        self.goals_for += scored as u32;
+        self.goals_against += conceded as u32;
+        self.xg_for += xg_for;
+        self.xg_against += xg_against;
+        if scored > conceded {
+            self.wins += 1;
+        } else if scored == conceded {
+            self.draws += 1;
+        } else {
+            self.losses += 1;
+        }
+    }
+
+    /// Share of this team's matches, as a percentage.
+    fn pct(&self, count: u32) -> f64 {
+        if self.matches == 0 {
+            0.0
+        } else {
+            count as f64 * 100.0 / self.matches as f64
+        }
+    }
+
+    fn per_match(&self, total: f64) -> f64 {
+        if self.matches == 0 {
+            0.0
+        } else {
+            total / self.matches as f64
+        }
+    }
+}
+
+/// Aggregated outcome of running one scenario many times.
+#[derive(Debug, Clone)]
+pub struct BatchReport {
+    pub sim_type: SimulationType,
+    pub config: SimConfig,
+    pub runs: u32,
+    pub base_seed: u64,
+    /// In the order the teams were given.
+    pub teams: Vec<TeamTally>,
+    /// Full-time scores (home-away, after extra time) across every match played.
+    pub scorelines: BTreeMap<(u8, u8), u32>,
+    pub matches: u32,
+}
+
+impl BatchReport {
+    fn new(sim_type: SimulationType, teams: &[String], config: &SimConfig, base_seed: u64) -> Self {
+        Self {
+            sim_type,
+            config: config.clone(),
+            runs: 0,
+            base_seed,
+            teams: teams
+                .iter()
+                .map(|t| TeamTally {
+                    team: t.clone(),
+                    ..TeamTally::default()
+                })
+                .collect(),
+            scorelines: BTreeMap::new(),
+            matches: 0,
+        }
+    }
+
+    fn team_mut(&mut self, team: &str) -> &mut TeamTally {
+        self.teams
+            .iter_mut()
+            .find(|t| t.team == team)
+            .expect("batch team in report")
+    }
+
+    fn record_match(&mut self, result: &MatchResult) {
+        let (home_xg, away_xg) = (result.stats.home.xg, result.stats.away.xg);
+        self.team_mut(&result.home)
+            .record(result.home_goals, result.away_goals, home_xg, away_xg);
+        self.team_mut(&result.away)
+            .record(result.away_goals, result.home_goals, away_xg, home_xg);
+        *self
+            .scorelines
+            .entry((result.home_goals, result.away_goals))
+            .or_insert(0) += 1;
+        self.matches += 1;
+    }
+
+    fn merge(&mut self, other: BatchReport) {
+        self.runs += other.runs;
+        self.matches += other.matches;
+        for (mine, theirs) in self.teams.iter_mut().zip(other.teams) {
+            mine.matches += theirs.matches;
+            mine.wins += theirs.wins;
+            mine.draws += theirs.draws;
+            mine.losses += theirs.losses;
+            mine.goals_for += theirs.goals_for;
+            mine.goals_against += theirs.goals_against;
+            mine.xg_for += theirs.xg_for;
+            mine.xg_against += theirs.xg_against;
+            mine.titles += theirs.titles;
+        }
+        for (score, count) in other.scorelines {
+            *self.scorelines.entry(score).or_insert(0) += count;
+        }
+    }
+
+    fn has_champion(&self) -> bool {
+        self.sim_type != SimulationType::Single
+    }
+
+    fn champion_pct(&self, tally: &TeamTally) -> f64 {
+        if self.runs == 0 {
+            0.0
+        } else {
+            tally.titles as f64 * 100.0 / self.runs as f64
+        }
+    }
+
+    /// Scorelines ordered from most to least common.
+    fn scorelines_by_count(&self) -> Vec<((u8, u8), u32)> {
+        let mut items: Vec<((u8, u8), u32)> =
+            self.scorelines.iter().map(|(s, c)| (*s, *c)).collect();
+        items.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        items
+    }
+
+    fn scoreline_pct(&self, count: u32) -> f64 {
+        if self.matches == 0 {
+            0.0
+        } else {
+            count as f64 * 100.0 / self.matches as f64
+        }
+    }
+}
+
+/// Play runs `first..first + count` of a scenario without playback frames.
+fn run_chunk(
+    sim_type: SimulationType,
+    teams: &[String],
+    config: &SimConfig,
+    base_seed: u64,
+    first: u32,
+    count: u32,
+) -> BatchReport {
+    let mut report = BatchReport::new(sim_type, teams, config, base_seed);
+    for run in first..first + count {
+        // Run 0 uses the same seed as `quick`/`export` with the same --seed.
+        let mut rng = Rng::new(derive_seed(base_seed, run as u64 + 1));
+        let prepared = run_simulation(sim_type, teams, config, &mut rng);
+        for result in &prepared.results {
+            report.record_match(result);
+        }
+        match &prepared.outcome {
+            SimOutcome::League { champion, .. }
+            | SimOutcome::Knockout { champion, .. }
+            | SimOutcome::Tournament { champion, .. }
+            | SimOutcome::Swiss {
+                leader: champion, ..
+            } => {
+                report.team_mut(champion).titles += 1;
+            }
+            SimOutcome::Single(_) => {}
+        }
+        report.runs += 1;
+    }
+    report
+}
+
+/// Run a scenario `runs` times with derived seeds, spread over `threads` worker threads.
+/// Each run derives its own seed, so the tallies depend only on the base seed, not the
+/// thread count.
+pub fn run_batch(
+    sim_type: SimulationType,
+    teams: &[String],
+    config: &SimConfig,
+    runs: u32,
+    base_seed: u64,
+    threads: usize,
+) -> BatchReport {
+    let config = SimConfig {
+        record_frames: false,
+        ..config.clone()
+    };
+    let threads = threads.clamp(1, runs.max(1) as usize) as u32;
+    let per_thread = runs / threads;
+    let remainder = runs % threads;
+
+    let partials: Vec<BatchReport> = thread::scope(|scope| {
+        let mut handles = Vec::new();
+        let mut first = 0;
+        for idx in 0..threads {
+            let count = per_thread + u32::from(idx < remainder);
+            let config = &config;
+            handles.push(
+                scope.spawn(move || run_chunk(sim_type, teams, config, base_seed, first, count)),
+            );
+            first += count;
+        }
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("batch worker panicked"))
+            .collect()
+    });
+
+    let mut report = BatchReport::new(sim_type, teams, &config, base_seed);
+    for partial in partials {
+        report.merge(partial);
+    }
+    report
+}
+
+pub fn default_threads() -> usize {
+    thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+pub fn batch_table_lines(report: &BatchReport) -> Vec<String> {
+    let mut lines = vec![
+        format!(
+            "Batch: {} x{} (seed {}, engine {})",
+            report.sim_type.as_str(),
+            report.runs,
+            report.base_seed,
+            report.config.engine.as_str()
+        ),
+        String::new(),
+    ];
+
+    let mut header = format!(
+        "{:<28} {:>7} {:>7} {:>7} {:>7} {:>6} {:>6} {:>6} {:>6}",
+        "Team", "Matches", "Win%", "Draw%", "Loss%", "GF", "GA", "xG", "xGA"
+    );
+    if report.has_champion() {
+        header.push_str(&format!(" {:>7}", "Champ%"));
+    }
+    lines.push(header);
+    for tally in &report.teams {
+        let mut line = format!(
+            "{:<28} {:>7} {:>7.1} {:>7.1} {:>7.1} {:>6.2} {:>6.2} {:>6.2} {:>6.2}",
+            display_name(&tally.team),
+            tally.matches,
+            tally.pct(tally.wins),
+            tally.pct(tally.draws),
+            tally.pct(tally.losses),
+            tally.per_match(tally.goals_for as f64),
+            tally.per_match(tally.goals_against as f64),
+            tally.per_match(tally.xg_for),
+            tally.per_match(tally.xg_against),
+        );
+        if report.has_champion() {
+            line.push_str(&format!(" {:>7.1}", report.champion_pct(tally)));
+        }
+        lines.push(line);
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "Most common scorelines (home-away, {} matches):",
+        report.matches
+    ));
+    for ((home, away), count) in report
+        .scorelines_by_count()
+        .into_iter()
+        .take(TABLE_SCORELINES)
+    {
+        lines.push(format!(
+            "  {}-{}  {:>5.1}%  ({})",
+            home,
+            away,
+            report.scoreline_pct(count),
+            count
+        ));
+    }
+    lines
+}
+
+pub fn batch_to_csv_bytes(report: &BatchReport) -> io::Result<Vec<u8>> {
+    let mut out: Vec<u8> = Vec::new();
+    write_row(
+        &mut out,
+        &[
+            "Team".to_string(),
+            "Matches".to_string(),
+            "Win %".to_string(),
+            "Draw %".to_string(),
+            "Loss %".to_string(),
+            "Avg GF".to_string(),
+            "Avg GA".to_string(),
+            "Avg xG".to_string(),
+            "Avg xGA".to_string(),
+            "Champion %".to_string(),
+        ],
+    )?;
+    for tally in &report.teams {
+        write_row(
+            &mut out,
+            &[
+                tally.team.clone(),
+                tally.matches.to_string(),
+                format!("{:.2}", tally.pct(tally.wins)),
+                format!("{:.2}", tally.pct(tally.draws)),
+                format!("{:.2}", tally.pct(tally.losses)),
+                format!("{:.3}", tally.per_match(tally.goals_for as f64)),
+                format!("{:.3}", tally.per_match(tally.goals_against as f64)),
+                format!("{:.3}", tally.per_match(tally.xg_for)),
+                format!("{:.3}", tally.per_match(tally.xg_against)),
+                if report.has_champion() {
+                    format!("{:.2}", report.champion_pct(tally))
+                } else {
+                    String::new()
+                },
+            ],
+        )?;
+    }
+
+    out.extend_from_slice(b"\n");
+    write_row(
+        &mut out,
+        &[
+            "Home Goals".to_string(),
+            "Away Goals".to_string(),
+            "Count".to_string(),
+            "Probability %".to_string(),
+        ],
+    )?;
+    for ((home, away), count) in report.scorelines_by_count() {
+        write_row(
+            &mut out,
+            &[
+                home.to_string(),
+                away.to_string(),
+                count.to_string(),
+                format!("{:.2}", report.scoreline_pct(count)),
+            ],
+        )?;
+    }
+    Ok(out)
+}
+
+/// JSON shape of a [`BatchReport`]; shares are 0-1 rather than percentages.
+#[derive(Debug, Serialize)]
+struct BatchJson<'a> {
+    mode: &'a str,
+    engine: &'a str,
+    runs: u32,
+    seed: u64,
+    matches: u32,
+    teams: Vec<TeamJson<'a>>,
+    scorelines: Vec<ScorelineJson>,
+}
+
+#[derive(Debug, Serialize)]
+struct TeamJson<'a> {
+    team: &'a str,
+    matches: u32,
+    win: f64,
+    draw: f64,
+    loss: f64,
+    avg_goals_for: f64,
+    avg_goals_against: f64,
+    avg_xg: f64,
+    avg_xg_against: f64,
+    /// `None` for modes without a champion.
+    champion: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct ScorelineJson {
+    home: u8,
+    away: u8,
+    count: u32,
+    probability: f64,
+}
+
+/// Round to `places` decimals so the JSON stays as short as the other formats.
+fn rounded(value: f64, places: i32) -> f64 {
+    let scale = 10f64.powi(places);
+    (value * scale).round() / scale
+}
+
+pub fn batch_to_json(report: &BatchReport) -> String {
+    let teams = report
+        .teams
+        .iter()
+        .map(|tally| TeamJson {
+            team: &tally.team,
+            matches: tally.matches,
+            win: rounded(tally.pct(tally.wins) / 100.0, 4),
+            draw: rounded(tally.pct(tally.draws) / 100.0, 4),
+            loss: rounded(tally.pct(tally.losses) / 100.0, 4),
+            avg_goals_for: rounded(tally.per_match(tally.goals_for as f64), 3),
+            avg_goals_against: rounded(tally.per_match(tally.goals_against as f64), 3),
+            avg_xg: rounded(tally.per_match(tally.xg_for), 3),
+            avg_xg_against: rounded(tally.per_match(tally.xg_against), 3),
+            champion: report
+                .has_champion()
+                .then(|| rounded(report.champion_pct(tally) / 100.0, 4)),
+        })
+        .collect();
+    let scorelines = report
+        .scorelines_by_count()
+        .into_iter()
+        .map(|((home, away), count)| ScorelineJson {
+            home,
+            away,
+            count,
+            probability: rounded(report.scoreline_pct(count) / 100.0, 4),
+        })
+        .collect();
+    let json = BatchJson {
+        mode: report.sim_type.as_str(),
+        engine: report.config.engine.as_str(),
+        runs: report.runs,
+        seed: report.base_seed,
+        matches: report.matches,
+        teams,
+        scorelines,
+    };
+    serde_json::to_string(&json).expect("batch report serializes to JSON")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn teams(names: &[&str]) -> Vec<String> {
+        names.iter().map(|t| t.to_string()).collect()
+    }
+
+    fn batch(sim_type: SimulationType, teams: &[String], runs: u32, threads: usize) -> BatchReport {
+        run_batch(sim_type, teams, &SimConfig::default(), runs, 11, threads)
+    }
+
+    #[test]
+    fn thread_count_does_not_change_the_report() {
+        let teams = teams(&["Arsenal", "Inter", "Real Madrid", "Bayern Munich"]);
+        let single = batch(SimulationType::League4, &teams, 13, 1);
+        for threads in [2, 3, 8, 64] {
+            let split = batch(SimulationType::League4, &teams, 13, threads);
+            assert_eq!((split.runs, split.matches), (single.runs, single.matches));
+            assert_eq!(split.scorelines, single.scorelines);
+            for (a, b) in split.teams.iter().zip(&single.teams) {
+                assert_eq!(
+                    (a.matches, a.wins, a.draws, a.losses, a.titles),
+                    (b.matches, b.wins, b.draws, b.losses, b.titles)
+                );
+                assert_eq!(
+                    (a.goals_for, a.goals_against),
+                    (b.goals_for, b.goals_against)
+                );
+                // Partial xG sums are added in a different order.
+                assert!((a.xg_for - b.xg_for).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn first_run_matches_a_single_simulation() {
+        let teams = teams(&["Arsenal", "Inter"]);
+        let report = batch(SimulationType::Single, &teams, 1, 4);
+        let mut rng = Rng::new(derive_seed(11, 1));
+        let config = SimConfig {
+            record_frames: false,
+            ..SimConfig::default()
+        };
+        let prepared = run_simulation(SimulationType::Single, &teams, &config, &mut rng);
+        let result = &prepared.results[0];
+        assert_eq!(
+            report.scorelines.keys().copied().collect::<Vec<_>>(),
+            vec![(result.home_goals, result.away_goals)]
+        );
+    }
+
+    #[test]
+    fn shares_add_up() {
+        let teams = teams(&["Arsenal", "Inter", "Real Madrid", "Bayern Munich"]);
+        let report = batch(SimulationType::Knockout4, &teams, 40, 3);
+        assert!(report.has_champion());
+        let titles: u32 = report.teams.iter().map(|t| t.titles).sum();
+        assert_eq!(titles, report.runs);
+        for tally in &report.teams {
+            let shares = tally.pct(tally.wins) + tally.pct(tally.draws) + tally.pct(tally.losses);
+            assert!((shares - 100.0).abs() < 1e-9, "{}: {shares}", tally.team);
+        }
+        let champions: f64 = report.teams.iter().map(|t| report.champion_pct(t)).sum();
+        assert!((champions - 100.0).abs() < 1e-9);
+        let scorelines: u32 = report.scorelines.values().sum();
+        assert_eq!(scorelines, report.matches);
+
+        let single = batch(SimulationType::Single, &teams[..2], 5, 2);
+        assert!(!single.has_champion());
+        assert_eq!((single.runs, single.matches), (5, 5));
+    }
+}
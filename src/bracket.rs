@@ -0,0 +1,285 @@
+use crate::data::display_name;
+use crate::utils::Rng;
+
+/// How teams are placed into a knockout bracket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DrawKind {
+    /// Teams are seeded in the order given; top seeds get any byes.
+    #[default]
+    Seeded,
+    /// Teams are shuffled before seeding.
+    Random,
+    /// Teams are drawn from seeding pots under separation rules; see [`crate::draw`].
+    Pots,
+}
+
+impl DrawKind {
+    pub const ALL: [DrawKind; 3] = [DrawKind::Seeded, DrawKind::Random, DrawKind::Pots];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DrawKind::Seeded => "seeded",
+            DrawKind::Random => "random",
+            DrawKind::Pots => "pots",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DrawKind::Seeded => "Seeded",
+            DrawKind::Random => "Random draw",
+            DrawKind::Pots => "Pot draw",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<DrawKind> {
+        DrawKind::ALL.into_iter().find(|d| d.as_str() == value)
+    }
+}
+
+/// Seed index at each bracket position for a bracket of `size` (a power of two),
+/// so seeds 1 and 2 can only meet in the final, 1-4 in the semis, and so on.
+pub fn seed_order(size: usize) -> Vec<usize> {
+    let mut order = vec![0];
+    while order.len() < size {
+        let n = order.len() * 2;
+        order = order.iter().flat_map(|&s| [s, n - 1 - s]).collect();
+    }
+    order
+}
+
+/// Short tie label prefix for a round with `teams_in_round` slots: R16, QF, SF, Final.
+pub fn round_code(teams_in_round: usize) -> String {
+    match teams_in_round {
+        2 => "Final".to_string(),
+        4 => "SF".to_string(),
+        8 => "QF".to_string(),
+        n => format!("R{n}"),
+    }
+}
+
+pub fn round_name(teams_in_round: usize) -> String {
+    match teams_in_round {
+        2 => "Final".to_string(),
+        4 => "Semi-finals".to_string(),
+        8 => "Quarter-finals".to_string(),
+        n => format!("Round of {n}"),
+    }
+}
+
+/// One slot pairing in the bracket. A first-round tie with no away side is a bye.
+#[derive(Debug, Clone, Default)]
+pub struct BracketTie {
+    pub label: String,
+    pub home: Option<String>,
+    pub away: Option<String>,
+    /// Result line once played.
+    pub line: Option<String>,
+    pub winner: Option<String>,
+}
+
+impl BracketTie {
+    pub fn is_bye(&self) -> bool {
+        self.winner.is_some() && self.line.is_none()
+    }
+
+    fn text(&self) -> String {
+        if let Some(line) = &self.line {
+            return line.clone();
+        }
+        let side = |team: &Option<String>| {
+            team.as_deref()
+                .map(display_name)
+                .unwrap_or_else(|| "TBD".to_string())
+        };
+        if self.is_bye() {
+            format!("{}: {} (bye)", self.label, side(&self.home))
+        } else {
+            format!(
+                "{}: {} vs {}",
+                self.label,
+                side(&self.home),
+                side(&self.away)
+            )
+        }
+    }
+}
+
+/// Single-elimination bracket; `rounds[0]` is the first round and the last round
+/// holds the final.
+#[derive(Debug, Clone)]
+pub struct Bracket {
+    pub team_count: usize,
+    pub rounds: Vec<Vec<BracketTie>>,
+}
+
+impl Bracket {
+    /// Place `teams` into a bracket after drawing them with `kind`. Pot draws are
+    /// made by [`crate::draw::draw_bracket_from_pots`]; here they keep the given order.
+    pub fn draw(teams: &[String], kind: DrawKind, rng: &mut Rng) -> Self {
+        let mut pool = teams.to_vec();
+        if kind == DrawKind::Random {
+            rng.shuffle(&mut pool);
+        }
+        Self::seeded(&pool)
+    }
+
+    /// Bracket with `teams` seeded in order, padded to a power of two with byes.
+    pub fn seeded(teams: &[String]) -> Self {
+        let size = teams.len().max(2).next_power_of_two();
+        let order = seed_order(size);
+
+        let mut rounds = Vec::new();
+        let mut slots = size;
+        while slots >= 2 {
+            let ties = slots / 2;
+            let code = round_code(slots);
+            rounds.push(
+                (0..ties)
+                    .map(|i| BracketTie {
+                        label: if ties == 1 {
+                            code.clone()
+                        } else {
+                            format!("{} {}", code, i + 1)
+                        },
+                        ..BracketTie::default()
+                    })
+                    .collect::<Vec<_>>(),
+            );
+            slots /= 2;
+        }
+
+        let mut bracket = Self {
+            team_count: teams.len(),
+            rounds,
+        };
+        for (idx, pair) in order.chunks(2).enumerate() {
+            let tie = &mut bracket.rounds[0][idx];
+            tie.home = teams.get(pair[0]).cloned();
+            tie.away = teams.get(pair[1]).cloned();
+            if tie.away.is_none() {
+                let team = tie.home.clone().expect("byes only face seeded teams");
+                tie.winner = Some(team.clone());
+                bracket.advance(0, idx, team);
+            }
+        }
+        bracket
+    }
+
+    /// Record a played tie and move its winner into the next round.
+    pub fn set_result(&mut self, round: usize, idx: usize, winner: String, line: String) {
+        let tie = &mut self.rounds[round][idx];
+        tie.winner = Some(winner.clone());
+        tie.line = Some(line);
+        self.advance(round, idx, winner);
+    }
+
+    fn advance(&mut self, round: usize, idx: usize, team: String) {
+        if let Some(next) = self.rounds.get_mut(round + 1) {
+            let tie = &mut next[idx / 2];
+            if idx.is_multiple_of(2) {
+                tie.home = Some(team);
+            } else {
+                tie.away = Some(team);
+            }
+        }
+    }
+
+    pub fn champion(&self) -> Option<&str> {
+        self.rounds.last()?.first()?.winner.as_deref()
+    }
+
+    /// Sideways bracket: each round is indented one step further and sits between
+    /// the two ties that feed it.
+    pub fn lines(&self) -> Vec<String> {
+        let mut out = vec![format!("Knockout Bracket ({} teams)", self.team_count)];
+        if let Some(last) = self.rounds.len().checked_sub(1) {
+            self.push_lines(last, 0, &mut out);
+        }
+        out.push(format!(
+            "Champion: {}",
+            self.champion()
+                .map(|c| format!("{} 🏆", display_name(c)))
+                .unwrap_or_else(|| "TBD".to_string())
+        ));
+        out
+    }
+
+    fn push_lines(&self, round: usize, idx: usize, out: &mut Vec<String>) {
+        if round > 0 {
+            self.push_lines(round - 1, idx * 2, out);
+        }
+        let text = self.rounds[round][idx].text();
+        if round == 0 {
+            out.push(text);
+        } else {
+            out.push(format!("{}  +-- {}", "    ".repeat(round - 1), text));
+        }
+        if round > 0 {
+            self.push_lines(round - 1, idx * 2 + 1, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn teams(n: usize) -> Vec<String> {
+        (1..=n).map(|i| format!("Seed {i}")).collect()
+    }
+
+    #[test]
+    fn seed_order_keeps_top_seeds_apart() {
+        assert_eq!(seed_order(2), vec![0, 1]);
+        assert_eq!(seed_order(4), vec![0, 3, 1, 2]);
+        assert_eq!(seed_order(8), vec![0, 7, 3, 4, 1, 6, 2, 5]);
+        let order = seed_order(16);
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..16).collect::<Vec<_>>());
+        // Seeds 1 and 2 sit in opposite halves, seeds 1-4 in different quarters.
+        assert!(order[..8].contains(&0) && order[8..].contains(&1));
+        for quarter in order.chunks(4) {
+            assert_eq!(quarter.iter().filter(|&&s| s < 4).count(), 1);
+        }
+    }
+
+    #[test]
+    fn byes_go_to_the_top_seeds() {
+        let bracket = Bracket::seeded(&teams(6));
+        assert_eq!(bracket.rounds.len(), 3);
+        let byes: Vec<&str> = bracket.rounds[0]
+            .iter()
+            .filter(|t| t.is_bye())
+            .filter_map(|t| t.home.as_deref())
+            .collect();
+        assert_eq!(byes, vec!["Seed 1", "Seed 2"]);
+        for tie in bracket.rounds[0].iter().filter(|t| !t.is_bye()) {
+            assert!(tie.home.is_some() && tie.away.is_some());
+        }
+        // Bye winners are already through to the semi-finals.
+        assert_eq!(bracket.rounds[1][0].home.as_deref(), Some("Seed 1"));
+        assert_eq!(bracket.rounds[1][1].home.as_deref(), Some("Seed 2"));
+    }
+
+    #[test]
+    fn full_bracket_has_no_byes() {
+        let bracket = Bracket::seeded(&teams(8));
+        assert!(bracket.rounds[0].iter().all(|t| !t.is_bye()));
+        assert_eq!(bracket.rounds[0][0].home.as_deref(), Some("Seed 1"));
+        assert_eq!(bracket.rounds[0][0].away.as_deref(), Some("Seed 8"));
+    }
+
+    #[test]
+    fn results_advance_to_the_final() {
+        let mut bracket = Bracket::seeded(&teams(4));
+        bracket.set_result(0, 0, "Seed 1".to_string(), "SF 1".to_string());
+        bracket.set_result(0, 1, "Seed 3".to_string(), "SF 2".to_string());
+        let final_tie = &bracket.rounds[1][0];
+        assert_eq!(final_tie.home.as_deref(), Some("Seed 1"));
+        assert_eq!(final_tie.away.as_deref(), Some("Seed 3"));
+        bracket.set_result(1, 0, "Seed 3".to_string(), "Final".to_string());
+        assert_eq!(bracket.champion(), Some("Seed 3"));
+    }
+}
@@ -53,12 +53,164 @@ pub const TACTICS: [Tactic; 4] = [
     },
 ];
 
+/// Outfield shape parsed from a formation string such as "4-2-3-1".
+/// Every line between the back line and the forward line counts as midfield.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Formation {
+    pub defenders: u8,
+    pub midfielders: u8,
+    pub forwards: u8,
+}
+
+impl Formation {
+    /// Parse a formation of 3-5 single-digit lines (each 1-6) adding up to ten outfield players.
+    pub const fn parse(formation: &str) -> Option<Formation> {
+        let bytes = formation.as_bytes();
+        if bytes.len().is_multiple_of(2) {
+            return None;
+        }
+
+        let mut lines = [0u8; 5];
+        let mut count = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let c = bytes[i];
+            if i.is_multiple_of(2) {
+                if c < b'1' || c > b'6' || count == lines.len() {
+                    return None;
+                }
+                lines[count] = c - b'0';
+                count += 1;
+            } else if c != b'-' {
+                return None;
+            }
+            i += 1;
+        }
+        if count < 3 {
+            return None;
+        }
+
+        let mut midfielders = 0;
+        let mut j = 1;
+        while j < count - 1 {
+            midfielders += lines[j];
+            j += 1;
+        }
+        let defenders = lines[0];
+        let forwards = lines[count - 1];
+        if defenders + midfielders + forwards != 10 {
+            return None;
+        }
+
+        Some(Formation {
+            defenders,
+            midfielders,
+            forwards,
+        })
+    }
+
+    /// Share multiplier for the attacking-possession roll; crowded midfields win more of it.
+    pub fn midfield_control(self) -> f64 {
+        1.0 + 0.05 * (self.midfielders as f64 - 4.0)
+    }
+
+    /// xG multiplier conceded on fast breaks; a back three is more exposed than a back four.
+    pub fn counter_exposure(self) -> f64 {
+        1.0 + 0.12 * (4.0 - self.defenders as f64)
+    }
+
+    /// Shot-rate multiplier from the number of forwards.
+    pub fn shot_threat(self) -> f64 {
+        1.0 + 0.05 * (self.forwards as f64 - 2.0)
+    }
+}
+
+/// Whether a team is a club or represents a country.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TeamKind {
+    Club,
+    National,
+}
+
+impl TeamKind {
+    pub const ALL: [TeamKind; 2] = [TeamKind::Club, TeamKind::National];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TeamKind::Club => "club",
+            TeamKind::National => "national",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TeamKind::Club => "Club",
+            TeamKind::National => "National team",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<TeamKind> {
+        TeamKind::ALL.into_iter().find(|k| k.as_str() == value)
+    }
+}
+
+/// Continental confederation a team plays under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confederation {
+    Afc,
+    Caf,
+    Concacaf,
+    Conmebol,
+    Ofc,
+    Uefa,
+}
+
+impl Confederation {
+    pub const ALL: [Confederation; 6] = [
+        Confederation::Afc,
+        Confederation::Caf,
+        Confederation::Concacaf,
+        Confederation::Conmebol,
+        Confederation::Ofc,
+        Confederation::Uefa,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Confederation::Afc => "AFC",
+            Confederation::Caf => "CAF",
+            Confederation::Concacaf => "CONCACAF",
+            Confederation::Conmebol => "CONMEBOL",
+            Confederation::Ofc => "OFC",
+            Confederation::Uefa => "UEFA",
+        }
+    }
+
+    /// Accepts any letter case ("UEFA", "uefa").
+    pub fn parse(value: &str) -> Option<Confederation> {
+        Confederation::ALL
+            .into_iter()
+            .find(|c| c.as_str().eq_ignore_ascii_case(value))
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Team {
     pub name: &'static str,
     pub flag: &'static str,
+    pub kind: TeamKind,
+    /// Country the team represents or plays in; clubs and national teams of one
+    /// country share it.
+    pub country: &'static str,
+    pub confederation: Confederation,
+    /// Domestic league for clubs; national teams have none.
+    pub league: Option<&'static str>,
     pub formation: &'static str,
     pub tactic: &'static str,
+    pub attack: u8,
+    pub midfield: u8,
+    pub defence: u8,
+    pub goalkeeper: u8,
 }
 
 pub const TEAMS_DATA: [Team; 85] = [
@@ -66,521 +218,1218 @@ pub const TEAMS_DATA: [Team; 85] = [
     Team {
         name: "Kashima Antlers",
         flag: "🇯🇵",
+        kind: TeamKind::Club,
+        country: "Japan",
+        confederation: Confederation::Afc,
+        league: Some("J.League"),
         formation: "4-4-2",
         tactic: "counter",
+        attack: 70,
+        midfield: 72,
+        defence: 72,
+        goalkeeper: 72,
     },
     Team {
         name: "Urawa Red Diamonds",
         flag: "🇯🇵",
+        kind: TeamKind::Club,
+        country: "Japan",
+        confederation: Confederation::Afc,
+        league: Some("J.League"),
         formation: "4-2-3-1",
         tactic: "possession",
+        attack: 73,
+        midfield: 74,
+        defence: 68,
+        goalkeeper: 70,
     },
     Team {
         name: "Gamba Osaka",
         flag: "🇯🇵",
+        kind: TeamKind::Club,
+        country: "Japan",
+        confederation: Confederation::Afc,
+        league: Some("J.League"),
         formation: "4-4-2",
         tactic: "counter",
+        attack: 72,
+        midfield: 69,
+        defence: 68,
+        goalkeeper: 69,
     },
     Team {
         name: "Cerezo Osaka",
         flag: "🇯🇵",
+        kind: TeamKind::Club,
+        country: "Japan",
+        confederation: Confederation::Afc,
+        league: Some("J.League"),
         formation: "4-4-2",
         tactic: "counter",
+        attack: 70,
+        midfield: 66,
+        defence: 68,
+        goalkeeper: 70,
     },
     Team {
         name: "Kawasaki Frontale",
         flag: "🇯🇵",
+        kind: TeamKind::Club,
+        country: "Japan",
+        confederation: Confederation::Afc,
+        league: Some("J.League"),
         formation: "4-3-3",
         tactic: "possession",
+        attack: 73,
+        midfield: 73,
+        defence: 72,
+        goalkeeper: 74,
     },
     Team {
         name: "Yokohama F. Marinos",
         flag: "🇯🇵",
+        kind: TeamKind::Club,
+        country: "Japan",
+        confederation: Confederation::Afc,
+        league: Some("J.League"),
         formation: "4-3-3",
         tactic: "high_press",
+        attack: 75,
+        midfield: 76,
+        defence: 70,
+        goalkeeper: 74,
     },
     Team {
         name: "Nagoya Grampus",
         flag: "🇯🇵",
+        kind: TeamKind::Club,
+        country: "Japan",
+        confederation: Confederation::Afc,
+        league: Some("J.League"),
         formation: "4-2-3-1",
         tactic: "low_block",
+        attack: 69,
+        midfield: 71,
+        defence: 70,
+        goalkeeper: 68,
     },
     Team {
         name: "Shimizu S-Pulse",
         flag: "🇯🇵",
+        kind: TeamKind::Club,
+        country: "Japan",
+        confederation: Confederation::Afc,
+        league: Some("J.League"),
         formation: "4-4-2",
         tactic: "counter",
+        attack: 69,
+        midfield: 66,
+        defence: 66,
+        goalkeeper: 66,
     },
     Team {
         name: "Sanfrecce Hiroshima",
         flag: "🇯🇵",
+        kind: TeamKind::Club,
+        country: "Japan",
+        confederation: Confederation::Afc,
+        league: Some("J.League"),
         formation: "3-5-2",
         tactic: "possession",
+        attack: 72,
+        midfield: 73,
+        defence: 70,
+        goalkeeper: 72,
     },
     Team {
         name: "Consadole Sapporo",
         flag: "🇯🇵",
+        kind: TeamKind::Club,
+        country: "Japan",
+        confederation: Confederation::Afc,
+        league: Some("J.League"),
         formation: "3-5-2",
         tactic: "high_press",
+        attack: 66,
+        midfield: 67,
+        defence: 63,
+        goalkeeper: 63,
     },
     Team {
         name: "Ventforet Kofu",
         flag: "🇯🇵",
+        kind: TeamKind::Club,
+        country: "Japan",
+        confederation: Confederation::Afc,
+        league: Some("J.League"),
         formation: "4-4-2",
         tactic: "counter",
+        attack: 63,
+        midfield: 60,
+        defence: 59,
+        goalkeeper: 58,
     },
     Team {
         name: "Tokyo Verdy",
         flag: "🇯🇵",
+        kind: TeamKind::Club,
+        country: "Japan",
+        confederation: Confederation::Afc,
+        league: Some("J.League"),
         formation: "4-3-3",
         tactic: "possession",
+        attack: 63,
+        midfield: 68,
+        defence: 65,
+        goalkeeper: 65,
     },
     Team {
         name: "JEF United Chiba",
         flag: "🇯🇵",
+        kind: TeamKind::Club,
+        country: "Japan",
+        confederation: Confederation::Afc,
+        league: Some("J.League"),
         formation: "4-3-3",
         tactic: "counter",
+        attack: 63,
+        midfield: 59,
+        defence: 60,
+        goalkeeper: 63,
     },
     // European Clubs
     Team {
         name: "Arsenal",
         flag: "🇬🇧",
+        kind: TeamKind::Club,
+        country: "England",
+        confederation: Confederation::Uefa,
+        league: Some("Premier League"),
         formation: "4-3-3",
         tactic: "possession",
+        attack: 83,
+        midfield: 86,
+        defence: 82,
+        goalkeeper: 83,
     },
     Team {
         name: "FC Barcelona",
         flag: "🇪🇸",
+        kind: TeamKind::Club,
+        country: "Spain",
+        confederation: Confederation::Uefa,
+        league: Some("La Liga"),
         formation: "4-3-3",
         tactic: "possession",
+        attack: 83,
+        midfield: 86,
+        defence: 82,
+        goalkeeper: 83,
     },
     Team {
         name: "Real Madrid",
         flag: "🇪🇸",
+        kind: TeamKind::Club,
+        country: "Spain",
+        confederation: Confederation::Uefa,
+        league: Some("La Liga"),
         formation: "4-3-3",
         tactic: "counter",
+        attack: 91,
+        midfield: 85,
+        defence: 89,
+        goalkeeper: 88,
     },
     Team {
         name: "Manchester City",
         flag: "🇬🇧",
+        kind: TeamKind::Club,
+        country: "England",
+        confederation: Confederation::Uefa,
+        league: Some("Premier League"),
         formation: "4-3-3",
         tactic: "possession",
+        attack: 86,
+        midfield: 91,
+        defence: 85,
+        goalkeeper: 89,
     },
     Team {
         name: "Manchester United",
         flag: "🇬🇧",
+        kind: TeamKind::Club,
+        country: "England",
+        confederation: Confederation::Uefa,
+        league: Some("Premier League"),
         formation: "4-2-3-1",
         tactic: "high_press",
+        attack: 82,
+        midfield: 82,
+        defence: 78,
+        goalkeeper: 81,
     },
     Team {
         name: "Liverpool",
         flag: "🇬🇧",
+        kind: TeamKind::Club,
+        country: "England",
+        confederation: Confederation::Uefa,
+        league: Some("Premier League"),
         formation: "4-3-3",
         tactic: "high_press",
+        attack: 89,
+        midfield: 85,
+        defence: 83,
+        goalkeeper: 88,
     },
     Team {
         name: "Bayern Munich",
         flag: "🇩🇪",
+        kind: TeamKind::Club,
+        country: "Germany",
+        confederation: Confederation::Uefa,
+        league: Some("Bundesliga"),
         formation: "4-2-3-1",
         tactic: "high_press",
+        attack: 86,
+        midfield: 88,
+        defence: 86,
+        goalkeeper: 86,
     },
     Team {
         name: "Borussia Dortmund",
         flag: "🇩🇪",
+        kind: TeamKind::Club,
+        country: "Germany",
+        confederation: Confederation::Uefa,
+        league: Some("Bundesliga"),
         formation: "4-2-3-1",
         tactic: "high_press",
+        attack: 80,
+        midfield: 79,
+        defence: 76,
+        goalkeeper: 78,
     },
     Team {
         name: "Paris Saint-Germain",
         flag: "🇫🇷",
+        kind: TeamKind::Club,
+        country: "France",
+        confederation: Confederation::Uefa,
+        league: Some("Ligue 1"),
         formation: "4-3-3",
         tactic: "possession",
+        attack: 84,
+        midfield: 85,
+        defence: 85,
+        goalkeeper: 84,
     },
     Team {
         name: "Juventus",
         flag: "🇮🇹",
+        kind: TeamKind::Club,
+        country: "Italy",
+        confederation: Confederation::Uefa,
+        league: Some("Serie A"),
         formation: "3-5-2",
         tactic: "low_block",
+        attack: 77,
+        midfield: 79,
+        defence: 83,
+        goalkeeper: 81,
     },
     Team {
         name: "Inter",
         flag: "🇮🇹",
+        kind: TeamKind::Club,
+        country: "Italy",
+        confederation: Confederation::Uefa,
+        league: Some("Serie A"),
         formation: "3-5-2",
         tactic: "low_block",
+        attack: 80,
+        midfield: 83,
+        defence: 84,
+        goalkeeper: 86,
     },
     Team {
         name: "AC Milan",
         flag: "🇮🇹",
+        kind: TeamKind::Club,
+        country: "Italy",
+        confederation: Confederation::Uefa,
+        league: Some("Serie A"),
         formation: "4-2-3-1",
         tactic: "possession",
+        attack: 78,
+        midfield: 81,
+        defence: 79,
+        goalkeeper: 79,
     },
     Team {
         name: "Ajax",
         flag: "🇳🇱",
+        kind: TeamKind::Club,
+        country: "Netherlands",
+        confederation: Confederation::Uefa,
+        league: Some("Eredivisie"),
         formation: "4-3-3",
         tactic: "possession",
+        attack: 76,
+        midfield: 76,
+        defence: 73,
+        goalkeeper: 75,
     },
     Team {
         name: "Benfica",
         flag: "🇵🇹",
+        kind: TeamKind::Club,
+        country: "Portugal",
+        confederation: Confederation::Uefa,
+        league: Some("Primeira Liga"),
         formation: "4-2-3-1",
         tactic: "possession",
+        attack: 79,
+        midfield: 77,
+        defence: 77,
+        goalkeeper: 75,
     },
     Team {
         name: "Porto",
         flag: "🇵🇹",
+        kind: TeamKind::Club,
+        country: "Portugal",
+        confederation: Confederation::Uefa,
+        league: Some("Primeira Liga"),
         formation: "4-4-2",
         tactic: "counter",
+        attack: 78,
+        midfield: 75,
+        defence: 74,
+        goalkeeper: 77,
     },
     Team {
         name: "Celtic",
         flag: "🇬🇧",
+        kind: TeamKind::Club,
+        country: "Scotland",
+        confederation: Confederation::Uefa,
+        league: Some("Scottish Premiership"),
         formation: "4-3-3",
         tactic: "possession",
+        attack: 71,
+        midfield: 74,
+        defence: 73,
+        goalkeeper: 74,
     },
     // UEFA National Teams
     Team {
         name: "England",
         flag: "🇬🇧",
+        kind: TeamKind::National,
+        country: "England",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-2-3-1",
         tactic: "high_press",
+        attack: 87,
+        midfield: 90,
+        defence: 87,
+        goalkeeper: 85,
     },
     Team {
         name: "France",
         flag: "🇫🇷",
+        kind: TeamKind::National,
+        country: "France",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-2-3-1",
         tactic: "high_press",
+        attack: 88,
+        midfield: 88,
+        defence: 87,
+        goalkeeper: 90,
     },
     Team {
         name: "Spain",
         flag: "🇪🇸",
+        kind: TeamKind::National,
+        country: "Spain",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-3-3",
         tactic: "possession",
+        attack: 88,
+        midfield: 88,
+        defence: 88,
+        goalkeeper: 88,
     },
     Team {
         name: "Germany",
         flag: "🇩🇪",
+        kind: TeamKind::National,
+        country: "Germany",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-2-3-1",
         tactic: "high_press",
+        attack: 87,
+        midfield: 83,
+        defence: 82,
+        goalkeeper: 82,
     },
     Team {
         name: "Italy",
         flag: "🇮🇹",
+        kind: TeamKind::National,
+        country: "Italy",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-4-2",
         tactic: "counter",
+        attack: 84,
+        midfield: 83,
+        defence: 80,
+        goalkeeper: 83,
     },
     Team {
         name: "Portugal",
         flag: "🇵🇹",
+        kind: TeamKind::National,
+        country: "Portugal",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-3-3",
         tactic: "possession",
+        attack: 87,
+        midfield: 86,
+        defence: 82,
+        goalkeeper: 85,
     },
     Team {
         name: "Netherlands",
         flag: "🇳🇱",
+        kind: TeamKind::National,
+        country: "Netherlands",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-3-3",
         tactic: "possession",
+        attack: 81,
+        midfield: 85,
+        defence: 84,
+        goalkeeper: 84,
     },
     Team {
         name: "Belgium",
         flag: "🇧🇪",
+        kind: TeamKind::National,
+        country: "Belgium",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-2-3-1",
         tactic: "high_press",
+        attack: 85,
+        midfield: 84,
+        defence: 78,
+        goalkeeper: 79,
     },
     Team {
         name: "Croatia",
         flag: "🇭🇷",
+        kind: TeamKind::National,
+        country: "Croatia",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-4-2",
         tactic: "counter",
+        attack: 79,
+        midfield: 80,
+        defence: 78,
+        goalkeeper: 78,
     },
     Team {
         name: "Denmark",
         flag: "🇩🇰",
+        kind: TeamKind::National,
+        country: "Denmark",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-4-2",
         tactic: "counter",
+        attack: 81,
+        midfield: 79,
+        defence: 77,
+        goalkeeper: 76,
     },
     Team {
         name: "Switzerland",
         flag: "🇨🇭",
+        kind: TeamKind::National,
+        country: "Switzerland",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-4-2",
         tactic: "counter",
+        attack: 78,
+        midfield: 76,
+        defence: 76,
+        goalkeeper: 79,
     },
     Team {
         name: "Austria",
         flag: "🇦🇹",
+        kind: TeamKind::National,
+        country: "Austria",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 78,
+        midfield: 77,
+        defence: 76,
+        goalkeeper: 75,
     },
     Team {
         name: "Sweden",
         flag: "🇸🇪",
+        kind: TeamKind::National,
+        country: "Sweden",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 77,
+        midfield: 74,
+        defence: 76,
+        goalkeeper: 76,
     },
     Team {
         name: "Norway",
         flag: "🇳🇴",
+        kind: TeamKind::National,
+        country: "Norway",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 78,
+        midfield: 73,
+        defence: 74,
+        goalkeeper: 77,
     },
     Team {
         name: "Poland",
         flag: "🇵🇱",
+        kind: TeamKind::National,
+        country: "Poland",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 78,
+        midfield: 72,
+        defence: 76,
+        goalkeeper: 77,
     },
     Team {
         name: "Serbia",
         flag: "🇷🇸",
+        kind: TeamKind::National,
+        country: "Serbia",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 75,
+        midfield: 72,
+        defence: 76,
+        goalkeeper: 77,
     },
     Team {
         name: "Turkey",
         flag: "🇹🇷",
+        kind: TeamKind::National,
+        country: "Turkey",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 79,
+        midfield: 77,
+        defence: 75,
+        goalkeeper: 78,
     },
     Team {
         name: "Ukraine",
         flag: "🇺🇦",
+        kind: TeamKind::National,
+        country: "Ukraine",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 75,
+        midfield: 72,
+        defence: 73,
+        goalkeeper: 73,
     },
     Team {
         name: "Czech Republic",
         flag: "🇨🇿",
+        kind: TeamKind::National,
+        country: "Czech Republic",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 75,
+        midfield: 71,
+        defence: 74,
+        goalkeeper: 72,
     },
     Team {
         name: "Scotland",
         flag: "🇬🇧",
+        kind: TeamKind::National,
+        country: "Scotland",
+        confederation: Confederation::Uefa,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 72,
+        midfield: 71,
+        defence: 74,
+        goalkeeper: 73,
     },
     // CONMEBOL National Teams
     Team {
         name: "Argentina",
         flag: "🇦🇷",
+        kind: TeamKind::National,
+        country: "Argentina",
+        confederation: Confederation::Conmebol,
+        league: None,
         formation: "4-2-3-1",
         tactic: "high_press",
+        attack: 87,
+        midfield: 88,
+        defence: 87,
+        goalkeeper: 89,
     },
     Team {
         name: "Brazil",
         flag: "🇧🇷",
+        kind: TeamKind::National,
+        country: "Brazil",
+        confederation: Confederation::Conmebol,
+        league: None,
         formation: "4-2-3-1",
         tactic: "high_press",
+        attack: 87,
+        midfield: 85,
+        defence: 82,
+        goalkeeper: 88,
     },
     Team {
         name: "Uruguay",
         flag: "🇺🇾",
+        kind: TeamKind::National,
+        country: "Uruguay",
+        confederation: Confederation::Conmebol,
+        league: None,
         formation: "4-4-2",
         tactic: "counter",
+        attack: 79,
+        midfield: 78,
+        defence: 81,
+        goalkeeper: 79,
     },
     Team {
         name: "Colombia",
         flag: "🇨🇴",
+        kind: TeamKind::National,
+        country: "Colombia",
+        confederation: Confederation::Conmebol,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 79,
+        midfield: 78,
+        defence: 79,
+        goalkeeper: 79,
     },
     Team {
         name: "Chile",
         flag: "🇨🇱",
+        kind: TeamKind::National,
+        country: "Chile",
+        confederation: Confederation::Conmebol,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 73,
+        midfield: 69,
+        defence: 73,
+        goalkeeper: 72,
     },
     Team {
         name: "Peru",
         flag: "🇵🇪",
+        kind: TeamKind::National,
+        country: "Peru",
+        confederation: Confederation::Conmebol,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 71,
+        midfield: 68,
+        defence: 70,
+        goalkeeper: 70,
     },
     Team {
         name: "Ecuador",
         flag: "🇪🇨",
+        kind: TeamKind::National,
+        country: "Ecuador",
+        confederation: Confederation::Conmebol,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 75,
+        midfield: 74,
+        defence: 75,
+        goalkeeper: 74,
     },
     Team {
         name: "Paraguay",
         flag: "🇵🇾",
+        kind: TeamKind::National,
+        country: "Paraguay",
+        confederation: Confederation::Conmebol,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 73,
+        midfield: 71,
+        defence: 69,
+        goalkeeper: 71,
     },
     Team {
         name: "Bolivia",
         flag: "🇧🇴",
+        kind: TeamKind::National,
+        country: "Bolivia",
+        confederation: Confederation::Conmebol,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 64,
+        midfield: 62,
+        defence: 62,
+        goalkeeper: 62,
     },
     Team {
         name: "Venezuela",
         flag: "🇻🇪",
+        kind: TeamKind::National,
+        country: "Venezuela",
+        confederation: Confederation::Conmebol,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 70,
+        midfield: 67,
+        defence: 70,
+        goalkeeper: 70,
     },
     // CONCACAF National Teams
     Team {
         name: "United States",
         flag: "🇺🇸",
+        kind: TeamKind::National,
+        country: "United States",
+        confederation: Confederation::Concacaf,
+        league: None,
         formation: "4-2-3-1",
         tactic: "high_press",
+        attack: 80,
+        midfield: 79,
+        defence: 73,
+        goalkeeper: 76,
     },
     Team {
         name: "Mexico",
         flag: "🇲🇽",
+        kind: TeamKind::National,
+        country: "Mexico",
+        confederation: Confederation::Concacaf,
+        league: None,
         formation: "4-4-2",
         tactic: "counter",
+        attack: 75,
+        midfield: 75,
+        defence: 78,
+        goalkeeper: 78,
     },
     Team {
         name: "Canada",
         flag: "🇨🇦",
+        kind: TeamKind::National,
+        country: "Canada",
+        confederation: Confederation::Concacaf,
+        league: None,
         formation: "4-4-2",
         tactic: "counter",
+        attack: 74,
+        midfield: 73,
+        defence: 74,
+        goalkeeper: 76,
     },
     Team {
         name: "Costa Rica",
         flag: "🇨🇷",
+        kind: TeamKind::National,
+        country: "Costa Rica",
+        confederation: Confederation::Concacaf,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 67,
+        midfield: 66,
+        defence: 69,
+        goalkeeper: 70,
     },
     Team {
         name: "Panama",
         flag: "🇵🇦",
+        kind: TeamKind::National,
+        country: "Panama",
+        confederation: Confederation::Concacaf,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 71,
+        midfield: 69,
+        defence: 69,
+        goalkeeper: 70,
     },
     Team {
         name: "Jamaica",
         flag: "🇯🇲",
+        kind: TeamKind::National,
+        country: "Jamaica",
+        confederation: Confederation::Concacaf,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 66,
+        midfield: 65,
+        defence: 67,
+        goalkeeper: 64,
     },
     Team {
         name: "Honduras",
         flag: "🇭🇳",
+        kind: TeamKind::National,
+        country: "Honduras",
+        confederation: Confederation::Concacaf,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 65,
+        midfield: 62,
+        defence: 62,
+        goalkeeper: 64,
     },
     // AFC/OFC National Teams
     Team {
         name: "Japan",
         flag: "🇯🇵",
+        kind: TeamKind::National,
+        country: "Japan",
+        confederation: Confederation::Afc,
+        league: None,
         formation: "4-3-3",
         tactic: "possession",
+        attack: 78,
+        midfield: 80,
+        defence: 79,
+        goalkeeper: 79,
     },
     Team {
         name: "South Korea",
         flag: "🇰🇷",
+        kind: TeamKind::National,
+        country: "South Korea",
+        confederation: Confederation::Afc,
+        league: None,
         formation: "4-2-3-1",
         tactic: "high_press",
+        attack: 79,
+        midfield: 78,
+        defence: 76,
+        goalkeeper: 76,
     },
     Team {
         name: "Australia",
         flag: "🇦🇺",
+        kind: TeamKind::National,
+        country: "Australia",
+        confederation: Confederation::Afc,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 76,
+        midfield: 71,
+        defence: 74,
+        goalkeeper: 75,
     },
     Team {
         name: "Iran",
         flag: "🇮🇷",
+        kind: TeamKind::National,
+        country: "Iran",
+        confederation: Confederation::Afc,
+        league: None,
         formation: "4-4-2",
         tactic: "counter",
+        attack: 75,
+        midfield: 75,
+        defence: 73,
+        goalkeeper: 74,
     },
     Team {
         name: "Saudi Arabia",
         flag: "🇸🇦",
+        kind: TeamKind::National,
+        country: "Saudi Arabia",
+        confederation: Confederation::Afc,
+        league: None,
         formation: "4-4-2",
         tactic: "counter",
+        attack: 73,
+        midfield: 67,
+        defence: 71,
+        goalkeeper: 72,
     },
     Team {
         name: "Qatar",
         flag: "🇶🇦",
+        kind: TeamKind::National,
+        country: "Qatar",
+        confederation: Confederation::Afc,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 68,
+        midfield: 67,
+        defence: 70,
+        goalkeeper: 69,
     },
     Team {
         name: "Iraq",
         flag: "🇮🇶",
+        kind: TeamKind::National,
+        country: "Iraq",
+        confederation: Confederation::Afc,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 70,
+        midfield: 64,
+        defence: 66,
+        goalkeeper: 67,
     },
     Team {
         name: "United Arab Emirates",
         flag: "🇦🇪",
+        kind: TeamKind::National,
+        country: "United Arab Emirates",
+        confederation: Confederation::Afc,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 68,
+        midfield: 63,
+        defence: 63,
+        goalkeeper: 67,
     },
     Team {
         name: "PRC China",
         flag: "🇨🇳",
+        kind: TeamKind::National,
+        country: "China",
+        confederation: Confederation::Afc,
+        league: None,
         formation: "4-3-3",
         tactic: "possession",
+        attack: 60,
+        midfield: 64,
+        defence: 61,
+        goalkeeper: 62,
     },
     // CAF National Teams
     Team {
         name: "Morocco",
         flag: "🇲🇦",
+        kind: TeamKind::National,
+        country: "Morocco",
+        confederation: Confederation::Caf,
+        league: None,
         formation: "4-2-3-1",
         tactic: "high_press",
+        attack: 80,
+        midfield: 81,
+        defence: 76,
+        goalkeeper: 77,
     },
     Team {
         name: "Senegal",
         flag: "🇸🇳",
+        kind: TeamKind::National,
+        country: "Senegal",
+        confederation: Confederation::Caf,
+        league: None,
         formation: "4-4-2",
         tactic: "counter",
+        attack: 78,
+        midfield: 78,
+        defence: 79,
+        goalkeeper: 75,
     },
     Team {
         name: "Nigeria",
         flag: "🇳🇬",
+        kind: TeamKind::National,
+        country: "Nigeria",
+        confederation: Confederation::Caf,
+        league: None,
         formation: "4-2-3-1",
         tactic: "high_press",
+        attack: 75,
+        midfield: 74,
+        defence: 71,
+        goalkeeper: 73,
     },
     Team {
         name: "Egypt",
         flag: "🇪🇬",
+        kind: TeamKind::National,
+        country: "Egypt",
+        confederation: Confederation::Caf,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 73,
+        midfield: 73,
+        defence: 72,
+        goalkeeper: 74,
     },
     Team {
         name: "Algeria",
         flag: "🇩🇿",
+        kind: TeamKind::National,
+        country: "Algeria",
+        confederation: Confederation::Caf,
+        league: None,
         formation: "4-4-2",
         tactic: "counter",
+        attack: 75,
+        midfield: 74,
+        defence: 75,
+        goalkeeper: 71,
     },
     Team {
         name: "Tunisia",
         flag: "🇹🇳",
+        kind: TeamKind::National,
+        country: "Tunisia",
+        confederation: Confederation::Caf,
+        league: None,
         formation: "4-4-2",
         tactic: "counter",
+        attack: 72,
+        midfield: 72,
+        defence: 70,
+        goalkeeper: 69,
     },
     Team {
         name: "Ghana",
         flag: "🇬🇭",
+        kind: TeamKind::National,
+        country: "Ghana",
+        confederation: Confederation::Caf,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 71,
+        midfield: 70,
+        defence: 73,
+        goalkeeper: 72,
     },
     Team {
         name: "Cameroon",
         flag: "🇨🇲",
+        kind: TeamKind::National,
+        country: "Cameroon",
+        confederation: Confederation::Caf,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 71,
+        midfield: 69,
+        defence: 70,
+        goalkeeper: 72,
     },
     Team {
         name: "Ivory Coast",
         flag: "🇨🇮",
+        kind: TeamKind::National,
+        country: "Ivory Coast",
+        confederation: Confederation::Caf,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 75,
+        midfield: 75,
+        defence: 75,
+        goalkeeper: 75,
     },
     Team {
         name: "South Africa",
         flag: "🇿🇦",
+        kind: TeamKind::National,
+        country: "South Africa",
+        confederation: Confederation::Caf,
+        league: None,
         formation: "4-2-3-1",
         tactic: "counter",
+        attack: 69,
+        midfield: 69,
+        defence: 67,
+        goalkeeper: 70,
     },
 ];
 
+const fn formations_valid(teams: &[Team]) -> bool {
+    let mut i = 0;
+    while i < teams.len() {
+        if Formation::parse(teams[i].formation).is_none() {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+// Reject malformed formation strings at build time.
+const _: () = assert!(
+    formations_valid(&TEAMS_DATA),
+    "TEAMS_DATA contains a malformed formation"
+);
+
 /// Generate team names array dynamically from TEAMS_DATA at compile time
 const fn extract_team_names<const N: usize>(data: &[Team; N]) -> [&str; N] {
     let mut result = [""; N];
@@ -603,6 +1452,91 @@ pub fn team_flag(team: &str) -> &'static str {
     team_by_name(team).map(|t| t.flag).unwrap_or("🏳️")
 }
 
+/// Country a team represents or plays in, or "" for unknown teams.
+pub fn team_country(team: &str) -> &'static str {
+    team_by_name(team).map(|t| t.country).unwrap_or("")
+}
+
+pub fn team_confederation(team: &str) -> Option<Confederation> {
+    team_by_name(team).map(|t| t.confederation)
+}
+
+/// Narrows the team pool for listings and CPU auto-fill; unset fields match
+/// every team. Country and league match without regard to letter case.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TeamFilter {
+    pub kind: Option<TeamKind>,
+    pub confederation: Option<Confederation>,
+    pub country: Option<String>,
+    pub league: Option<String>,
+}
+
+impl TeamFilter {
+    /// Build a filter from text values, as given on the web API.
+    pub fn parse(
+        kind: Option<&str>,
+        confederation: Option<&str>,
+        country: Option<&str>,
+        league: Option<&str>,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            kind: kind
+                .map(|k| TeamKind::parse(k).ok_or_else(|| format!("Unsupported team kind: {k}")))
+                .transpose()?,
+            confederation: confederation
+                .map(|c| {
+                    Confederation::parse(c).ok_or_else(|| format!("Unknown confederation: {c}"))
+                })
+                .transpose()?,
+            country: country.map(str::to_string),
+            league: league.map(str::to_string),
+        })
+    }
+
+    pub fn matches(&self, team: &Team) -> bool {
+        let same = |wanted: &Option<String>, value: Option<&str>| match wanted {
+            Some(wanted) => value.is_some_and(|v| v.eq_ignore_ascii_case(wanted)),
+            None => true,
+        };
+        self.kind.is_none_or(|k| k == team.kind)
+            && self.confederation.is_none_or(|c| c == team.confederation)
+            && same(&self.country, Some(team.country))
+            && same(&self.league, team.league)
+    }
+
+    /// Names of the matching teams, in roster order.
+    pub fn teams(&self) -> Vec<&'static str> {
+        TEAMS_DATA
+            .iter()
+            .filter(|t| self.matches(t))
+            .map(|t| t.name)
+            .collect()
+    }
+
+    /// E.g. "all teams", "UEFA national teams", "clubs (England, Premier League)".
+    pub fn label(&self) -> String {
+        let kind = match self.kind {
+            Some(TeamKind::Club) => "clubs",
+            Some(TeamKind::National) => "national teams",
+            None => "teams",
+        };
+        let mut label = match self.confederation {
+            Some(confed) => format!("{} {}", confed.as_str(), kind),
+            None if self == &Self::default() => "all teams".to_string(),
+            None => kind.to_string(),
+        };
+        let places: Vec<&str> = [&self.country, &self.league]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect();
+        if !places.is_empty() {
+            label.push_str(&format!(" ({})", places.join(", ")));
+        }
+        label
+    }
+}
+
 pub fn display_name(team: &str) -> String {
     format!("{} {}", team_flag(team), team)
 }
@@ -611,6 +1545,38 @@ pub fn display_name(team: &str) -> String {
 pub struct TeamProfile {
     pub formation: &'static str,
     pub tactic: &'static str,
+    pub attack: u8,
+    pub midfield: u8,
+    pub defence: u8,
+    pub goalkeeper: u8,
+}
+
+impl TeamProfile {
+    pub fn lines(&self) -> Formation {
+        Formation::parse(self.formation).unwrap_or(Formation {
+            defenders: 4,
+            midfielders: 4,
+            forwards: 2,
+        })
+    }
+
+    /// Mean of the four unit ratings.
+    pub fn overall(&self) -> f64 {
+        (self.attack as f64 + self.midfield as f64 + self.defence as f64 + self.goalkeeper as f64)
+            / 4.0
+    }
+
+    /// Every unit rating moved by `points` (e.g. from Elo), kept within 1-99.
+    pub fn shifted(self, points: f64) -> TeamProfile {
+        let shift = |rating: u8| (rating as f64 + points).round().clamp(1.0, 99.0) as u8;
+        TeamProfile {
+            attack: shift(self.attack),
+            midfield: shift(self.midfield),
+            defence: shift(self.defence),
+            goalkeeper: shift(self.goalkeeper),
+            ..self
+        }
+    }
 }
 
 pub fn tactic_by_key(key: &str) -> Tactic {
@@ -626,9 +1592,96 @@ pub fn profile_for(team: &str) -> TeamProfile {
         .map(|t| TeamProfile {
             formation: t.formation,
             tactic: t.tactic,
+            attack: t.attack,
+            midfield: t.midfield,
+            defence: t.defence,
+            goalkeeper: t.goalkeeper,
         })
         .unwrap_or(TeamProfile {
             formation: "4-4-2",
             tactic: "counter",
+            attack: 70,
+            midfield: 70,
+            defence: 70,
+            goalkeeper: 70,
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formation_parse_splits_lines() {
+        let shape = |d, m, f| Formation {
+            defenders: d,
+            midfielders: m,
+            forwards: f,
+        };
+        assert_eq!(Formation::parse("4-4-2"), Some(shape(4, 4, 2)));
+        assert_eq!(Formation::parse("4-2-3-1"), Some(shape(4, 5, 1)));
+        assert_eq!(Formation::parse("3-4-1-1-1"), Some(shape(3, 6, 1)));
+        for bad in [
+            "4-4", "4-4-3", "44-2", "4-4-2-", "0-5-5", "4-4-1-1-", "", "4x4x2",
+        ] {
+            assert_eq!(Formation::parse(bad), None, "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn team_filter_ignores_letter_case() {
+        let italy = TeamFilter {
+            country: Some("iTALY".to_string()),
+            ..TeamFilter::default()
+        };
+        let teams = italy.teams();
+        assert!(teams.contains(&"Italy") && teams.len() > 1);
+        assert!(teams
+            .iter()
+            .all(|t| team_by_name(t).unwrap().country == "Italy"));
+
+        let serie_a = TeamFilter {
+            league: Some("serie a".to_string()),
+            ..TeamFilter::default()
+        };
+        assert_eq!(serie_a.teams().len(), teams.len() - 1);
+        assert!(!serie_a.teams().contains(&"Italy"));
+    }
+
+    #[test]
+    fn national_teams_never_match_a_league() {
+        let leagues: Vec<&str> = TEAMS_DATA.iter().filter_map(|t| t.league).collect();
+        for team in TEAMS_DATA.iter().filter(|t| t.kind == TeamKind::National) {
+            for league in &leagues {
+                let filter = TeamFilter {
+                    league: Some(league.to_string()),
+                    ..TeamFilter::default()
+                };
+                assert!(!filter.matches(team), "{} in {league}", team.name);
+            }
+        }
+    }
+
+    #[test]
+    fn team_filter_parse_and_label() {
+        let label = |kind, confed, country, league| {
+            TeamFilter::parse(kind, confed, country, league)
+                .unwrap()
+                .label()
+        };
+        assert_eq!(label(None, None, None, None), "all teams");
+        assert_eq!(label(Some("club"), None, None, None), "clubs");
+        assert_eq!(label(None, Some("uefa"), None, None), "UEFA teams");
+        assert_eq!(
+            label(Some("national"), Some("UEFA"), None, None),
+            "UEFA national teams"
+        );
+        assert_eq!(label(None, None, Some("Japan"), None), "teams (Japan)");
+        assert_eq!(
+            label(Some("club"), None, Some("England"), Some("Premier League")),
+            "clubs (England, Premier League)"
+        );
+        assert!(TeamFilter::parse(Some("amateur"), None, None, None).is_err());
+        assert!(TeamFilter::parse(None, Some("Mars"), None, None).is_err());
+    }
+}
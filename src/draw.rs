@@ -0,0 +1,641 @@
+use crate::bracket::{round_code, seed_order};
+use crate::data::{display_name, team_confederation, team_country, Confederation};
+use crate::groups::{group_name, GROUP_SIZE};
+use crate::utils::Rng;
+
+/// Which teams a pot draw keeps apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Separation {
+    /// Only the pots apply.
+    None,
+    /// No two teams from one country in a group or first-round tie.
+    #[default]
+    Country,
+    /// Country, plus one team per confederation in a group or tie; groups of
+    /// four may take two from UEFA, as at the World Cup.
+    Confederation,
+}
+
+impl Separation {
+    pub const ALL: [Separation; 3] = [
+        Separation::None,
+        Separation::Country,
+        Separation::Confederation,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Separation::None => "none",
+            Separation::Country => "country",
+            Separation::Confederation => "confederation",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Separation::None => "no separation",
+            Separation::Country => "country",
+            Separation::Confederation => "country and confederation",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Separation> {
+        Separation::ALL.into_iter().find(|s| s.as_str() == value)
+    }
+
+    /// The next looser rule, tried when no draw satisfies this one.
+    fn relaxed(self) -> Separation {
+        match self {
+            Separation::Confederation => Separation::Country,
+            _ => Separation::None,
+        }
+    }
+}
+
+/// One ball out of a pot and the draw as it stands afterwards.
+#[derive(Debug, Clone)]
+pub struct DrawStep {
+    /// E.g. "Pot 2: Japan -> Group C (passed over Group A: same confederation)".
+    pub line: String,
+    pub board: Vec<String>,
+}
+
+/// How a pot draw went, ball by ball.
+#[derive(Debug, Clone)]
+pub struct DrawLog {
+    /// Pot members, for the opening announcement.
+    pub pots: Vec<Vec<String>>,
+    /// Separation actually applied; looser than asked when no draw could satisfy it.
+    pub separation: Separation,
+    pub relaxed: bool,
+    pub steps: Vec<DrawStep>,
+}
+
+impl DrawLog {
+    /// Opening lines: pots and the separation in force.
+    pub fn intro_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .pots
+            .iter()
+            .enumerate()
+            .map(|(idx, pot)| {
+                let members: Vec<String> = pot.iter().map(|t| display_name(t)).collect();
+                format!("Pot {}: {}", idx + 1, members.join(", "))
+            })
+            .collect();
+        lines.push(self.separation_line());
+        lines
+    }
+
+    pub fn separation_line(&self) -> String {
+        format!(
+            "Separation: {}{}",
+            self.separation.label(),
+            if self.relaxed {
+                " (relaxed: no draw met the requested rule)"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+/// Draw `teams` into groups of four from pots. The first quarter of the list is
+/// pot 1, the next quarter pot 2, and so on. Each ball goes to the first group
+/// in order that has room for its pot, keeps the separation rule and still
+/// leaves a valid draw for every ball not yet drawn.
+pub fn draw_groups_from_pots(
+    teams: &[String],
+    separation: Separation,
+    rng: &mut Rng,
+) -> (Vec<Vec<String>>, DrawLog) {
+    let group_count = teams.len() / GROUP_SIZE;
+    let team_pots: Vec<usize> = (0..teams.len()).map(|idx| idx / group_count).collect();
+    let units: Vec<Vec<Option<usize>>> = (0..group_count)
+        .map(|g| (0..GROUP_SIZE).map(|p| Some(g * GROUP_SIZE + p)).collect())
+        .collect();
+    let cell_pots: Vec<usize> = (0..group_count * GROUP_SIZE)
+        .map(|c| c % GROUP_SIZE)
+        .collect();
+    let names: Vec<String> = (0..group_count).map(group_name).collect();
+
+    let mut board = Board::new(teams, team_pots, cell_pots, units, names, separation);
+    let log = board.run(rng);
+    let groups = (0..group_count)
+        .map(|g| {
+            (0..GROUP_SIZE)
+                .map(|p| {
+                    let team = board.filled[g * GROUP_SIZE + p].expect("every cell filled");
+                    teams[team].clone()
+                })
+                .collect()
+        })
+        .collect();
+    (groups, log)
+}
+
+/// Draw `teams` into bracket seed order for [`crate::bracket::Bracket::seeded`].
+/// The first four seeds (fewer in small brackets) form pot 1 and are drawn into
+/// different quarters, so they cannot meet before the semi-finals. The rest of
+/// the top half is pot 2 and the remaining teams are the unseeded pot, each drawn
+/// against a seeded team; with byes the seeds left without an opponent go through.
+pub fn draw_bracket_from_pots(
+    teams: &[String],
+    separation: Separation,
+    rng: &mut Rng,
+) -> (Vec<String>, DrawLog) {
+    let size = teams.len().max(2).next_power_of_two();
+    let half = size / 2;
+    let top = half.min(4);
+    let pot_of = |seed: usize| {
+        if seed < top {
+            0
+        } else if seed < half {
+            1
+        } else if top < half {
+            2
+        } else {
+            1
+        }
+    };
+    let team_pots: Vec<usize> = (0..teams.len()).map(pot_of).collect();
+    let order = seed_order(size);
+    let units: Vec<Vec<Option<usize>>> = order
+        .chunks(2)
+        .map(|pair| {
+            pair.iter()
+                .map(|&s| (s < teams.len()).then_some(s))
+                .collect()
+        })
+        .collect();
+    let code = round_code(size);
+    let names: Vec<String> = (0..units.len())
+        .map(|i| format!("{} {}", code, i + 1))
+        .collect();
+
+    let mut board = Board::new(
+        teams,
+        team_pots.clone(),
+        team_pots,
+        units,
+        names,
+        separation,
+    );
+    let log = board.run(rng);
+    let seeds = (0..teams.len())
+        .map(|s| teams[board.filled[s].expect("every seed filled")].clone())
+        .collect();
+    (seeds, log)
+}
+
+/// A country or confederation the separation rule limits per unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Limit {
+    Country(&'static str),
+    Confederation(Confederation),
+}
+
+type UnitShape = (
+    Vec<Option<usize>>,
+    Vec<(&'static str, Option<Confederation>)>,
+);
+
+/// Cells to fill, grouped into units (groups or first-round ties). Each team may
+/// only take a cell of its own pot.
+struct Board<'a> {
+    teams: &'a [String],
+    /// Each team's `country` and `confederation` metadata; a team without any is
+    /// never kept apart from others.
+    countries: Vec<&'static str>,
+    confederations: Vec<Option<Confederation>>,
+    team_pots: Vec<usize>,
+    cell_pots: Vec<usize>,
+    /// Cells of each unit in slot order; `None` is a bye.
+    units: Vec<Vec<Option<usize>>>,
+    cell_units: Vec<usize>,
+    unit_names: Vec<String>,
+    filled: Vec<Option<usize>>,
+    separation: Separation,
+}
+
+impl<'a> Board<'a> {
+    fn new(
+        teams: &'a [String],
+        team_pots: Vec<usize>,
+        cell_pots: Vec<usize>,
+        units: Vec<Vec<Option<usize>>>,
+        unit_names: Vec<String>,
+        separation: Separation,
+    ) -> Self {
+        let mut cell_units = vec![0; cell_pots.len()];
+        for (unit, cells) in units.iter().enumerate() {
+            for cell in cells.iter().flatten() {
+                cell_units[*cell] = unit;
+            }
+        }
+        Self {
+            teams,
+            countries: teams.iter().map(|t| team_country(t)).collect(),
+            confederations: teams.iter().map(|t| team_confederation(t)).collect(),
+            team_pots,
+            filled: vec![None; cell_pots.len()],
+            cell_pots,
+            units,
+            cell_units,
+            unit_names,
+            separation,
+        }
+    }
+
+    fn run(&mut self, rng: &mut Rng) -> DrawLog {
+        let pot_count = self.team_pots.iter().max().map_or(0, |p| p + 1);
+        let pots: Vec<Vec<String>> = (0..pot_count)
+            .map(|p| {
+                (0..self.teams.len())
+                    .filter(|&t| self.team_pots[t] == p)
+                    .map(|t| self.teams[t].clone())
+                    .collect()
+            })
+            .collect();
+
+        let requested = self.separation;
+        let mut everyone: Vec<usize> = (0..self.teams.len()).collect();
+        while !self.solvable(&mut everyone) {
+            self.separation = self.separation.relaxed();
+        }
+
+        let mut steps = Vec::with_capacity(self.teams.len());
+        let mut waiting: Vec<usize> = (0..self.teams.len()).collect();
+        for pot in 0..pot_count {
+            let mut balls: Vec<usize> = waiting
+                .iter()
+                .copied()
+                .filter(|&t| self.team_pots[t] == pot)
+                .collect();
+            rng.shuffle(&mut balls);
+            for team in balls {
+                waiting.retain(|&t| t != team);
+                let mut passed = Vec::new();
+                let mut cells: Vec<usize> = self.open_cells(team).collect();
+                cells.sort_by_key(|&c| self.cell_units[c]);
+                let mut chosen = None;
+                for cell in cells {
+                    let unit = self.unit_names[self.cell_units[cell]].clone();
+                    if let Some(reason) = self.clash(team, cell) {
+                        passed.push(format!("{unit}: {reason}"));
+                        continue;
+                    }
+                    self.filled[cell] = Some(team);
+                    if self.solvable(&mut waiting) {
+                        chosen = Some(cell);
+                        break;
+                    }
+                    self.filled[cell] = None;
+                    passed.push(format!("{unit}: would leave no valid draw"));
+                }
+                let cell = chosen.expect("a solvable draw always has a place for the next ball");
+                let mut line = format!(
+                    "Pot {}: {} -> {}",
+                    pot + 1,
+                    display_name(&self.teams[team]),
+                    self.unit_names[self.cell_units[cell]]
+                );
+                if !passed.is_empty() {
+                    line.push_str(&format!(" (passed over {})", passed.join("; ")));
+                }
+                steps.push(DrawStep {
+                    line,
+                    board: self.lines(),
+                });
+            }
+        }
+
+        DrawLog {
+            pots,
+            separation: self.separation,
+            relaxed: self.separation != requested,
+            steps,
+        }
+    }
+
+    fn open_cells(&self, team: usize) -> impl Iterator<Item = usize> + '_ {
+        let pot = self.team_pots[team];
+        (0..self.cell_pots.len())
+            .filter(move |&c| self.cell_pots[c] == pot && self.filled[c].is_none())
+    }
+
+    /// Limits the separation rule puts on `team`.
+    fn limits(&self, team: usize) -> Vec<Limit> {
+        let mut limits = Vec::new();
+        if self.separation != Separation::None && !self.countries[team].is_empty() {
+            limits.push(Limit::Country(self.countries[team]));
+        }
+        if self.separation == Separation::Confederation {
+            limits.extend(self.confederations[team].map(Limit::Confederation));
+        }
+        limits
+    }
+
+    fn within(&self, limit: Limit, team: usize) -> bool {
+        match limit {
+            Limit::Country(country) => self.countries[team] == country,
+            Limit::Confederation(confed) => self.confederations[team] == Some(confed),
+        }
+    }
+
+    /// Teams under `limit` a unit may hold, and how many it holds now.
+    fn room(&self, limit: Limit, unit: usize) -> (usize, usize) {
+        let cells = &self.units[unit];
+        let cap = match limit {
+            Limit::Confederation(Confederation::Uefa) if cells.len() > 2 => 2,
+            _ => 1,
+        };
+        let used = cells
+            .iter()
+            .flatten()
+            .filter_map(|&c| self.filled[c])
+            .filter(|&t| self.within(limit, t))
+            .count();
+        (cap, used)
+    }
+
+    /// Why `team` may not take `cell`, if the separation rule forbids it.
+    fn clash(&self, team: usize, cell: usize) -> Option<&'static str> {
+        self.limits(team).into_iter().find_map(|limit| {
+            let (cap, used) = self.room(limit, self.cell_units[cell]);
+            (used >= cap).then_some(match limit {
+                Limit::Country(_) => "same country",
+                Limit::Confederation(_) => "same confederation",
+            })
+        })
+    }
+
+    /// Quick count behind the search: each country or confederation must fit the
+    /// room left for it, in total and pot by pot.
+    fn room_left(&self, waiting: &[usize]) -> bool {
+        let mut limits: Vec<Limit> = waiting.iter().flat_map(|&t| self.limits(t)).collect();
+        limits.sort();
+        limits.dedup();
+        for limit in limits {
+            let teams: Vec<usize> = waiting
+                .iter()
+                .copied()
+                .filter(|&t| self.within(limit, t))
+                .collect();
+            let mut pots: Vec<usize> = teams.iter().map(|&t| self.team_pots[t]).collect();
+            pots.sort();
+            pots.dedup();
+            let fits = |pot: Option<usize>, need: usize| {
+                let room: usize = (0..self.units.len())
+                    .map(|unit| {
+                        let open = self.units[unit]
+                            .iter()
+                            .flatten()
+                            .filter(|&&c| self.filled[c].is_none())
+                            .filter(|&&c| match pot {
+                                Some(p) => self.cell_pots[c] == p,
+                                None => pots.contains(&self.cell_pots[c]),
+                            })
+                            .count();
+                        let (cap, used) = self.room(limit, unit);
+                        open.min(cap.saturating_sub(used))
+                    })
+                    .sum();
+                need <= room
+            };
+            if !fits(None, teams.len()) {
+                return false;
+            }
+            for &pot in &pots {
+                let need = teams.iter().filter(|&&t| self.team_pots[t] == pot).count();
+                if !fits(Some(pot), need) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Whether every team in `waiting` can still be placed. Tries the most
+    /// constrained team first and backtracks; `waiting` comes back unchanged.
+    fn solvable(&mut self, waiting: &mut Vec<usize>) -> bool {
+        if waiting.is_empty() || self.separation == Separation::None {
+            return true;
+        }
+        if !self.room_left(waiting) {
+            return false;
+        }
+        let mut best: Option<(usize, Vec<usize>)> = None;
+        for (idx, &team) in waiting.iter().enumerate() {
+            let options: Vec<usize> = self
+                .open_cells(team)
+                .filter(|&c| self.clash(team, c).is_none())
+                .collect();
+            if options.is_empty() {
+                return false;
+            }
+            if best.as_ref().is_none_or(|(_, o)| options.len() < o.len()) {
+                best = Some((idx, options));
+            }
+        }
+        let (idx, options) = best.expect("waiting is not empty");
+        let team = waiting.swap_remove(idx);
+
+        // Units with the same open pots and the same kinds of team are
+        // interchangeable; try only one of them.
+        let mut tried = Vec::new();
+        let mut found = false;
+        for cell in options {
+            let shape = self.shape(self.cell_units[cell]);
+            if tried.contains(&shape) {
+                continue;
+            }
+            tried.push(shape);
+            self.filled[cell] = Some(team);
+            found = self.solvable(waiting);
+            self.filled[cell] = None;
+            if found {
+                break;
+            }
+        }
+
+        waiting.push(team);
+        let last = waiting.len() - 1;
+        waiting.swap(idx, last);
+        found
+    }
+
+    /// What the rest of the search can see of a unit: its open pots (`None` for a
+    /// bye) and the country and confederation of each team in it.
+    fn shape(&self, unit: usize) -> UnitShape {
+        let mut open = Vec::new();
+        let mut members = Vec::new();
+        for slot in &self.units[unit] {
+            match slot.map(|c| (c, self.filled[c])) {
+                None => open.push(None),
+                Some((c, None)) => open.push(Some(self.cell_pots[c])),
+                Some((_, Some(t))) => members.push((self.countries[t], self.confederations[t])),
+            }
+        }
+        open.sort();
+        members.sort();
+        (open, members)
+    }
+
+    /// Every unit with its teams so far: "Group A: Brazil, Japan, -, -" or
+    /// "R16 3: Real Madrid vs -".
+    fn lines(&self) -> Vec<String> {
+        self.units
+            .iter()
+            .zip(&self.unit_names)
+            .map(|(unit, name)| {
+                let slot = |cell: &Option<usize>| match cell {
+                    Some(c) => self.filled[*c]
+                        .map(|t| display_name(&self.teams[t]))
+                        .unwrap_or_else(|| "-".to_string()),
+                    None => "bye".to_string(),
+                };
+                let slots: Vec<String> = unit.iter().map(slot).collect();
+                if unit.len() == 2 {
+                    format!("{}: {}", name, slots.join(" vs "))
+                } else {
+                    format!("{}: {}", name, slots.join(", "))
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(teams: &[&str]) -> Vec<String> {
+        teams.iter().map(|t| t.to_string()).collect()
+    }
+
+    /// Every unit holds at most `cap` teams of any one value of `key`.
+    fn spread(units: &[Vec<String>], key: impl Fn(&str) -> String, cap: usize) -> bool {
+        units.iter().all(|unit| {
+            unit.iter()
+                .all(|t| unit.iter().filter(|o| key(o) == key(t)).count() <= cap)
+        })
+    }
+
+    fn confederation(team: &str) -> String {
+        format!("{:?}", team_confederation(team))
+    }
+
+    #[test]
+    fn groups_keep_countries_apart() {
+        let teams = names(&[
+            "Arsenal",
+            "Juventus",
+            "Liverpool",
+            "Inter",
+            "Real Madrid",
+            "Bayern Munich",
+            "FC Barcelona",
+            "Borussia Dortmund",
+        ]);
+        for seed in 0..20 {
+            let (groups, log) =
+                draw_groups_from_pots(&teams, Separation::Country, &mut Rng::new(seed));
+            assert!(!log.relaxed);
+            assert_eq!(log.separation, Separation::Country);
+            assert_eq!(log.steps.len(), teams.len());
+            assert!(
+                spread(&groups, |t| team_country(t).to_string(), 1),
+                "{groups:?}"
+            );
+            // One team from each pot per group.
+            for group in &groups {
+                for (pot, team) in group.iter().enumerate() {
+                    assert!(log.pots[pot].contains(team));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn separation_uses_country_metadata_not_flags() {
+        // Arsenal and Celtic share a flag but not a country.
+        let teams = names(&["Arsenal", "Celtic", "Inter", "Ajax"]);
+        let (_, log) = draw_groups_from_pots(&teams, Separation::Country, &mut Rng::new(1));
+        assert!(!log.relaxed);
+    }
+
+    #[test]
+    fn impossible_separation_is_relaxed() {
+        let teams = names(&["Arsenal", "Liverpool", "Manchester City", "Inter"]);
+        let (groups, log) = draw_groups_from_pots(&teams, Separation::Country, &mut Rng::new(1));
+        assert!(log.relaxed);
+        assert_eq!(log.separation, Separation::None);
+        assert_eq!(groups, vec![teams]);
+    }
+
+    #[test]
+    fn confederation_separation_allows_two_uefa_teams() {
+        let teams = names(&[
+            "Brazil",
+            "Argentina",
+            "Japan",
+            "Iran",
+            "Mexico",
+            "Canada",
+            "Morocco",
+            "Senegal",
+        ]);
+        for seed in 0..20 {
+            let (groups, log) =
+                draw_groups_from_pots(&teams, Separation::Confederation, &mut Rng::new(seed));
+            assert!(!log.relaxed);
+            assert!(spread(&groups, confederation, 1), "{groups:?}");
+        }
+
+        let two_uefa = names(&["France", "Brazil", "Spain", "Japan"]);
+        let (_, log) =
+            draw_groups_from_pots(&two_uefa, Separation::Confederation, &mut Rng::new(1));
+        assert!(!log.relaxed);
+
+        // A third UEFA side breaks the cap, so only countries are kept apart.
+        let three_uefa = names(&["France", "Brazil", "Spain", "Germany"]);
+        let (_, log) =
+            draw_groups_from_pots(&three_uefa, Separation::Confederation, &mut Rng::new(1));
+        assert!(log.relaxed);
+        assert_eq!(log.separation, Separation::Country);
+    }
+
+    #[test]
+    fn bracket_keeps_top_seeds_and_countries_apart() {
+        let teams = names(&[
+            "Arsenal",
+            "Inter",
+            "Real Madrid",
+            "Bayern Munich",
+            "Liverpool",
+            "Juventus",
+            "FC Barcelona",
+            "Borussia Dortmund",
+        ]);
+        for seed in 0..20 {
+            let (seeds, log) =
+                draw_bracket_from_pots(&teams, Separation::Country, &mut Rng::new(seed));
+            assert!(!log.relaxed);
+            let ties: Vec<Vec<String>> = seed_order(8)
+                .chunks(2)
+                .map(|pair| pair.iter().map(|&s| seeds[s].clone()).collect())
+                .collect();
+            assert!(
+                spread(&ties, |t| team_country(t).to_string(), 1),
+                "{ties:?}"
+            );
+            // Pot 1 fills seeds 1-4, one per quarter of the bracket.
+            let mut top: Vec<&String> = seeds[..4].iter().collect();
+            top.sort();
+            let mut pot1: Vec<&String> = teams[..4].iter().collect();
+            pot1.sort();
+            assert_eq!(top, pot1);
+        }
+    }
+}
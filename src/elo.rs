@@ -0,0 +1,440 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::data::{display_name, profile_for, TEAMS};
+use crate::sim::MatchResult;
+use crate::utils::csv_escape;
+
+pub const DEFAULT_RATINGS_FILE: &str = "soccercloud-ratings.csv";
+
+/// Elo of a team whose profile averages 75, the neutral rating.
+const BASE_ELO: f64 = 1500.0;
+/// Elo points per point of profile rating, both for starting values and for strength.
+const ELO_PER_RATING_POINT: f64 = 10.0;
+/// Elo bonus for the home side outside neutral venues.
+const HOME_ELO_BONUS: f64 = 60.0;
+
+/// Stored rating for one team.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EloEntry {
+    pub rating: f64,
+    pub played: u32,
+}
+
+/// Decides how far one result moves a team's rating.
+pub trait KFactor {
+    fn k(&self, entry: &EloEntry, goal_margin: u8) -> f64;
+}
+
+/// The same K for every match.
+pub struct FixedK;
+
+impl KFactor for FixedK {
+    fn k(&self, _entry: &EloEntry, _goal_margin: u8) -> f64 {
+        24.0
+    }
+}
+
+/// World Football Elo style: wider wins move ratings further.
+pub struct GoalMarginK;
+
+impl KFactor for GoalMarginK {
+    fn k(&self, _entry: &EloEntry, goal_margin: u8) -> f64 {
+        let mult = match goal_margin {
+            0 | 1 => 1.0,
+            2 => 1.5,
+            3 => 1.75,
+            n => 1.75 + (n as f64 - 3.0) / 8.0,
+        };
+        20.0 * mult
+    }
+}
+
+/// Large steps while a team has few results, settling once it has a record.
+pub struct ProvisionalK;
+
+impl KFactor for ProvisionalK {
+    fn k(&self, entry: &EloEntry, _goal_margin: u8) -> f64 {
+        if entry.played < 20 {
+            40.0
+        } else {
+            20.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KFactorKind {
+    #[default]
+    Fixed,
+    GoalMargin,
+    Provisional,
+}
+
+impl KFactorKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            KFactorKind::Fixed => "fixed",
+            KFactorKind::GoalMargin => "margin",
+            KFactorKind::Provisional => "provisional",
+        }
+    }
+
+    pub fn factor(self) -> &'static dyn KFactor {
+        match self {
+            KFactorKind::Fixed => &FixedK,
+            KFactorKind::GoalMargin => &GoalMarginK,
+            KFactorKind::Provisional => &ProvisionalK,
+        }
+    }
+}
+
+/// Where ratings live and whether finished simulations update them.
+#[derive(Debug, Clone)]
+pub struct RatingsConfig {
+    pub path: PathBuf,
+    pub k_factor: KFactorKind,
+    pub update: bool,
+}
+
+impl RatingsConfig {
+    /// Load, apply and save `results` if updates are enabled.
+    pub fn record(&self, results: &[MatchResult]) -> io::Result<()> {
+        if !self.update || results.is_empty() {
+            return Ok(());
+        }
+        let mut store = EloStore::load(&self.path)?;
+        for result in results {
+            store.record(result, self.k_factor.factor());
+        }
+        store.save(&self.path)
+    }
+}
+
+/// Elo before a team's first recorded match, derived from its profile ratings.
+pub fn initial_rating(team: &str) -> f64 {
+    BASE_ELO + (profile_for(team).overall() - 75.0) * ELO_PER_RATING_POINT
+}
+
+/// Ratings snapshot used as team strength: profile ratings move with Elo gained or lost.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EloStrength {
+    ratings: BTreeMap<String, f64>,
+}
+
+impl EloStrength {
+    /// Rating points to add to every unit of `team`.
+    pub fn shift(&self, team: &str) -> f64 {
+        self.ratings
+            .get(team)
+            .map(|r| (r - initial_rating(team)) / ELO_PER_RATING_POINT)
+            .unwrap_or(0.0)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EloStore {
+    entries: BTreeMap<String, EloEntry>,
+}
+
+impl EloStore {
+    /// Read ratings from `path`; a missing file is an empty store.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+
+        let mut entries = BTreeMap::new();
+        for (idx, line) in text.lines().enumerate().skip(1) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let invalid = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: bad ratings line {}", path.display(), idx + 1),
+                )
+            };
+            // Team names may contain commas, so split from the right.
+            let mut cols = line.rsplitn(3, ',');
+            let played = cols
+                .next()
+                .and_then(|v| v.parse().ok())
+                .ok_or_else(invalid)?;
+            let rating = cols
+                .next()
+                .and_then(|v| v.parse().ok())
+                .ok_or_else(invalid)?;
+            let team = cols.next().ok_or_else(invalid)?;
+            let team = team
+                .strip_prefix('"')
+                .and_then(|t| t.strip_suffix('"'))
+                .map(|t| t.replace("\"\"", "\""))
+                .unwrap_or_else(|| team.to_string());
+            entries.insert(team, EloEntry { rating, played });
+        }
+        Ok(Self { entries })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = String::from("Team,Rating,Played\n");
+        for (team, entry) in &self.entries {
+            out.push_str(&format!(
+                "{},{:.1},{}\n",
+                csv_escape(team),
+                entry.rating,
+                entry.played
+            ));
+        }
+        // Write beside the file and rename over it, so an interrupted save cannot
+        // truncate the ratings history.
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        fs::write(&tmp, out)?;
+        fs::rename(&tmp, path)
+    }
+
+    /// Remove the ratings file, returning every team to its starting rating.
+    pub fn reset(path: &Path) -> io::Result<()> {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    fn entry(&self, team: &str) -> EloEntry {
+        self.entries.get(team).copied().unwrap_or(EloEntry {
+            rating: initial_rating(team),
+            played: 0,
+        })
+    }
+
+    /// Apply one result. Matches settled on penalties count as draws.
+    pub fn record(&mut self, result: &MatchResult, k_factor: &dyn KFactor) {
+        let home = self.entry(&result.home);
+        let away = self.entry(&result.away);
+        let bonus = if result.neutral_venue {
+            0.0
+        } else {
+            HOME_ELO_BONUS
+        };
+        let expected_home = 1.0 / (1.0 + 10f64.powf((away.rating - home.rating - bonus) / 400.0));
+        let actual_home = match result.home_goals.cmp(&result.away_goals) {
+            std::cmp::Ordering::Greater => 1.0,
+            std::cmp::Ordering::Equal => 0.5,
+            std::cmp::Ordering::Less => 0.0,
+        };
+        let margin = result.home_goals.abs_diff(result.away_goals);
+
+        let home_k = k_factor.k(&home, margin);
+        let away_k = k_factor.k(&away, margin);
+        self.entries.insert(
+            result.home.clone(),
+            EloEntry {
+                rating: home.rating + home_k * (actual_home - expected_home),
+                played: home.played + 1,
+            },
+        );
+        self.entries.insert(
+            result.away.clone(),
+            EloEntry {
+                rating: away.rating + away_k * (expected_home - actual_home),
+                played: away.played + 1,
+            },
+        );
+    }
+
+    pub fn strength(&self) -> EloStrength {
+        EloStrength {
+            ratings: self
+                .entries
+                .iter()
+                .map(|(team, entry)| (team.clone(), entry.rating))
+                .collect(),
+        }
+    }
+
+    /// Ranked table of rated teams, or of every known team with `all`.
+    pub fn table_lines(&self, all: bool) -> Vec<String> {
+        let mut rows: Vec<(String, EloEntry)> = if all {
+            TEAMS
+                .iter()
+                .map(|team| (team.to_string(), self.entry(team)))
+                .collect()
+        } else {
+            self.entries
+                .iter()
+                .map(|(team, entry)| (team.clone(), *entry))
+                .collect()
+        };
+        rows.sort_by(|a, b| b.1.rating.total_cmp(&a.1.rating).then(a.0.cmp(&b.0)));
+
+        let mut lines = vec![format!(
+            "{:>3}  {:<30} {:>7} {:>7} {:>6}",
+            "#", "Team", "Elo", "Start", "Played"
+        )];
+        for (idx, (team, entry)) in rows.iter().enumerate() {
+            lines.push(format!(
+                "{:>3}  {:<30} {:>7.1} {:>7.0} {:>6}",
+                idx + 1,
+                display_name(team),
+                entry.rating,
+                initial_rating(team),
+                entry.played
+            ));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::{MatchStats, TeamStats};
+
+    fn result(home_goals: u8, away_goals: u8, neutral_venue: bool) -> MatchResult {
+        MatchResult {
+            home: "Home FC".to_string(),
+            away: "Away FC".to_string(),
+            home_goals,
+            away_goals,
+            home_profile: profile_for("Home FC"),
+            away_profile: profile_for("Away FC"),
+            stats: MatchStats {
+                home: TeamStats::default(),
+                away: TeamStats::default(),
+            },
+            home_possession: 50,
+            away_possession: 50,
+            goals: Vec::new(),
+            tactic_changes: Vec::new(),
+            extra_time: false,
+            neutral_venue,
+        }
+    }
+
+    /// Store with both sides on 1500 and no matches played.
+    fn level_store() -> EloStore {
+        let entry = EloEntry {
+            rating: 1500.0,
+            played: 0,
+        };
+        EloStore {
+            entries: [
+                ("Home FC".to_string(), entry),
+                ("Away FC".to_string(), entry),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("ratings-{name}-{}.csv", std::process::id()))
+    }
+
+    #[test]
+    fn home_win_moves_ratings_by_k_times_surprise() {
+        let mut store = level_store();
+        store.record(&result(2, 0, false), &FixedK);
+        // The 60-point home bonus makes the hosts a 58.5% favourite.
+        let expected = 1.0 / (1.0 + 10f64.powf(-60.0 / 400.0));
+        assert!((expected - 0.5855).abs() < 1e-4);
+        let home = store.entry("Home FC");
+        let away = store.entry("Away FC");
+        assert!((home.rating - (1500.0 + 24.0 * (1.0 - expected))).abs() < 1e-9);
+        assert!((home.rating + away.rating - 3000.0).abs() < 1e-9);
+        assert_eq!((home.played, away.played), (1, 1));
+    }
+
+    #[test]
+    fn neutral_venue_ignores_the_home_bonus() {
+        let mut store = level_store();
+        store.record(&result(1, 0, true), &FixedK);
+        assert!((store.entry("Home FC").rating - 1512.0).abs() < 1e-9);
+        assert!((store.entry("Away FC").rating - 1488.0).abs() < 1e-9);
+
+        // A home draw costs the favoured hosts; on neutral ground it changes nothing.
+        let mut store = level_store();
+        store.record(&result(1, 1, false), &FixedK);
+        assert!(store.entry("Home FC").rating < 1500.0);
+        let mut store = level_store();
+        store.record(&result(1, 1, true), &FixedK);
+        assert_eq!(store.entry("Home FC").rating, 1500.0);
+    }
+
+    #[test]
+    fn k_factors() {
+        let entry = |played| EloEntry {
+            rating: 1500.0,
+            played,
+        };
+        assert_eq!(FixedK.k(&entry(0), 0), 24.0);
+        assert_eq!(FixedK.k(&entry(50), 4), 24.0);
+        assert_eq!(GoalMarginK.k(&entry(0), 0), 20.0);
+        assert_eq!(GoalMarginK.k(&entry(0), 1), 20.0);
+        assert_eq!(GoalMarginK.k(&entry(0), 2), 30.0);
+        assert_eq!(GoalMarginK.k(&entry(0), 3), 35.0);
+        assert_eq!(GoalMarginK.k(&entry(0), 5), 40.0);
+        assert_eq!(ProvisionalK.k(&entry(0), 1), 40.0);
+        assert_eq!(ProvisionalK.k(&entry(19), 1), 40.0);
+        assert_eq!(ProvisionalK.k(&entry(20), 1), 20.0);
+    }
+
+    #[test]
+    fn save_then_load_round_trips() {
+        let mut store = EloStore::default();
+        store.entries.insert(
+            "Club, \"The\" Reserves".to_string(),
+            EloEntry {
+                rating: 1512.5,
+                played: 3,
+            },
+        );
+        store.entries.insert(
+            "Arsenal".to_string(),
+            EloEntry {
+                rating: 1620.0,
+                played: 40,
+            },
+        );
+        let path = temp_path("round-trip");
+        store.save(&path).unwrap();
+        let loaded = EloStore::load(&path);
+        EloStore::reset(&path).unwrap();
+        assert_eq!(loaded.unwrap().entries, store.entries);
+    }
+
+    #[test]
+    fn missing_file_loads_empty() {
+        let store = EloStore::load(&temp_path("missing")).unwrap();
+        assert!(store.entries.is_empty());
+    }
+
+    #[test]
+    fn record_only_writes_when_updates_are_on() {
+        let path = temp_path("update");
+        let before = "Team,Rating,Played\nHome FC,1500.0,0\nAway FC,1500.0,0\n";
+        fs::write(&path, before).unwrap();
+        let mut config = RatingsConfig {
+            path: path.clone(),
+            k_factor: KFactorKind::Fixed,
+            update: false,
+        };
+        config.record(&[result(3, 0, false)]).unwrap();
+        let untouched = fs::read_to_string(&path).unwrap();
+
+        config.update = true;
+        config.record(&[result(3, 0, false)]).unwrap();
+        let updated = EloStore::load(&path).unwrap();
+        EloStore::reset(&path).unwrap();
+
+        assert_eq!(untouched, before);
+        assert!(updated.entry("Home FC").rating > 1500.0);
+        assert_eq!(updated.entry("Away FC").played, 1);
+    }
+}
@@ -0,0 +1,478 @@
+use crate::data::{display_name, profile_for, tactic_by_key, Tactic, TeamProfile};
+use crate::events::{MatchEvent, Minute, ScoreLine, ShotType};
+use crate::sim::{
+    event_frame, possession_pct, rating_mult, simulate_match_with, GoalRecord, MatchOptions,
+    MatchResult, MatchStats, SimFrame, TeamStats, LIVE_ODDS_SALT,
+};
+use crate::squad::pick_shooter;
+use crate::utils::{derive_seed, Rng};
+use crate::winprob::{win_probability, LiveState};
+
+/// Anything that can play a single fixture and hand back a result plus playback frames.
+pub trait MatchEngine {
+    fn play(
+        &self,
+        home: &str,
+        away: &str,
+        options: MatchOptions,
+        rng: &mut Rng,
+    ) -> (MatchResult, Vec<SimFrame>);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EngineKind {
+    /// Minute-by-minute event simulation.
+    #[default]
+    Minute,
+    /// Scoreline drawn from a Dixon-Coles adjusted Poisson goal model.
+    Poisson,
+}
+
+impl EngineKind {
+    pub const ALL: [EngineKind; 2] = [EngineKind::Minute, EngineKind::Poisson];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EngineKind::Minute => "minute",
+            EngineKind::Poisson => "poisson",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            EngineKind::Minute => "Minute-by-minute",
+            EngineKind::Poisson => "Poisson (Dixon-Coles)",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<EngineKind> {
+        EngineKind::ALL.into_iter().find(|e| e.as_str() == value)
+    }
+
+    pub fn engine(self) -> &'static dyn MatchEngine {
+        match self {
+            EngineKind::Minute => &MinuteEngine,
+            EngineKind::Poisson => &PoissonEngine,
+        }
+    }
+}
+
+/// The default engine: [`simulate_match_with`].
+pub struct MinuteEngine;
+
+impl MatchEngine for MinuteEngine {
+    fn play(
+        &self,
+        home: &str,
+        away: &str,
+        options: MatchOptions,
+        rng: &mut Rng,
+    ) -> (MatchResult, Vec<SimFrame>) {
+        simulate_match_with(home, away, options, rng)
+    }
+}
+
+/// League-average goals per side before ratings and tactics.
+const BASE_GOALS: f64 = 1.1;
+/// Dixon-Coles low-score dependence; negative values make 0-0 and 1-1 more likely.
+const DC_RHO: f64 = -0.13;
+const MAX_GOALS: usize = 10;
+/// Average xG per shot used to size the shot count around the expected goals.
+const XG_PER_SHOT: f64 = 0.11;
+
+/// Draws the scoreline in one go, then lays goals out over a plain 90-minute timeline.
+/// Much faster than the minute engine but with a coarser stat line.
+pub struct PoissonEngine;
+
+/// Expected goals over 90 minutes for `atk` against `def` with their usual tactics.
+fn expected_goals(atk: TeamProfile, def: TeamProfile, venue_mult: f64) -> f64 {
+    goal_expectation(
+        atk,
+        tactic_by_key(atk.tactic),
+        def,
+        tactic_by_key(def.tactic),
+        venue_mult,
+    )
+}
+
+/// Expected goals over 90 minutes for `atk` against `def` with the given tactics.
+pub fn goal_expectation(
+    atk: TeamProfile,
+    atk_tactic: Tactic,
+    def: TeamProfile,
+    def_tactic: Tactic,
+    venue_mult: f64,
+) -> f64 {
+    BASE_GOALS
+        * rating_mult(atk.attack, 1.2)
+        * rating_mult(atk.midfield, 0.5)
+        * atk_tactic.goal_mult
+        * venue_mult
+        / (rating_mult(def.defence, 1.2) * rating_mult(def.goalkeeper, 0.6) * def_tactic.block_mult)
+}
+
+fn poisson_pmf(lambda: f64, k: usize) -> f64 {
+    let mut p = (-lambda).exp();
+    for i in 1..=k {
+        p *= lambda / i as f64;
+    }
+    p
+}
+
+fn poisson_sample(lambda: f64, rng: &mut Rng) -> u8 {
+    let limit = (-lambda).exp();
+    let mut k = 0u8;
+    let mut p = rng.next_f64();
+    while p > limit && k < u8::MAX {
+        k += 1;
+        p *= rng.next_f64();
+    }
+    k
+}
+
+/// Dixon-Coles correction factor for low-scoring results.
+fn dc_tau(h: usize, a: usize, lambda: f64, mu: f64) -> f64 {
+    match (h, a) {
+        (0, 0) => 1.0 - lambda * mu * DC_RHO,
+        (0, 1) => 1.0 + lambda * DC_RHO,
+        (1, 0) => 1.0 + mu * DC_RHO,
+        (1, 1) => 1.0 - DC_RHO,
+        _ => 1.0,
+    }
+}
+
+fn sample_scoreline(lambda: f64, mu: f64, rng: &mut Rng) -> (u8, u8) {
+    let mut grid = Vec::with_capacity((MAX_GOALS + 1) * (MAX_GOALS + 1));
+    for h in 0..=MAX_GOALS {
+        for a in 0..=MAX_GOALS {
+            let p = poisson_pmf(lambda, h) * poisson_pmf(mu, a) * dc_tau(h, a, lambda, mu);
+            grid.push(((h as u8, a as u8), p.max(0.0)));
+        }
+    }
+    let total: f64 = grid.iter().map(|(_, p)| p).sum();
+    let mut roll = rng.next_f64() * total;
+    for (score, p) in &grid {
+        roll -= p;
+        if roll < 0.0 {
+            return *score;
+        }
+    }
+    (0, 0)
+}
+
+/// Random minutes within `first..=last`, in playing order.
+fn goal_minutes(count: u8, first: u8, last: u8, rng: &mut Rng) -> Vec<u8> {
+    let span = (last - first + 1) as usize;
+    let mut minutes: Vec<u8> = (0..count)
+        .map(|_| first + rng.range_usize(span) as u8)
+        .collect();
+    minutes.sort_unstable();
+    minutes
+}
+
+/// Fill in shots and on-target counts around the goals actually scored.
+fn shot_line(stats: &mut TeamStats, expected: f64, goals: u8, rng: &mut Rng) {
+    let shots = poisson_sample(expected / XG_PER_SHOT, rng).max(goals) as u16;
+    let misses = shots - goals as u16;
+    let saved = (0..misses).filter(|_| rng.chance(0.3)).count() as u16;
+    stats.shots = shots;
+    stats.sot = goals as u16 + saved;
+    stats.xg = expected;
+    stats.attacks = shots * 2;
+    let by_type = &mut stats.shot_types[ShotType::InsideBox.index()];
+    by_type.shots = shots;
+    by_type.on_target = stats.sot;
+    by_type.goals = goals as u16;
+    by_type.xg = expected;
+}
+
+impl MatchEngine for PoissonEngine {
+    fn play(
+        &self,
+        home: &str,
+        away: &str,
+        options: MatchOptions,
+        rng: &mut Rng,
+    ) -> (MatchResult, Vec<SimFrame>) {
+        let home_profile = profile_for(home).shifted(options.home_rating_shift);
+        let away_profile = profile_for(away).shifted(options.away_rating_shift);
+        let lambda = expected_goals(home_profile, away_profile, options.home_factor());
+        let mu = expected_goals(away_profile, home_profile, 1.0);
+
+        let (mut home_goals, mut away_goals) = sample_scoreline(lambda, mu, rng);
+        let mut timeline: Vec<(u8, bool)> = Vec::new();
+        timeline.extend(
+            goal_minutes(home_goals, 1, 90, rng)
+                .into_iter()
+                .map(|m| (m, true)),
+        );
+        timeline.extend(
+            goal_minutes(away_goals, 1, 90, rng)
+                .into_iter()
+                .map(|m| (m, false)),
+        );
+
+        let extra_time = options.needs_extra_time(home_goals, away_goals);
+        let mut home_expected = lambda;
+        let mut away_expected = mu;
+        if extra_time {
+            let (et_lambda, et_mu) = (lambda / 3.0, mu / 3.0);
+            let et_home = poisson_sample(et_lambda, rng);
+            let et_away = poisson_sample(et_mu, rng);
+            timeline.extend(
+                goal_minutes(et_home, 91, 120, rng)
+                    .into_iter()
+                    .map(|m| (m, true)),
+            );
+            timeline.extend(
+                goal_minutes(et_away, 91, 120, rng)
+                    .into_iter()
+                    .map(|m| (m, false)),
+            );
+            home_goals += et_home;
+            away_goals += et_away;
+            home_expected += et_lambda;
+            away_expected += et_mu;
+        }
+        timeline.sort_by_key(|(m, _)| *m);
+
+        let mut stats = MatchStats {
+            home: TeamStats::with_lineup(home),
+            away: TeamStats::with_lineup(away),
+        };
+        shot_line(&mut stats.home, home_expected, home_goals, rng);
+        shot_line(&mut stats.away, away_expected, away_goals, rng);
+
+        let home_tactic = tactic_by_key(home_profile.tactic);
+        let away_tactic = tactic_by_key(away_profile.tactic);
+        let odds_seed = derive_seed(rng.clone().next_u64(), LIVE_ODDS_SALT);
+        let mut live = LiveState {
+            minute: Minute::default(),
+            home_goals: 0,
+            away_goals: 0,
+            home_tactic,
+            away_tactic,
+            home_reds: 0,
+            away_reds: 0,
+        };
+        let mut frames = Vec::new();
+        if options.record_frames {
+            let mut frame = event_frame(
+                scoreboard(home, away, 0, 0, 0) + &options.aggregate_suffix(0, 0),
+                vec![MatchEvent::Kickoff {
+                    home: home.to_string(),
+                    home_formation: home_profile.formation.to_string(),
+                    home_tactic: home_tactic.label.to_string(),
+                    away: away.to_string(),
+                    away_formation: away_profile.formation.to_string(),
+                    away_tactic: away_tactic.label.to_string(),
+                    neutral_venue: options.neutral_venue,
+                }],
+            );
+            if options.live_odds {
+                frame.win_prob = Some(win_probability(home, away, &live, options, odds_seed));
+            }
+            frames.push(frame);
+        }
+
+        let last_minute = if extra_time { 120 } else { 90 };
+        let mut goals = Vec::new();
+        let (mut h, mut a) = (0u8, 0u8);
+        let mut next = timeline.iter().peekable();
+        for minute in 1..=last_minute {
+            let clock = Minute {
+                base: minute,
+                added: 0,
+            };
+            let mut events = Vec::new();
+            if minute == 91 {
+                events.push(MatchEvent::ExtraTimeStart { half: 1 });
+            } else if minute == 106 {
+                events.push(MatchEvent::ExtraTimeStart { half: 2 });
+            }
+
+            while let Some((_, is_home)) = next.next_if(|(m, _)| *m == minute) {
+                let (team, team_stats, expected) = if *is_home {
+                    h += 1;
+                    (home, &mut stats.home, home_expected)
+                } else {
+                    a += 1;
+                    (away, &mut stats.away, away_expected)
+                };
+                let scorer = pick_shooter(&team_stats.players, rng);
+                team_stats.players[scorer].goals += 1;
+                team_stats.players[scorer].shots += 1;
+                let name = team_stats.players[scorer].name.clone();
+                let xg = expected / team_stats.shots.max(1) as f64;
+                events.push(MatchEvent::Goal {
+                    minute: clock,
+                    team: team.to_string(),
+                    player: name.clone(),
+                    xg,
+                    shot_type: ShotType::InsideBox,
+                    set_piece: None,
+                });
+                goals.push(GoalRecord {
+                    minute: clock,
+                    team: team.to_string(),
+                    scorer: name,
+                    set_piece: None,
+                });
+            }
+
+            let score = ScoreLine {
+                home: home.to_string(),
+                away: away.to_string(),
+                home_goals: h,
+                away_goals: a,
+            };
+            match minute {
+                45 => events.push(MatchEvent::HalfTime(score)),
+                90 if extra_time => events.push(MatchEvent::NormalTimeEnd(score)),
+                105 => events.push(MatchEvent::ExtraTimeHalfTime(score)),
+                m if m == last_minute => events.push(MatchEvent::FullTime {
+                    score,
+                    after_extra_time: extra_time,
+                }),
+                _ => {}
+            }
+            if options.record_frames {
+                live.minute = clock;
+                live.home_goals = h;
+                live.away_goals = a;
+                let mut frame = event_frame(
+                    scoreboard(home, away, h, a, minute) + &options.aggregate_suffix(h, a),
+                    events,
+                );
+                if options.live_odds {
+                    frame.win_prob = Some(win_probability(home, away, &live, options, odds_seed));
+                }
+                frames.push(frame);
+            }
+        }
+
+        let mut result = MatchResult {
+            home: home.to_string(),
+            away: away.to_string(),
+            home_goals,
+            away_goals,
+            home_profile,
+            away_profile,
+            stats,
+            home_possession: 50,
+            away_possession: 50,
+            goals,
+            tactic_changes: Vec::new(),
+            extra_time,
+            neutral_venue: options.neutral_venue,
+        };
+        let (home_poss, away_poss) = possession_pct(&result);
+        result.home_possession = home_poss;
+        result.away_possession = away_poss;
+        (result, frames)
+    }
+}
+
+fn scoreboard(home: &str, away: &str, h: u8, a: u8, minute: u8) -> String {
+    let home_profile = profile_for(home);
+    let away_profile = profile_for(away);
+    format!(
+        "{} ({}) {} - {} {} ({}) | {}'",
+        display_name(home),
+        home_profile.formation,
+        h,
+        a,
+        display_name(away),
+        away_profile.formation,
+        Minute {
+            base: minute,
+            added: 0,
+        }
+        .padded()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::SimConfig;
+
+    fn grid_probability(lambda: f64, mu: f64, h: usize, a: usize) -> f64 {
+        poisson_pmf(lambda, h) * poisson_pmf(mu, a) * dc_tau(h, a, lambda, mu)
+    }
+
+    #[test]
+    fn score_probabilities_sum_to_one() {
+        for (lambda, mu) in [(1.4, 1.1), (0.6, 2.3), (2.8, 0.4)] {
+            let total: f64 = (0..=MAX_GOALS)
+                .flat_map(|h| (0..=MAX_GOALS).map(move |a| grid_probability(lambda, mu, h, a)))
+                .sum();
+            // Only the sliver of mass beyond MAX_GOALS goals is missing.
+            assert!((total - 1.0).abs() < 1e-3, "{lambda}/{mu}: {total}");
+        }
+    }
+
+    #[test]
+    fn low_score_correction_favours_level_scores() {
+        let (lambda, mu) = (1.4, 1.1);
+        let plain = |h, a| poisson_pmf(lambda, h) * poisson_pmf(mu, a);
+        assert!(grid_probability(lambda, mu, 0, 0) > plain(0, 0));
+        assert!(grid_probability(lambda, mu, 1, 1) > plain(1, 1));
+        assert!(grid_probability(lambda, mu, 1, 0) < plain(1, 0));
+        assert!(grid_probability(lambda, mu, 0, 1) < plain(0, 1));
+        assert_eq!(grid_probability(lambda, mu, 2, 1), plain(2, 1));
+    }
+
+    #[test]
+    fn sampled_scores_stay_under_the_cap_and_match_the_grid() {
+        let (lambda, mu) = (1.4, 1.1);
+        let mut rng = Rng::new(3);
+        let runs = 20_000;
+        let mut nil_nil = 0;
+        for _ in 0..runs {
+            let (h, a) = sample_scoreline(lambda, mu, &mut rng);
+            assert!(h as usize <= MAX_GOALS && a as usize <= MAX_GOALS);
+            if (h, a) == (0, 0) {
+                nil_nil += 1;
+            }
+        }
+        let share = nil_nil as f64 / runs as f64;
+        assert!(
+            (share - grid_probability(lambda, mu, 0, 0)).abs() < 0.01,
+            "{share}"
+        );
+    }
+
+    #[test]
+    fn extra_time_only_for_level_knockout_matches() {
+        let league = SimConfig::default().match_options("Arsenal", "Inter", false);
+        let knockout = MatchOptions {
+            extra_time: true,
+            ..league
+        };
+        let mut rng = Rng::new(9);
+        let mut went_to_extra_time = 0;
+        for _ in 0..500 {
+            let (result, _) = PoissonEngine.play("Arsenal", "Inter", league, &mut rng);
+            assert!(!result.extra_time);
+            assert!(result.goals.iter().all(|g| g.minute.base <= 90));
+
+            let (result, _) = PoissonEngine.play("Arsenal", "Inter", knockout, &mut rng);
+            let regulation = |home: bool| {
+                let team = if home { "Arsenal" } else { "Inter" };
+                result
+                    .goals
+                    .iter()
+                    .filter(|g| g.team == team && g.minute.base <= 90)
+                    .count()
+            };
+            assert_eq!(result.extra_time, regulation(true) == regulation(false));
+            if result.extra_time {
+                went_to_extra_time += 1;
+            } else {
+                assert!(result.goals.iter().all(|g| g.minute.base <= 90));
+            }
+        }
+        assert!(went_to_extra_time > 0);
+    }
+}
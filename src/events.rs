@@ -0,0 +1,462 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::data::display_name;
+use crate::utils::pad2;
+
+/// Match clock reading. `added` counts stoppage minutes played after `base`
+/// (e.g. 45+2 is `base: 45, added: 2`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct Minute {
+    pub base: u8,
+    pub added: u8,
+}
+
+impl Minute {
+    /// Zero-padded form used in live logs and the scoreboard ("05", "45+2").
+    pub fn padded(self) -> String {
+        if self.added > 0 {
+            self.to_string()
+        } else {
+            pad2(self.base)
+        }
+    }
+}
+
+impl fmt::Display for Minute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.added > 0 {
+            write!(f, "{}+{}", self.base, self.added)
+        } else {
+            write!(f, "{}", self.base)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CardKind {
+    Yellow,
+    SecondYellow,
+    Red,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SetPiece {
+    Corner,
+    FreeKick,
+    Penalty,
+}
+
+impl SetPiece {
+    pub fn label(self) -> &'static str {
+        match self {
+            SetPiece::Corner => "Corner",
+            SetPiece::FreeKick => "Free Kick",
+            SetPiece::Penalty => "Penalty",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShotType {
+    InsideBox,
+    LongRange,
+    Header,
+    OneOnOne,
+    Rebound,
+    FreeKick,
+    Penalty,
+}
+
+impl ShotType {
+    pub const ALL: [ShotType; 7] = [
+        ShotType::InsideBox,
+        ShotType::LongRange,
+        ShotType::Header,
+        ShotType::OneOnOne,
+        ShotType::Rebound,
+        ShotType::FreeKick,
+        ShotType::Penalty,
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ShotType::InsideBox => "Inside Box",
+            ShotType::LongRange => "Long Range",
+            ShotType::Header => "Header",
+            ShotType::OneOnOne => "One-on-One",
+            ShotType::Rebound => "Rebound",
+            ShotType::FreeKick => "Direct Free Kick",
+            ShotType::Penalty => "Penalty",
+        }
+    }
+
+    /// How a goal of this type is described in the live log.
+    pub fn finish(self) -> &'static str {
+        match self {
+            ShotType::InsideBox => "drilled low",
+            ShotType::LongRange => "rocket from distance",
+            ShotType::Header => "powerful header",
+            ShotType::OneOnOne => "one-on-one finish",
+            ShotType::Rebound => "rebound tap-in",
+            ShotType::FreeKick => "free kick into the top corner",
+            ShotType::Penalty => "penalty",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreLine {
+    pub home: String,
+    pub away: String,
+    pub home_goals: u8,
+    pub away_goals: u8,
+}
+
+impl ScoreLine {
+    fn text(&self) -> String {
+        format!(
+            "{} {}-{} {}",
+            display_name(&self.home),
+            self.home_goals,
+            self.away_goals,
+            display_name(&self.away)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PenaltyKick {
+    pub number: u16,
+    pub sudden_death: bool,
+    pub team: String,
+    pub taker: String,
+    pub keeper: String,
+    pub scored: bool,
+    pub saved: bool,
+    pub home_score: u8,
+    pub away_score: u8,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TacticChange {
+    pub minute: Minute,
+    pub team: String,
+    pub from: String,
+    pub to: String,
+    pub reason: String,
+}
+
+impl TacticChange {
+    pub fn describe(&self) -> String {
+        format!(
+            "{}' {} switch from {} to {} ({}).",
+            self.minute.padded(),
+            display_name(&self.team),
+            self.from,
+            self.to,
+            self.reason
+        )
+    }
+}
+
+/// Typed match event. Display strings for the live log are derived from these via
+/// [`MatchEvent::describe`], so consumers never have to parse log text.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MatchEvent {
+    Kickoff {
+        home: String,
+        home_formation: String,
+        home_tactic: String,
+        away: String,
+        away_formation: String,
+        away_tactic: String,
+        neutral_venue: bool,
+    },
+    /// Any attempt at goal. Goals and saves are reported as their own events as well.
+    Shot {
+        minute: Minute,
+        team: String,
+        player: String,
+        xg: f64,
+        shot_type: ShotType,
+        on_target: bool,
+        near_miss: bool,
+        set_piece: Option<SetPiece>,
+    },
+    Goal {
+        minute: Minute,
+        team: String,
+        player: String,
+        xg: f64,
+        shot_type: ShotType,
+        set_piece: Option<SetPiece>,
+    },
+    Save {
+        minute: Minute,
+        team: String,
+        keeper: String,
+        xg: f64,
+    },
+    Corner {
+        minute: Minute,
+        team: String,
+    },
+    Offside {
+        minute: Minute,
+        team: String,
+    },
+    Foul {
+        minute: Minute,
+        team: String,
+        player: String,
+    },
+    /// Free kick in shooting range, awarded to `team`.
+    FreeKick {
+        minute: Minute,
+        team: String,
+    },
+    /// Penalty awarded to `team` for a foul by `player`.
+    PenaltyAwarded {
+        minute: Minute,
+        team: String,
+        player: String,
+    },
+    Card {
+        minute: Minute,
+        team: String,
+        player: String,
+        card: CardKind,
+    },
+    TacticChange(TacticChange),
+    StoppageTime {
+        minute: Minute,
+        added: u8,
+    },
+    HalfTime(ScoreLine),
+    NormalTimeEnd(ScoreLine),
+    ExtraTimeStart {
+        half: u8,
+    },
+    ExtraTimeHalfTime(ScoreLine),
+    FullTime {
+        score: ScoreLine,
+        after_extra_time: bool,
+    },
+    ShootoutStart {
+        first_team: String,
+    },
+    PenaltyKick(PenaltyKick),
+    ShootoutEnd {
+        winner: String,
+        winner_score: u8,
+        loser_score: u8,
+    },
+}
+
+impl MatchEvent {
+    /// Live-log line for this event, or `None` for events that are tracked but not narrated.
+    pub fn describe(&self) -> Option<String> {
+        let line = match self {
+            MatchEvent::Kickoff {
+                home,
+                home_formation,
+                home_tactic,
+                away,
+                away_formation,
+                away_tactic,
+                neutral_venue,
+            } => format!(
+                "Kickoff! {} ({}, {}) vs {} ({}, {}){}",
+                display_name(home),
+                home_formation,
+                home_tactic,
+                display_name(away),
+                away_formation,
+                away_tactic,
+                if *neutral_venue {
+                    " at a neutral venue"
+                } else {
+                    ""
+                }
+            ),
+            MatchEvent::Shot {
+                minute,
+                team,
+                player,
+                on_target: false,
+                near_miss: true,
+                ..
+            } => format!(
+                "{}' {} ({}) fires it just wide.",
+                minute.padded(),
+                player,
+                display_name(team)
+            ),
+            MatchEvent::Shot { .. } | MatchEvent::Foul { .. } => return None,
+            MatchEvent::Goal {
+                minute,
+                team,
+                player,
+                xg,
+                shot_type,
+                set_piece,
+            } => format!(
+                "{}' GOOOOAL - {} - {} ({}{}, xG {:.2})",
+                minute.padded(),
+                display_name(team),
+                player,
+                shot_type.finish(),
+                match set_piece {
+                    Some(SetPiece::Corner) => " from a corner",
+                    Some(SetPiece::FreeKick) if *shot_type != ShotType::FreeKick => {
+                        " from a free kick"
+                    }
+                    _ => "",
+                },
+                xg
+            ),
+            MatchEvent::Save {
+                minute,
+                team,
+                keeper,
+                ..
+            } => format!(
+                "{}' Big save by {}, {}'s keeper!",
+                minute.padded(),
+                keeper,
+                display_name(team)
+            ),
+            MatchEvent::Corner { minute, team } => {
+                format!("{}' Corner to {}.", minute.padded(), display_name(team))
+            }
+            MatchEvent::FreeKick { minute, team } => format!(
+                "{}' Free kick to {} in a dangerous position.",
+                minute.padded(),
+                display_name(team)
+            ),
+            MatchEvent::PenaltyAwarded {
+                minute,
+                team,
+                player,
+            } => format!(
+                "{}' PENALTY to {}! {} brings down an attacker in the box.",
+                minute.padded(),
+                display_name(team),
+                player
+            ),
+            MatchEvent::Offside { minute, team } => format!(
+                "{}' Flag up - {} caught offside.",
+                minute.padded(),
+                display_name(team)
+            ),
+            MatchEvent::Card {
+                minute,
+                team,
+                player,
+                card,
+            } => match card {
+                CardKind::Yellow => format!(
+                    "{}' Yellow card to {} ({}).",
+                    minute.padded(),
+                    player,
+                    display_name(team)
+                ),
+                CardKind::SecondYellow => format!(
+                    "{}' Second yellow - {} ({}) is sent off!",
+                    minute.padded(),
+                    player,
+                    display_name(team)
+                ),
+                CardKind::Red => format!(
+                    "{}' RED CARD! {} ({}) is sent off.",
+                    minute.padded(),
+                    player,
+                    display_name(team)
+                ),
+            },
+            MatchEvent::TacticChange(change) => change.describe(),
+            MatchEvent::StoppageTime { minute, added } => format!(
+                "{}' Fourth official signals {} added minute{}.",
+                minute.padded(),
+                added,
+                if *added == 1 { "" } else { "s" }
+            ),
+            MatchEvent::HalfTime(score) => format!("Halftime - {}", score.text()),
+            MatchEvent::NormalTimeEnd(score) => {
+                format!(
+                    "End of normal time - {}. Extra time to follow.",
+                    score.text()
+                )
+            }
+            MatchEvent::ExtraTimeStart { half } => format!(
+                "Extra time - {} half underway",
+                if *half == 1 { "first" } else { "second" }
+            ),
+            MatchEvent::ExtraTimeHalfTime(score) => {
+                format!("Extra time half-time - {}", score.text())
+            }
+            MatchEvent::FullTime {
+                score,
+                after_extra_time,
+            } => {
+                if *after_extra_time {
+                    format!("Full time after extra time - {}", score.text())
+                } else {
+                    format!("Full time - {}", score.text())
+                }
+            }
+            MatchEvent::ShootoutStart { first_team } => format!(
+                "Penalty shootout - {} to kick first",
+                display_name(first_team)
+            ),
+            MatchEvent::PenaltyKick(kick) => kick.describe(),
+            MatchEvent::ShootoutEnd {
+                winner,
+                winner_score,
+                loser_score,
+            } => format!(
+                "{} win the shootout {}-{}",
+                display_name(winner),
+                winner_score,
+                loser_score
+            ),
+        };
+        Some(line)
+    }
+}
+
+impl PenaltyKick {
+    pub fn describe(&self) -> String {
+        let outcome = if self.scored {
+            "scores".to_string()
+        } else if self.saved {
+            format!("saved by {}", self.keeper)
+        } else {
+            "misses".to_string()
+        };
+        format!(
+            "{}Kick {}: {} - {} {}, {}-{}",
+            if self.sudden_death {
+                "Sudden death - "
+            } else {
+                ""
+            },
+            self.number,
+            display_name(&self.team),
+            self.taker,
+            outcome,
+            self.home_score,
+            self.away_score
+        )
+    }
+}
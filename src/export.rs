@@ -1,9 +1,12 @@
 use std::io::{self, Write};
 
-use crate::sim::{PreparedSimulation, SimOutcome};
+use crate::data::display_name;
+use crate::events::{CardKind, MatchEvent, SetPiece, ShotType};
+use crate::season::{division_name, SeasonReport};
+use crate::sim::{PreparedSimulation, SimOutcome, StandingsRow, TieRecord};
 use crate::utils::csv_escape;
 
-fn write_row<W: Write>(mut w: W, cols: &[String]) -> io::Result<()> {
+pub fn write_row<W: Write>(mut w: W, cols: &[String]) -> io::Result<()> {
     let mut first = true;
     for col in cols {
         if !first {
@@ -15,6 +18,40 @@ fn write_row<W: Write>(mut w: W, cols: &[String]) -> io::Result<()> {
     w.write_all(b"\n")
 }
 
+/// Rule that put a row above the next one, or empty.
+fn tie_break_cell(row: &StandingsRow) -> String {
+    row.tie_break
+        .map(|t| t.label().to_string())
+        .unwrap_or_default()
+}
+
+/// Knockout rounds tie by tie: any legs, the tie line and shootout kicks.
+fn write_knockout<W: Write>(mut out: W, champion: &str, ties: &[TieRecord]) -> io::Result<()> {
+    write_row(&mut out, &["Stage".to_string(), "Match Result".to_string()])?;
+    for tie in ties {
+        for leg in &tie.legs {
+            write_row(&mut out, &[tie.stage.clone(), leg.clone()])?;
+        }
+        write_row(&mut out, &[tie.stage.clone(), tie.line.clone()])?;
+        for kick in &tie.kicks {
+            write_row(
+                &mut out,
+                &[
+                    tie.stage.clone(),
+                    format!("{} pens - {}", tie.stage, kick.describe()),
+                ],
+            )?;
+        }
+    }
+    write_row(
+        &mut out,
+        &[
+            "Champion".to_string(),
+            format!("Champion: {} 🏆", display_name(champion)),
+        ],
+    )
+}
+
 pub fn simulation_to_csv_bytes(sim: &PreparedSimulation) -> io::Result<Vec<u8>> {
     let mut out: Vec<u8> = Vec::new();
 
@@ -80,6 +117,22 @@ pub fn simulation_to_csv_bytes(sim: &PreparedSimulation) -> io::Result<Vec<u8>>
                     m.stats.away.corners.to_string(),
                 ],
             )?;
+            write_row(
+                &mut out,
+                &[
+                    "Set-piece Goals".to_string(),
+                    m.stats.home.set_piece_goals.to_string(),
+                    m.stats.away.set_piece_goals.to_string(),
+                ],
+            )?;
+            write_row(
+                &mut out,
+                &[
+                    "Set-piece xG".to_string(),
+                    format!("{:.2}", m.stats.home.set_piece_xg),
+                    format!("{:.2}", m.stats.away.set_piece_xg),
+                ],
+            )?;
             write_row(
                 &mut out,
                 &[
@@ -96,6 +149,14 @@ pub fn simulation_to_csv_bytes(sim: &PreparedSimulation) -> io::Result<Vec<u8>>
                     m.stats.away.yellows.to_string(),
                 ],
             )?;
+            write_row(
+                &mut out,
+                &[
+                    "Red Cards".to_string(),
+                    m.stats.home.reds.to_string(),
+                    m.stats.away.reds.to_string(),
+                ],
+            )?;
             write_row(
                 &mut out,
                 &[
@@ -104,8 +165,216 @@ pub fn simulation_to_csv_bytes(sim: &PreparedSimulation) -> io::Result<Vec<u8>>
                     m.stats.away.saves.to_string(),
                 ],
             )?;
+            write_row(
+                &mut out,
+                &[
+                    "Scorers".to_string(),
+                    m.scorers_text(&m.home),
+                    m.scorers_text(&m.away),
+                ],
+            )?;
+
+            out.write_all(b"\n")?;
+            write_row(
+                &mut out,
+                &[
+                    "Team".to_string(),
+                    "Number".to_string(),
+                    "Player".to_string(),
+                    "Position".to_string(),
+                    "Shots".to_string(),
+                    "Goals".to_string(),
+                    "Saves".to_string(),
+                    "Fouls".to_string(),
+                    "Yellow Cards".to_string(),
+                    "Sent Off".to_string(),
+                ],
+            )?;
+            for (team, team_stats) in [(&m.home, &m.stats.home), (&m.away, &m.stats.away)] {
+                for p in &team_stats.players {
+                    write_row(
+                        &mut out,
+                        &[
+                            team.clone(),
+                            p.number.to_string(),
+                            p.name.clone(),
+                            p.position.short().to_string(),
+                            p.shots.to_string(),
+                            p.goals.to_string(),
+                            p.saves.to_string(),
+                            p.fouls.to_string(),
+                            p.yellows.to_string(),
+                            if p.sent_off { "Yes" } else { "No" }.to_string(),
+                        ],
+                    )?;
+                }
+            }
+
+            out.write_all(b"\n")?;
+            write_row(
+                &mut out,
+                &[
+                    "Team".to_string(),
+                    "Shot Type".to_string(),
+                    "Shots".to_string(),
+                    "On Target".to_string(),
+                    "Goals".to_string(),
+                    "xG".to_string(),
+                ],
+            )?;
+            for (team, stats) in [(&m.home, &m.stats.home), (&m.away, &m.stats.away)] {
+                for shot_type in ShotType::ALL {
+                    let t = stats.shot_types[shot_type.index()];
+                    write_row(
+                        &mut out,
+                        &[
+                            team.clone(),
+                            shot_type.label().to_string(),
+                            t.shots.to_string(),
+                            t.on_target.to_string(),
+                            t.goals.to_string(),
+                            format!("{:.2}", t.xg),
+                        ],
+                    )?;
+                }
+            }
+
+            out.write_all(b"\n")?;
+            write_row(
+                &mut out,
+                &[
+                    "Minute".to_string(),
+                    "Team".to_string(),
+                    "Event".to_string(),
+                    "Player".to_string(),
+                    "xG".to_string(),
+                    "Shot Type".to_string(),
+                    "Set Piece".to_string(),
+                ],
+            )?;
+            for event in sim.frames.iter().flat_map(|f| &f.events) {
+                let tactic_label;
+                let shot_type = match event {
+                    MatchEvent::Shot { shot_type, .. } | MatchEvent::Goal { shot_type, .. } => {
+                        Some(*shot_type)
+                    }
+                    _ => None,
+                };
+                let (minute, team, kind, player, xg, set_piece) = match event {
+                    MatchEvent::Shot {
+                        minute,
+                        team,
+                        player,
+                        xg,
+                        on_target,
+                        set_piece,
+                        ..
+                    } => (
+                        minute,
+                        team,
+                        if *on_target {
+                            "Shot on Target"
+                        } else {
+                            "Shot off Target"
+                        },
+                        player.as_str(),
+                        Some(xg),
+                        *set_piece,
+                    ),
+                    MatchEvent::Goal {
+                        minute,
+                        team,
+                        player,
+                        xg,
+                        set_piece,
+                        ..
+                    } => (minute, team, "Goal", player.as_str(), Some(xg), *set_piece),
+                    MatchEvent::Save {
+                        minute,
+                        team,
+                        keeper,
+                        xg,
+                    } => (minute, team, "Save", keeper.as_str(), Some(xg), None),
+                    MatchEvent::Corner { minute, team } => {
+                        (minute, team, "Corner", "", None, Some(SetPiece::Corner))
+                    }
+                    MatchEvent::FreeKick { minute, team } => (
+                        minute,
+                        team,
+                        "Free Kick",
+                        "",
+                        None,
+                        Some(SetPiece::FreeKick),
+                    ),
+                    MatchEvent::PenaltyAwarded {
+                        minute,
+                        team,
+                        player,
+                    } => (
+                        minute,
+                        team,
+                        "Penalty Awarded",
+                        player.as_str(),
+                        None,
+                        Some(SetPiece::Penalty),
+                    ),
+                    MatchEvent::Offside { minute, team } => {
+                        (minute, team, "Offside", "", None, None)
+                    }
+                    MatchEvent::Foul {
+                        minute,
+                        team,
+                        player,
+                    } => (minute, team, "Foul", player.as_str(), None, None),
+                    MatchEvent::Card {
+                        minute,
+                        team,
+                        player,
+                        card,
+                    } => (
+                        minute,
+                        team,
+                        match card {
+                            CardKind::Yellow => "Yellow Card",
+                            CardKind::SecondYellow => "Second Yellow",
+                            CardKind::Red => "Red Card",
+                        },
+                        player.as_str(),
+                        None,
+                        None,
+                    ),
+                    MatchEvent::TacticChange(change) => {
+                        tactic_label = format!("Tactic: {}", change.to);
+                        (
+                            &change.minute,
+                            &change.team,
+                            tactic_label.as_str(),
+                            "",
+                            None,
+                            None,
+                        )
+                    }
+                    _ => continue,
+                };
+                write_row(
+                    &mut out,
+                    &[
+                        minute.to_string(),
+                        team.clone(),
+                        kind.to_string(),
+                        player.to_string(),
+                        xg.map(|v| format!("{:.2}", v)).unwrap_or_default(),
+                        shot_type.map(|st| st.label()).unwrap_or("").to_string(),
+                        set_piece.map(|sp| sp.label()).unwrap_or("").to_string(),
+                    ],
+                )?;
+            }
         }
-        SimOutcome::League { final_table, .. } => {
+        SimOutcome::League {
+            final_table,
+            fixtures,
+            ..
+        } => {
             write_row(
                 &mut out,
                 &[
@@ -118,6 +387,19 @@ pub fn simulation_to_csv_bytes(sim: &PreparedSimulation) -> io::Result<Vec<u8>>
                     "GA".to_string(),
                     "GD".to_string(),
                     "Pts".to_string(),
+                    "Home W".to_string(),
+                    "Home D".to_string(),
+                    "Home L".to_string(),
+                    "Home GF".to_string(),
+                    "Home GA".to_string(),
+                    "Home Pts".to_string(),
+                    "Away W".to_string(),
+                    "Away D".to_string(),
+                    "Away L".to_string(),
+                    "Away GF".to_string(),
+                    "Away GA".to_string(),
+                    "Away Pts".to_string(),
+                    "Tie-break".to_string(),
                 ],
             )?;
 
@@ -134,26 +416,324 @@ pub fn simulation_to_csv_bytes(sim: &PreparedSimulation) -> io::Result<Vec<u8>>
                         row.ga.to_string(),
                         row.gd.to_string(),
                         row.pts.to_string(),
+                        row.home.w.to_string(),
+                        row.home.d.to_string(),
+                        row.home.l.to_string(),
+                        row.home.gf.to_string(),
+                        row.home.ga.to_string(),
+                        row.home.pts.to_string(),
+                        row.away.w.to_string(),
+                        row.away.d.to_string(),
+                        row.away.l.to_string(),
+                        row.away.gf.to_string(),
+                        row.away.ga.to_string(),
+                        row.away.pts.to_string(),
+                        tie_break_cell(row),
+                    ],
+                )?;
+            }
+
+            out.write_all(b"\n")?;
+            write_row(
+                &mut out,
+                &[
+                    "Matchday".to_string(),
+                    "Home".to_string(),
+                    "Away".to_string(),
+                    "Home Goals".to_string(),
+                    "Away Goals".to_string(),
+                ],
+            )?;
+            for fixture in fixtures {
+                write_row(
+                    &mut out,
+                    &[
+                        fixture.matchday.to_string(),
+                        fixture.home.clone(),
+                        fixture.away.clone(),
+                        fixture.home_goals.to_string(),
+                        fixture.away_goals.to_string(),
                     ],
                 )?;
             }
         }
-        SimOutcome::Knockout { .. } => {
-            write_row(&mut out, &["Stage".to_string(), "Match Result".to_string()])?;
-            for line in &sim.history_lines {
-                let stage = if line.starts_with("Semi 1") {
-                    "Semi 1"
-                } else if line.starts_with("Semi 2") {
-                    "Semi 2"
-                } else if line.starts_with("Final") {
-                    "Final"
-                } else if line.starts_with("Champion") {
-                    "Champion"
-                } else {
-                    "Info"
-                };
-                write_row(&mut out, &[stage.to_string(), line.clone()])?;
+        SimOutcome::Knockout { champion, ties } => {
+            write_knockout(&mut out, champion, ties)?;
+        }
+        SimOutcome::Tournament {
+            champion,
+            groups,
+            ties,
+        } => {
+            for group in groups {
+                write_row(
+                    &mut out,
+                    &[
+                        group.name.clone(),
+                        "P".to_string(),
+                        "W".to_string(),
+                        "D".to_string(),
+                        "L".to_string(),
+                        "GF".to_string(),
+                        "GA".to_string(),
+                        "GD".to_string(),
+                        "Pts".to_string(),
+                        "Tie-break".to_string(),
+                    ],
+                )?;
+                for row in &group.table {
+                    write_row(
+                        &mut out,
+                        &[
+                            row.team.clone(),
+                            row.p.to_string(),
+                            row.w.to_string(),
+                            row.d.to_string(),
+                            row.l.to_string(),
+                            row.gf.to_string(),
+                            row.ga.to_string(),
+                            row.gd.to_string(),
+                            row.pts.to_string(),
+                            tie_break_cell(row),
+                        ],
+                    )?;
+                }
+                write_row(
+                    &mut out,
+                    &[
+                        "Matchday".to_string(),
+                        "Home".to_string(),
+                        "Away".to_string(),
+                        "Home Goals".to_string(),
+                        "Away Goals".to_string(),
+                    ],
+                )?;
+                for fixture in &group.fixtures {
+                    write_row(
+                        &mut out,
+                        &[
+                            fixture.matchday.to_string(),
+                            fixture.home.clone(),
+                            fixture.away.clone(),
+                            fixture.home_goals.to_string(),
+                            fixture.away_goals.to_string(),
+                        ],
+                    )?;
+                }
+                out.write_all(b"\n")?;
             }
+            write_knockout(&mut out, champion, ties)?;
+        }
+        SimOutcome::Swiss {
+            table,
+            fixtures,
+            format,
+            ..
+        } => {
+            write_row(
+                &mut out,
+                &[
+                    "Pos".to_string(),
+                    "Team".to_string(),
+                    "P".to_string(),
+                    "W".to_string(),
+                    "D".to_string(),
+                    "L".to_string(),
+                    "GF".to_string(),
+                    "GA".to_string(),
+                    "GD".to_string(),
+                    "Pts".to_string(),
+                    "Zone".to_string(),
+                    "Tie-break".to_string(),
+                ],
+            )?;
+            for (pos, row) in table.iter().enumerate() {
+                write_row(
+                    &mut out,
+                    &[
+                        (pos + 1).to_string(),
+                        row.team.clone(),
+                        row.p.to_string(),
+                        row.w.to_string(),
+                        row.d.to_string(),
+                        row.l.to_string(),
+                        row.gf.to_string(),
+                        row.ga.to_string(),
+                        row.gd.to_string(),
+                        row.pts.to_string(),
+                        format.zone(table.len(), pos).label().to_string(),
+                        tie_break_cell(row),
+                    ],
+                )?;
+            }
+
+            out.write_all(b"\n")?;
+            write_row(
+                &mut out,
+                &[
+                    "Matchday".to_string(),
+                    "Home".to_string(),
+                    "Away".to_string(),
+                    "Home Goals".to_string(),
+                    "Away Goals".to_string(),
+                ],
+            )?;
+            for fixture in fixtures {
+                write_row(
+                    &mut out,
+                    &[
+                        fixture.matchday.to_string(),
+                        fixture.home.clone(),
+                        fixture.away.clone(),
+                        fixture.home_goals.to_string(),
+                        fixture.away_goals.to_string(),
+                    ],
+                )?;
+            }
+        }
+    }
+
+    // The timeline has no fixture column, so it belongs to single matches only.
+    let mut odds = sim
+        .frames
+        .iter()
+        .filter_map(|f| f.win_prob.as_ref())
+        .peekable();
+    if matches!(sim.outcome, SimOutcome::Single(_)) && odds.peek().is_some() {
+        out.write_all(b"\n")?;
+        write_row(
+            &mut out,
+            &[
+                "Minute".to_string(),
+                "Home".to_string(),
+                "Away".to_string(),
+                "Home Win %".to_string(),
+                "Draw %".to_string(),
+                "Away Win %".to_string(),
+            ],
+        )?;
+        for prob in odds {
+            write_row(
+                &mut out,
+                &[
+                    prob.minute.to_string(),
+                    prob.home.clone(),
+                    prob.away.clone(),
+                    format!("{:.1}", prob.home_win * 100.0),
+                    format!("{:.1}", prob.draw * 100.0),
+                    format!("{:.1}", prob.away_win * 100.0),
+                ],
+            )?;
+        }
+    }
+
+    Ok(out)
+}
+
+/// Final tables of every season played, then play-off ties, then the movement report.
+pub fn season_reports_to_csv_bytes(reports: &[SeasonReport]) -> io::Result<Vec<u8>> {
+    let mut out: Vec<u8> = Vec::new();
+
+    write_row(
+        &mut out,
+        &[
+            "Season".to_string(),
+            "Division".to_string(),
+            "Pos".to_string(),
+            "Team".to_string(),
+            "P".to_string(),
+            "W".to_string(),
+            "D".to_string(),
+            "L".to_string(),
+            "GF".to_string(),
+            "GA".to_string(),
+            "GD".to_string(),
+            "Pts".to_string(),
+            "Status".to_string(),
+            "Tie-break".to_string(),
+        ],
+    )?;
+    for report in reports {
+        for division in &report.divisions {
+            let rows = division.table.iter().zip(&division.status);
+            for (pos, (row, status)) in rows.enumerate() {
+                write_row(
+                    &mut out,
+                    &[
+                        report.season.to_string(),
+                        division.name.clone(),
+                        (pos + 1).to_string(),
+                        row.team.clone(),
+                        row.p.to_string(),
+                        row.w.to_string(),
+                        row.d.to_string(),
+                        row.l.to_string(),
+                        row.gf.to_string(),
+                        row.ga.to_string(),
+                        row.gd.to_string(),
+                        row.pts.to_string(),
+                        status.label().to_string(),
+                        tie_break_cell(row),
+                    ],
+                )?;
+            }
+        }
+    }
+
+    let mut playoffs = reports
+        .iter()
+        .flat_map(|r| r.divisions.iter().map(move |d| (r.season, d)))
+        .flat_map(|(season, d)| d.playoff.iter().map(move |tie| (season, &d.name, tie)))
+        .peekable();
+    if playoffs.peek().is_some() {
+        out.write_all(b"\n")?;
+        write_row(
+            &mut out,
+            &[
+                "Season".to_string(),
+                "Division".to_string(),
+                "Stage".to_string(),
+                "Match Result".to_string(),
+            ],
+        )?;
+        for (season, division, tie) in playoffs {
+            for line in tie.legs.iter().chain(std::iter::once(&tie.line)) {
+                write_row(
+                    &mut out,
+                    &[
+                        season.to_string(),
+                        division.clone(),
+                        tie.stage.clone(),
+                        line.clone(),
+                    ],
+                )?;
+            }
+        }
+    }
+
+    out.write_all(b"\n")?;
+    write_row(
+        &mut out,
+        &[
+            "Season".to_string(),
+            "Team".to_string(),
+            "From".to_string(),
+            "To".to_string(),
+            "Reason".to_string(),
+        ],
+    )?;
+    for report in reports {
+        for movement in &report.movements {
+            write_row(
+                &mut out,
+                &[
+                    report.season.to_string(),
+                    movement.team.clone(),
+                    division_name(movement.from),
+                    division_name(movement.to),
+                    movement.reason.to_string(),
+                ],
+            )?;
         }
     }
 
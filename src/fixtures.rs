@@ -0,0 +1,127 @@
+/// One round of a round robin: pairings by team index (home, away) and the team
+/// sitting out when the league has an odd number of teams.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matchday {
+    pub fixtures: Vec<(usize, usize)>,
+    pub bye: Option<usize>,
+}
+
+/// Circle-method (Berger table) schedule for `team_count` teams: every pair meets once
+/// per round robin and each team plays at most once per matchday. Venues alternate
+/// for every team except for the unavoidable breaks. `double` appends the return legs
+/// with venues swapped.
+pub fn round_robin(team_count: usize, double: bool) -> Vec<Matchday> {
+    if team_count < 2 {
+        return Vec::new();
+    }
+
+    // Teams 0..circle rotate around the circle; team `circle` stays pinned. Odd
+    // leagues pin a phantom team, and whoever it meets has the bye.
+    let circle = team_count + team_count % 2 - 1;
+    let pinned = circle;
+
+    let mut first_half = Vec::with_capacity(circle);
+    for round in 0..circle {
+        let mut fixtures = Vec::with_capacity(team_count / 2);
+        let mut bye = None;
+        if pinned < team_count {
+            if round % 2 == 0 {
+                fixtures.push((round, pinned));
+            } else {
+                fixtures.push((pinned, round));
+            }
+        } else {
+            bye = Some(round);
+        }
+        // The rest of the circle pairs off around `round`, alternating orientation
+        // so neighbours in the circle do not share a venue pattern.
+        for k in 1..=(circle - 1) / 2 {
+            let a = (round + k) % circle;
+            let b = (round + circle - k) % circle;
+            if k % 2 == 1 {
+                fixtures.push((a, b));
+            } else {
+                fixtures.push((b, a));
+            }
+        }
+        first_half.push(Matchday { fixtures, bye });
+    }
+
+    let mut matchdays = first_half.clone();
+    if double {
+        matchdays.extend(first_half.into_iter().map(|md| Matchday {
+            fixtures: md.fixtures.into_iter().map(|(h, a)| (a, h)).collect(),
+            bye: md.bye,
+        }));
+    }
+    matchdays
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    /// How often each unordered pair of teams meets.
+    fn meetings(matchdays: &[Matchday]) -> BTreeMap<(usize, usize), usize> {
+        let mut met = BTreeMap::new();
+        for md in matchdays {
+            for &(h, a) in &md.fixtures {
+                *met.entry((h.min(a), h.max(a))).or_insert(0) += 1;
+            }
+        }
+        met
+    }
+
+    #[test]
+    fn every_team_meets_every_other_exactly_once() {
+        for team_count in 2..=21 {
+            let met = meetings(&round_robin(team_count, false));
+            assert_eq!(met.len(), team_count * (team_count - 1) / 2);
+            assert!(met.values().all(|&n| n == 1), "{team_count} teams: {met:?}");
+        }
+    }
+
+    #[test]
+    fn teams_play_at_most_once_per_matchday() {
+        for team_count in 2..=21 {
+            for md in round_robin(team_count, false) {
+                let mut seen = vec![false; team_count];
+                for &(h, a) in &md.fixtures {
+                    assert_ne!(h, a);
+                    assert!(!seen[h] && !seen[a], "{team_count} teams: {md:?}");
+                    seen[h] = true;
+                    seen[a] = true;
+                }
+                assert_eq!(md.bye.is_some(), team_count % 2 == 1);
+                if let Some(bye) = md.bye {
+                    assert!(!seen[bye]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn odd_leagues_give_every_team_one_bye() {
+        let matchdays = round_robin(7, false);
+        assert_eq!(matchdays.len(), 7);
+        let mut byes: Vec<usize> = matchdays.iter().filter_map(|md| md.bye).collect();
+        byes.sort_unstable();
+        assert_eq!(byes, (0..7).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn double_round_robin_swaps_venues() {
+        let matchdays = round_robin(6, true);
+        assert_eq!(matchdays.len(), 10);
+        let (first, second) = matchdays.split_at(5);
+        for (out, back) in first.iter().zip(second) {
+            let swapped: Vec<(usize, usize)> = out.fixtures.iter().map(|&(h, a)| (a, h)).collect();
+            assert_eq!(back.fixtures, swapped);
+        }
+        let met = meetings(&matchdays);
+        assert_eq!(met.len(), 15);
+        assert!(met.values().all(|&n| n == 2));
+    }
+}
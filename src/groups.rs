@@ -0,0 +1,156 @@
+use crate::bracket::{seed_order, DrawKind};
+use crate::utils::Rng;
+
+/// Teams per tournament group.
+pub const GROUP_SIZE: usize = 4;
+/// Two groups is the smallest tournament with a knockout stage worth playing.
+pub const MIN_TOURNAMENT_TEAMS: usize = 2 * GROUP_SIZE;
+
+/// "Group A", "Group B", ...
+pub fn group_name(idx: usize) -> String {
+    format!("Group {}", group_letter(idx))
+}
+
+pub fn group_letter(idx: usize) -> char {
+    (b'A' + (idx % 26) as u8) as char
+}
+
+/// Split `teams` (a multiple of [`GROUP_SIZE`]) into groups of four drawn from pots.
+/// The first quarter of the list is pot 1, the next quarter pot 2, and so on. A seeded
+/// draw puts pot 1 at the head of the groups in the order given and draws the other
+/// pots across groups; a random draw shuffles every team before potting. Pot draws
+/// with separation rules are made by [`crate::draw::draw_groups_from_pots`].
+pub fn draw_groups(teams: &[String], kind: DrawKind, rng: &mut Rng) -> Vec<Vec<String>> {
+    let group_count = teams.len() / GROUP_SIZE;
+    let mut pool = teams.to_vec();
+    if kind == DrawKind::Random {
+        rng.shuffle(&mut pool);
+    }
+
+    let mut groups = vec![Vec::with_capacity(GROUP_SIZE); group_count];
+    for (pot_idx, pot) in pool.chunks(group_count).enumerate() {
+        let mut pot = pot.to_vec();
+        if pot_idx > 0 {
+            rng.shuffle(&mut pot);
+        }
+        for (group, team) in groups.iter_mut().zip(pot) {
+            group.push(team);
+        }
+    }
+    groups
+}
+
+/// Third-placed teams that go through alongside the top two of every group: enough
+/// to fill the knockout bracket to a power of two, and never more than one per group.
+pub fn best_third_count(group_count: usize) -> usize {
+    let top_two = group_count * 2;
+    (top_two.next_power_of_two() - top_two).min(group_count)
+}
+
+/// A team through to the knockout stage and where it finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Qualifier {
+    pub team: String,
+    pub group: usize,
+    /// Final group position, 1-based.
+    pub position: usize,
+}
+
+impl Qualifier {
+    /// Short slot code such as "1A" or "3C".
+    pub fn code(&self) -> String {
+        format!("{}{}", self.position, group_letter(self.group))
+    }
+}
+
+/// Arrange `seeds` (best first) into bracket order for [`crate::bracket::Bracket::seeded`],
+/// swapping teams of the same position so no first-round tie is a group rematch
+/// where another pairing allows it.
+pub fn knockout_seeds(mut seeds: Vec<Qualifier>) -> Vec<Qualifier> {
+    let size = seeds.len().max(2).next_power_of_two();
+    let pairs: Vec<(usize, usize)> = seed_order(size)
+        .chunks(2)
+        .map(|p| (p[0], p[1]))
+        .filter(|&(_, b)| b < seeds.len())
+        .collect();
+
+    for i in 0..pairs.len() {
+        let (a, b) = pairs[i];
+        if seeds[a].group != seeds[b].group {
+            continue;
+        }
+        // Prefer a partner with the same finishing position so seeding holds.
+        let swap = [true, false].into_iter().find_map(|same_position| {
+            pairs.iter().find_map(|&(c, d)| {
+                let fits = d != b
+                    && (!same_position || seeds[d].position == seeds[b].position)
+                    && seeds[d].group != seeds[a].group
+                    && seeds[b].group != seeds[c].group;
+                fits.then_some(d)
+            })
+        });
+        if let Some(d) = swap {
+            seeds.swap(b, d);
+        }
+    }
+    seeds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn qualifier(group: usize, position: usize) -> Qualifier {
+        Qualifier {
+            team: format!("{position}{}", group_letter(group)),
+            group,
+            position,
+        }
+    }
+
+    #[test]
+    fn best_thirds_fill_the_bracket() {
+        // Six groups send twelve teams through, so four thirds make it 16.
+        for (groups, thirds) in [(2, 0), (3, 2), (4, 0), (5, 5), (6, 4), (8, 0), (12, 8)] {
+            assert_eq!(best_third_count(groups), thirds, "{groups} groups");
+        }
+    }
+
+    #[test]
+    fn group_winners_avoid_their_runner_up() {
+        let mut rng = Rng::new(3);
+        for groups in 2..=8 {
+            for thirds in [0, best_third_count(groups)] {
+                for _ in 0..20 {
+                    let mut runners_up: Vec<_> = (0..groups).map(|g| qualifier(g, 2)).collect();
+                    let mut third_placed: Vec<_> = (0..groups).map(|g| qualifier(g, 3)).collect();
+                    rng.shuffle(&mut runners_up);
+                    rng.shuffle(&mut third_placed);
+                    let winners: Vec<_> = (0..groups).map(|g| qualifier(g, 1)).collect();
+                    let given: Vec<_> = winners
+                        .iter()
+                        .cloned()
+                        .chain(runners_up)
+                        .chain(third_placed.into_iter().take(thirds))
+                        .collect();
+
+                    let seeds = knockout_seeds(given.clone());
+                    // Winners keep their seeds; only lower seeds are moved.
+                    assert_eq!(seeds[..groups], winners[..]);
+                    let mut sorted = seeds.clone();
+                    sorted.sort_by_key(|q| (q.position, q.group));
+                    let mut expected = given;
+                    expected.sort_by_key(|q| (q.position, q.group));
+                    assert_eq!(sorted, expected);
+
+                    let size = seeds.len().next_power_of_two();
+                    for pair in seed_order(size).chunks(2) {
+                        if let (Some(a), Some(b)) = (seeds.get(pair[0]), seeds.get(pair[1])) {
+                            assert_ne!(a.group, b.group, "{} v {}", a.code(), b.code());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
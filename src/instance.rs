@@ -1,14 +1,36 @@
 use std::collections::VecDeque;
 
+use crate::events::MatchEvent;
 use crate::export::simulation_to_csv_bytes;
-use crate::sim::{run_simulation, PreparedSimulation, SimOutcome, SimulationType};
+use crate::sim::{
+    run_simulation, MatchResult, PreparedSimulation, SimConfig, SimOutcome, SimulationType,
+};
 use crate::utils::Rng;
+use crate::winprob::WinProbability;
 
 pub const MAX_LOG_LINES: usize = 1000;
 
+/// Everything needed to simulate an instance, detached so it can run on another thread.
+#[derive(Debug, Clone)]
+pub struct SimJob {
+    sim_type: SimulationType,
+    teams: Vec<String>,
+    seed: u64,
+    config: SimConfig,
+}
+
+impl SimJob {
+    pub fn run(self) -> PreparedSimulation {
+        let mut rng = Rng::new(self.seed);
+        run_simulation(self.sim_type, &self.teams, &self.config, &mut rng)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum SimStatus {
     Pending,
+    /// Started; the simulation is being computed off the caller's thread.
+    Preparing,
     Running {
         frame_index: usize,
         total_frames: usize,
@@ -22,39 +44,75 @@ pub struct SimulationInstance {
     pub sim_type: SimulationType,
     pub teams: Vec<String>,
     pub seed: u64,
+    pub config: SimConfig,
     pub status: SimStatus,
     pub scoreboard: String,
     pub logs: VecDeque<String>,
+    pub events: VecDeque<MatchEvent>,
     pub stats_lines: Vec<String>,
     pub competition_lines: Vec<String>,
     pub history_lines: Vec<String>,
+    /// Latest live win probability of the match being played.
+    pub win_prob: Option<WinProbability>,
     prepared: Option<PreparedSimulation>,
+    ratings_recorded: bool,
 }
 
 impl SimulationInstance {
-    pub fn new(id: usize, sim_type: SimulationType, teams: Vec<String>, seed: u64) -> Self {
+    pub fn new(
+        id: usize,
+        sim_type: SimulationType,
+        teams: Vec<String>,
+        seed: u64,
+        config: SimConfig,
+    ) -> Self {
         Self {
             id,
             sim_type,
             teams,
             seed,
+            config,
             status: SimStatus::Pending,
             scoreboard: "Waiting for kickoff...".to_string(),
             logs: VecDeque::with_capacity(MAX_LOG_LINES),
+            events: VecDeque::with_capacity(MAX_LOG_LINES),
             stats_lines: Vec::new(),
             competition_lines: Vec::new(),
             history_lines: Vec::new(),
+            win_prob: None,
             prepared: None,
+            ratings_recorded: false,
         }
     }
 
-    pub fn start(&mut self) {
+    /// Marks a pending instance as preparing and hands out the job to simulate it;
+    /// `None` if it was already started.
+    pub fn start(&mut self) -> Option<SimJob> {
         if !matches!(self.status, SimStatus::Pending) {
+            return None;
+        }
+        self.status = SimStatus::Preparing;
+        Some(SimJob {
+            sim_type: self.sim_type,
+            teams: self.teams.clone(),
+            seed: self.seed,
+            config: self.config.clone(),
+        })
+    }
+
+    /// Puts a started instance back to pending when its job did not finish.
+    pub fn abort_start(&mut self) {
+        if matches!(self.status, SimStatus::Preparing) {
+            self.status = SimStatus::Pending;
+        }
+    }
+
+    /// Installs the finished job's simulation and starts playback.
+    pub fn begin(&mut self, prepared: PreparedSimulation) {
+        if !matches!(self.status, SimStatus::Preparing) {
             return;
         }
 
-        let mut rng = Rng::new(self.seed);
-        let prepared = run_simulation(self.sim_type, &self.teams, &mut rng);
         let total_frames = prepared.frames.len();
         self.stats_lines.clear();
         self.competition_lines.clear();
@@ -101,6 +159,9 @@ impl SimulationInstance {
             for line in frame.logs {
                 self.push_log(line);
             }
+            for event in frame.events {
+                self.push_event(event);
+            }
             if let Some(stats) = frame.stats_lines {
                 self.stats_lines = stats;
             }
@@ -110,6 +171,9 @@ impl SimulationInstance {
             for item in frame.history_append {
                 self.history_lines.push(item);
             }
+            if frame.win_prob.is_some() {
+                self.win_prob = frame.win_prob;
+            }
             frame_index += 1;
         }
 
@@ -125,12 +189,28 @@ impl SimulationInstance {
     }
 
     pub fn clone_as(&self, new_id: usize, new_seed: u64) -> Self {
-        Self::new(new_id, self.sim_type, self.teams.clone(), new_seed)
+        Self::new(
+            new_id,
+            self.sim_type,
+            self.teams.clone(),
+            new_seed,
+            self.config.clone(),
+        )
+    }
+
+    /// Results of a completed run, handed out once so ratings count each match a single time.
+    pub fn take_results_for_ratings(&mut self) -> Option<Vec<MatchResult>> {
+        if self.ratings_recorded || !matches!(self.status, SimStatus::Completed) {
+            return None;
+        }
+        self.ratings_recorded = true;
+        self.prepared.as_ref().map(|p| p.results.clone())
     }
 
     pub fn progress_text(&self) -> String {
         match &self.status {
             SimStatus::Pending => "Ready to start".to_string(),
+            SimStatus::Preparing => "Simulating...".to_string(),
             SimStatus::Running {
                 frame_index,
                 total_frames,
@@ -156,8 +236,17 @@ impl SimulationInstance {
                 format!("{} {}-{} {}", m.home, m.home_goals, m.away_goals, m.away)
             }
             SimOutcome::League { champion, .. } => format!("Champion: {}", champion),
-            SimOutcome::Knockout { champion } => format!("Champion: {}", champion),
+            SimOutcome::Knockout { champion, .. } => format!("Champion: {}", champion),
+            SimOutcome::Tournament { champion, .. } => format!("Champion: {}", champion),
+            SimOutcome::Swiss { leader, .. } => format!("League phase winner: {}", leader),
+        }
+    }
+
+    fn push_event(&mut self, event: MatchEvent) {
+        if self.events.len() == MAX_LOG_LINES {
+            self.events.pop_front();
         }
+        self.events.push_back(event);
     }
 
     fn push_log(&mut self, line: String) {
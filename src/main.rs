@@ -1,21 +1,52 @@
 mod app;
+mod batch;
+mod bracket;
 mod data;
+mod draw;
+mod elo;
+mod engine;
+mod events;
 mod export;
+mod fixtures;
+mod groups;
 mod instance;
+mod manager;
+mod season;
+mod setpiece;
+mod shootout;
+mod shots;
 mod sim;
+mod squad;
+mod swiss;
+mod tiebreak;
 mod ui;
 mod utils;
 mod web;
+mod winprob;
 
 use std::fs::File;
 use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
 
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 use app::{resolve_quick_single_teams, run_tui, App, Speed};
-use data::{display_name, TEAMS};
-use export::simulation_to_csv_bytes;
-use sim::{run_simulation, SimulationType};
+use batch::{batch_table_lines, batch_to_csv_bytes, batch_to_json, default_threads, run_batch};
+use bracket::DrawKind;
+use data::{display_name, team_by_name, Confederation, TeamFilter, TeamKind, TEAMS_DATA};
+use draw::Separation;
+use elo::{EloStore, KFactorKind, RatingsConfig, DEFAULT_RATINGS_FILE};
+use engine::EngineKind;
+use export::{season_reports_to_csv_bytes, simulation_to_csv_bytes};
+use groups::{GROUP_SIZE, MIN_TOURNAMENT_TEAMS};
+use season::{MovementRules, Pyramid, DEFAULT_PLAYOFF, DEFAULT_PROMOTED, DEFAULT_PYRAMID_FILE};
+use sim::{
+    run_simulation, SimConfig, SimulationType, TieFormat, DEFAULT_HOME_ADVANTAGE,
+    MIN_KNOCKOUT_TEAMS, MIN_LEAGUE_TEAMS,
+};
+use swiss::{SwissFormat, DEFAULT_SWISS_MATCHES};
+use tiebreak::{parse_tie_breakers, TieBreaker};
 use utils::{derive_seed, Rng};
 use web::run_web_server;
 
@@ -38,19 +69,48 @@ struct Cli {
     #[arg(long, global = true)]
     demo: bool,
 
+    /// File holding persistent Elo ratings.
+    #[arg(long, global = true, default_value = DEFAULT_RATINGS_FILE)]
+    ratings_file: PathBuf,
+
+    /// How far each result moves Elo ratings.
+    #[arg(long, global = true, value_enum, default_value_t = KFactorArg::Fixed)]
+    k_factor: KFactorArg,
+
+    /// Do not update Elo ratings from finished simulations.
+    #[arg(long, global = true)]
+    no_ratings: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
 #[derive(Debug, Subcommand)]
 enum Commands {
+    /// Play one match headless; a missing side is auto-filled by the CPU.
     Quick {
         #[arg(long)]
         home: Option<String>,
         #[arg(long)]
         away: Option<String>,
+        /// Play on neutral ground (no home advantage).
+        #[arg(long)]
+        neutral: bool,
+        #[arg(long, default_value_t = DEFAULT_HOME_ADVANTAGE)]
+        home_advantage: f64,
+        #[arg(long, value_enum, default_value_t = EngineArg::Minute)]
+        engine: EngineArg,
+        /// Use current Elo ratings as team strength.
+        #[arg(long)]
+        elo: bool,
+        #[command(flatten)]
+        pool: PoolArgs,
+    },
+    /// List teams with their kind, country, confederation and league.
+    List {
+        #[command(flatten)]
+        pool: PoolArgs,
     },
-    List,
     Export {
         #[arg(long, value_enum)]
         mode: ModeArg,
@@ -58,6 +118,73 @@ enum Commands {
         out: String,
         #[arg(long = "team", required = true)]
         teams: Vec<String>,
+        #[command(flatten)]
+        competition: CompetitionArgs,
+    },
+    /// Run a scenario many times and report outcome probabilities.
+    /// Batch runs never update Elo ratings.
+    Batch {
+        #[arg(long, value_enum)]
+        mode: ModeArg,
+        #[arg(long = "team", required = true)]
+        teams: Vec<String>,
+        #[arg(long, default_value_t = 1000)]
+        runs: u32,
+        /// Worker threads (defaults to all available cores).
+        #[arg(long)]
+        threads: Option<usize>,
+        #[arg(long, value_enum, default_value_t = FormatArg::Table)]
+        format: FormatArg,
+        /// Play single matches on neutral ground.
+        #[arg(long)]
+        neutral: bool,
+        #[command(flatten)]
+        competition: CompetitionArgs,
+    },
+    /// Play seasons of a league pyramid with promotion and relegation.
+    /// The pyramid is saved after every season, so the next run carries on from it.
+    Season {
+        /// Start a new pyramid, replacing any saved one.
+        #[arg(long)]
+        new: bool,
+        /// Teams for a new pyramid, top division first (split evenly).
+        #[arg(long = "team", requires = "new")]
+        teams: Vec<String>,
+        /// Divisions in a new pyramid.
+        #[arg(long, default_value_t = 2, requires = "new")]
+        divisions: usize,
+        /// Teams per division when a new pyramid is filled from the strongest teams.
+        #[arg(long, default_value_t = 12, requires = "new")]
+        size: usize,
+        /// Automatic promotion places per division; relegation mirrors promotion.
+        #[arg(long)]
+        promoted: Option<usize>,
+        /// Teams in each promotion play-off, after the automatic places (0 for none).
+        #[arg(long)]
+        playoff: Option<usize>,
+        /// Seasons to play in this run.
+        #[arg(long, default_value_t = 1)]
+        seasons: u32,
+        /// Also write final tables and movements of the seasons played as CSV.
+        #[arg(long)]
+        out: Option<String>,
+        /// File holding the pyramid between runs.
+        #[arg(long, default_value = DEFAULT_PYRAMID_FILE)]
+        file: PathBuf,
+        #[command(flatten)]
+        sim: SimArgs,
+        /// Filters for the teams a new pyramid is filled from.
+        #[command(flatten)]
+        pool: PoolArgs,
+    },
+    /// Show or reset persistent Elo ratings.
+    Ratings {
+        /// Delete the ratings file so every team starts over.
+        #[arg(long)]
+        reset: bool,
+        /// Include teams that have not played yet.
+        #[arg(long)]
+        all: bool,
     },
 }
 
@@ -65,7 +192,241 @@ enum Commands {
 enum ModeArg {
     Single,
     League4,
+    /// Round robin for any number of teams.
+    League,
     Knockout4,
+    /// Single-elimination bracket for any number of teams.
+    Knockout,
+    /// Groups of four, then a knockout bracket (8, 12, 16, ... teams).
+    Tournament,
+    /// Swiss league phase: one table, opponents drawn from strength pots.
+    Swiss,
+}
+
+// Match and table rules shared by export, batch and season.
+// A plain comment: clap would show a doc comment as the help of commands that flatten it.
+#[derive(Debug, Clone, Args)]
+struct SimArgs {
+    /// Play league pairings home and away (double round robin).
+    #[arg(long)]
+    double: bool,
+    /// Play knockout and play-off ties home and away, decided on aggregate.
+    #[arg(long)]
+    two_legged: bool,
+    /// Break level aggregates on away goals.
+    #[arg(long, requires = "two_legged")]
+    away_goals: bool,
+    /// Tie-breakers for tables: a preset (premier-league, uefa, fifa) or a
+    /// comma-separated rule list such as h2h-points,gd,gf,lots.
+    #[arg(long, default_value = "premier-league")]
+    tie_break: String,
+    #[arg(long, default_value_t = DEFAULT_HOME_ADVANTAGE)]
+    home_advantage: f64,
+    #[arg(long, value_enum, default_value_t = EngineArg::Minute)]
+    engine: EngineArg,
+    /// Use current Elo ratings as team strength.
+    #[arg(long)]
+    elo: bool,
+}
+
+impl SimArgs {
+    fn config(&self, ratings: &RatingsConfig) -> io::Result<SimConfig> {
+        Ok(SimConfig {
+            home_advantage: check_home_advantage(self.home_advantage)?,
+            double_round_robin: self.double,
+            tie_format: tie_format(self.two_legged, self.away_goals),
+            tie_breakers: tie_breakers(&self.tie_break)?,
+            engine: self.engine.into(),
+            elo: elo_strength(ratings, self.elo)?,
+            ..SimConfig::default()
+        })
+    }
+}
+
+// Draw, bracket and Swiss options on top of `SimArgs`, for export and batch.
+#[derive(Debug, Clone, Args)]
+struct CompetitionArgs {
+    #[command(flatten)]
+    sim: SimArgs,
+    /// Play the knockout final on neutral ground.
+    #[arg(long)]
+    neutral_final: bool,
+    /// Knockout and group draw: teams seeded in the order given, shuffled, or
+    /// drawn from seeding pots.
+    #[arg(long, value_enum, default_value_t = DrawArg::Seeded)]
+    draw: DrawArg,
+    /// Teams a pot draw keeps apart in groups and first-round ties.
+    #[arg(long, value_enum, default_value_t = SeparationArg::Country)]
+    separation: SeparationArg,
+    /// Send the best third-placed group teams through (tournament).
+    #[arg(long)]
+    best_thirds: bool,
+    /// Matches per team in a Swiss league phase (even; two per pot).
+    #[arg(long, default_value_t = DEFAULT_SWISS_MATCHES)]
+    swiss_matches: usize,
+    /// Swiss places that qualify directly.
+    #[arg(long)]
+    direct: Option<usize>,
+    /// Swiss places after the direct ones that go into the play-off.
+    #[arg(long)]
+    playoff: Option<usize>,
+}
+
+impl CompetitionArgs {
+    fn config(&self, ratings: &RatingsConfig) -> io::Result<SimConfig> {
+        Ok(SimConfig {
+            neutral_final: self.neutral_final,
+            draw: self.draw.into(),
+            separation: self.separation.into(),
+            best_thirds: self.best_thirds,
+            swiss: SwissFormat {
+                matches: self.swiss_matches,
+                direct: self.direct,
+                playoff: self.playoff,
+            },
+            ..self.sim.config(ratings)?
+        })
+    }
+}
+
+// Filters on the team pool; CPU auto-fill only draws from matching teams.
+// A plain comment for the same reason as `SimArgs` above.
+#[derive(Debug, Clone, Args)]
+struct PoolArgs {
+    /// Only clubs or only national teams.
+    #[arg(long, value_enum)]
+    kind: Option<KindArg>,
+    /// Only teams from one confederation (any letter case).
+    #[arg(long, value_enum, ignore_case = true)]
+    confed: Option<ConfedArg>,
+    /// Only teams from one country, e.g. "England".
+    #[arg(long)]
+    country: Option<String>,
+    /// Only clubs in one domestic league, e.g. "Serie A".
+    #[arg(long)]
+    league: Option<String>,
+}
+
+impl From<PoolArgs> for TeamFilter {
+    fn from(value: PoolArgs) -> Self {
+        Self {
+            kind: value.kind.map(Into::into),
+            confederation: value.confed.map(Into::into),
+            country: value.country,
+            league: value.league,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum KindArg {
+    Club,
+    National,
+}
+
+impl From<KindArg> for TeamKind {
+    fn from(value: KindArg) -> Self {
+        match value {
+            KindArg::Club => TeamKind::Club,
+            KindArg::National => TeamKind::National,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ConfedArg {
+    Afc,
+    Caf,
+    Concacaf,
+    Conmebol,
+    Ofc,
+    Uefa,
+}
+
+impl From<ConfedArg> for Confederation {
+    fn from(value: ConfedArg) -> Self {
+        match value {
+            ConfedArg::Afc => Confederation::Afc,
+            ConfedArg::Caf => Confederation::Caf,
+            ConfedArg::Concacaf => Confederation::Concacaf,
+            ConfedArg::Conmebol => Confederation::Conmebol,
+            ConfedArg::Ofc => Confederation::Ofc,
+            ConfedArg::Uefa => Confederation::Uefa,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum FormatArg {
+    Table,
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum EngineArg {
+    Minute,
+    Poisson,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum DrawArg {
+    Seeded,
+    Random,
+    Pots,
+}
+
+impl From<DrawArg> for DrawKind {
+    fn from(value: DrawArg) -> Self {
+        match value {
+            DrawArg::Seeded => DrawKind::Seeded,
+            DrawArg::Random => DrawKind::Random,
+            DrawArg::Pots => DrawKind::Pots,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SeparationArg {
+    None,
+    Country,
+    Confederation,
+}
+
+impl From<SeparationArg> for Separation {
+    fn from(value: SeparationArg) -> Self {
+        match value {
+            SeparationArg::None => Separation::None,
+            SeparationArg::Country => Separation::Country,
+            SeparationArg::Confederation => Separation::Confederation,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum KFactorArg {
+    Fixed,
+    Margin,
+    Provisional,
+}
+
+impl From<KFactorArg> for KFactorKind {
+    fn from(value: KFactorArg) -> Self {
+        match value {
+            KFactorArg::Fixed => KFactorKind::Fixed,
+            KFactorArg::Margin => KFactorKind::GoalMargin,
+            KFactorArg::Provisional => KFactorKind::Provisional,
+        }
+    }
+}
+
+impl From<EngineArg> for EngineKind {
+    fn from(value: EngineArg) -> Self {
+        match value {
+            EngineArg::Minute => EngineKind::Minute,
+            EngineArg::Poisson => EngineKind::Poisson,
+        }
+    }
 }
 
 impl From<ModeArg> for SimulationType {
@@ -73,7 +434,11 @@ impl From<ModeArg> for SimulationType {
         match value {
             ModeArg::Single => SimulationType::Single,
             ModeArg::League4 => SimulationType::League4,
+            ModeArg::League => SimulationType::League,
             ModeArg::Knockout4 => SimulationType::Knockout4,
+            ModeArg::Knockout => SimulationType::Knockout,
+            ModeArg::Tournament => SimulationType::Tournament,
+            ModeArg::Swiss => SimulationType::Swiss,
         }
     }
 }
@@ -81,6 +446,11 @@ impl From<ModeArg> for SimulationType {
 fn main() -> io::Result<()> {
     let cli = Cli::parse();
     let base_seed = cli.seed.unwrap_or_else(|| Rng::from_time().next_u64());
+    let ratings = RatingsConfig {
+        path: cli.ratings_file.clone(),
+        k_factor: cli.k_factor.into(),
+        update: !cli.no_ratings,
+    };
 
     if cli.listen_open && !cli.web {
         return Err(io::Error::new(
@@ -96,7 +466,7 @@ fn main() -> io::Result<()> {
                 "--web cannot be combined with subcommands",
             ));
         }
-        return run_web_server(base_seed, cli.speed, cli.listen_open, cli.demo);
+        return run_web_server(base_seed, cli.speed, cli.listen_open, cli.demo, ratings);
     }
 
     if cli.demo {
@@ -108,34 +478,294 @@ fn main() -> io::Result<()> {
 
     match cli.command {
         None => {
-            let app = App::new(base_seed, cli.speed);
+            let app = App::new(base_seed, cli.speed, ratings);
             run_tui(app)
         }
-        Some(Commands::Quick { home, away }) => quick_mode(home, away, base_seed),
-        Some(Commands::List) => {
-            for team in TEAMS {
-                println!("{}", display_name(team));
+        Some(Commands::Quick {
+            home,
+            away,
+            neutral,
+            home_advantage,
+            engine,
+            elo,
+            pool,
+        }) => {
+            let config = SimConfig {
+                home_advantage: check_home_advantage(home_advantage)?,
+                neutral_venue: neutral,
+                engine: engine.into(),
+                elo: elo_strength(&ratings, elo)?,
+                ..SimConfig::default()
+            };
+            quick_mode(home, away, &pool.into(), &config, &ratings, base_seed)
+        }
+        Some(Commands::List { pool }) => {
+            let filter = TeamFilter::from(pool);
+            let teams: Vec<_> = TEAMS_DATA.iter().filter(|t| filter.matches(t)).collect();
+            if teams.is_empty() {
+                println!("No {} match.", filter.label());
+            }
+            for team in teams {
+                println!(
+                    "{:<28} {:<13} {:<22} {:<9} {}",
+                    display_name(team.name),
+                    team.kind.label(),
+                    team.country,
+                    team.confederation.as_str(),
+                    team.league.unwrap_or("-")
+                );
             }
             Ok(())
         }
-        Some(Commands::Export { mode, out, teams }) => export_mode(mode, out, teams, base_seed),
+        Some(Commands::Export {
+            mode,
+            out,
+            teams,
+            competition,
+        }) => {
+            let config = competition.config(&ratings)?;
+            export_mode(mode, out, teams, &config, &ratings, base_seed)
+        }
+        Some(Commands::Batch {
+            mode,
+            teams,
+            runs,
+            threads,
+            format,
+            neutral,
+            competition,
+        }) => {
+            let config = SimConfig {
+                neutral_venue: neutral,
+                ..competition.config(&ratings)?
+            };
+            let threads = threads.unwrap_or_else(default_threads);
+            batch_mode(mode, teams, runs, threads, format, &config, base_seed)
+        }
+        Some(Commands::Season {
+            new,
+            teams,
+            divisions,
+            size,
+            promoted,
+            playoff,
+            seasons,
+            out,
+            file,
+            sim,
+            pool,
+        }) => {
+            let pool = TeamFilter::from(pool);
+            if !new && pool != TeamFilter::default() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "team filters only apply to a new pyramid (`season --new`)",
+                ));
+            }
+            let config = sim.config(&ratings)?;
+            let pyramid = if new {
+                let rules = MovementRules {
+                    promoted: promoted.unwrap_or(DEFAULT_PROMOTED),
+                    playoff: playoff.unwrap_or(DEFAULT_PLAYOFF),
+                };
+                new_pyramid(teams, &pool, divisions, size, rules, &config)?
+            } else {
+                match Pyramid::load(&file) {
+                    Ok(pyramid) => pyramid,
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::NotFound,
+                            format!(
+                                "no pyramid at {}; start one with `season --new`",
+                                file.display()
+                            ),
+                        ));
+                    }
+                    Err(e) => return Err(e),
+                }
+            };
+            let options = SeasonOptions {
+                promoted,
+                playoff,
+                seasons,
+                out,
+                file,
+            };
+            season_mode(pyramid, options, &config, &ratings, base_seed)
+        }
+        Some(Commands::Ratings { reset, all }) => ratings_mode(&ratings, reset, all),
+    }
+}
+
+fn tie_format(two_legged: bool, away_goals: bool) -> TieFormat {
+    if two_legged {
+        TieFormat::TwoLegs { away_goals }
+    } else {
+        TieFormat::SingleMatch
     }
 }
 
-fn quick_mode(home: Option<String>, away: Option<String>, base_seed: u64) -> io::Result<()> {
-    let teams =
-        resolve_quick_single_teams(home.as_deref(), away.as_deref(), derive_seed(base_seed, 1))
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+fn tie_breakers(value: &str) -> io::Result<Vec<TieBreaker>> {
+    parse_tie_breakers(value).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+fn check_home_advantage(value: f64) -> io::Result<f64> {
+    SimConfig::check_home_advantage(value)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+/// Current Elo ratings as team strength, when `enabled`.
+fn elo_strength(
+    ratings: &RatingsConfig,
+    enabled: bool,
+) -> io::Result<Option<Arc<elo::EloStrength>>> {
+    if !enabled {
+        return Ok(None);
+    }
+    Ok(Some(Arc::new(EloStore::load(&ratings.path)?.strength())))
+}
+
+fn ratings_mode(ratings: &RatingsConfig, reset: bool, all: bool) -> io::Result<()> {
+    if reset {
+        EloStore::reset(&ratings.path)?;
+        println!("Reset ratings ({})", ratings.path.display());
+        return Ok(());
+    }
+
+    let store = EloStore::load(&ratings.path)?;
+    let lines = store.table_lines(all);
+    println!(
+        "Elo ratings ({}, K: {})",
+        ratings.path.display(),
+        ratings.k_factor.as_str()
+    );
+    if lines.len() == 1 {
+        println!("No ratings yet. Finished simulations will add them.");
+        return Ok(());
+    }
+    for line in lines {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// Pyramid from the given teams, or from the strongest `divisions * size` teams.
+fn new_pyramid(
+    mut teams: Vec<String>,
+    filter: &TeamFilter,
+    divisions: usize,
+    size: usize,
+    rules: MovementRules,
+    config: &SimConfig,
+) -> io::Result<Pyramid> {
+    if teams.is_empty() {
+        let mut pool: Vec<String> = filter.teams().iter().map(|t| t.to_string()).collect();
+        pool.sort_by(|a, b| config.strength(b).total_cmp(&config.strength(a)));
+        let wanted = divisions * size;
+        if wanted > pool.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} divisions of {} need {} teams; only {} {} exist",
+                    divisions,
+                    size,
+                    wanted,
+                    pool.len(),
+                    filter.label()
+                ),
+            ));
+        }
+        pool.truncate(wanted);
+        teams = pool;
+    }
+    check_teams(&teams)?;
+    Pyramid::new(teams, divisions, rules)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+struct SeasonOptions {
+    promoted: Option<usize>,
+    playoff: Option<usize>,
+    seasons: u32,
+    out: Option<String>,
+    file: PathBuf,
+}
+
+fn season_mode(
+    mut pyramid: Pyramid,
+    options: SeasonOptions,
+    config: &SimConfig,
+    ratings: &RatingsConfig,
+    base_seed: u64,
+) -> io::Result<()> {
+    let rules = MovementRules {
+        promoted: options.promoted.unwrap_or(pyramid.rules.promoted),
+        playoff: options.playoff.unwrap_or(pyramid.rules.playoff),
+    };
+    rules
+        .check(&pyramid.divisions)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    pyramid.rules = rules;
+    if options.seasons == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--seasons must be at least 1",
+        ));
+    }
+
+    let mut reports = Vec::with_capacity(options.seasons as usize);
+    for _ in 0..options.seasons {
+        let mut rng = Rng::new(derive_seed(base_seed, pyramid.season as u64));
+        let report = pyramid.play_season(config, &mut rng);
+        ratings.record(&report.results)?;
+        pyramid.save(&options.file)?;
+        for line in report.lines() {
+            println!("{}", line);
+        }
+        println!();
+        reports.push(report);
+    }
+    println!("Saved pyramid ({})", options.file.display());
+
+    if let Some(out) = options.out {
+        let mut f = File::create(&out)?;
+        f.write_all(&season_reports_to_csv_bytes(&reports)?)?;
+        println!("Wrote {}", out);
+    }
+    Ok(())
+}
+
+fn quick_mode(
+    home: Option<String>,
+    away: Option<String>,
+    pool: &TeamFilter,
+    config: &SimConfig,
+    ratings: &RatingsConfig,
+    base_seed: u64,
+) -> io::Result<()> {
+    let teams = resolve_quick_single_teams(
+        home.as_deref(),
+        away.as_deref(),
+        pool,
+        derive_seed(base_seed, 1),
+    )
+    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
 
     let seed = derive_seed(base_seed, 1);
     let mut rng = Rng::new(seed);
-    let prepared = run_simulation(SimulationType::Single, &teams, &mut rng);
+    let prepared = run_simulation(SimulationType::Single, &teams, config, &mut rng);
+    ratings.record(&prepared.results)?;
 
     println!("seed={seed}");
     if let sim::SimOutcome::Single(m) = prepared.outcome {
         println!("{} {}-{} {}", m.home, m.home_goals, m.away_goals, m.away);
         println!("xG {:.2} - {:.2}", m.stats.home.xg, m.stats.away.xg);
         println!("Possession {}% - {}%", m.home_possession, m.away_possession);
+        println!(
+            "Scorers {} | {}",
+            m.scorers_text(&m.home),
+            m.scorers_text(&m.away)
+        );
     }
 
     println!("-- log --");
@@ -148,10 +778,63 @@ fn quick_mode(home: Option<String>, away: Option<String>, base_seed: u64) -> io:
     Ok(())
 }
 
-fn export_mode(mode: ModeArg, out: String, teams: Vec<String>, base_seed: u64) -> io::Result<()> {
+fn export_mode(
+    mode: ModeArg,
+    out: String,
+    teams: Vec<String>,
+    config: &SimConfig,
+    ratings: &RatingsConfig,
+    base_seed: u64,
+) -> io::Result<()> {
+    check_team_count(mode, &teams, config)?;
+    check_teams(&teams)?;
+
+    let mut rng = Rng::new(derive_seed(base_seed, 1));
+    let prepared = run_simulation(mode.into(), &teams, config, &mut rng);
+    ratings.record(&prepared.results)?;
+    let bytes = simulation_to_csv_bytes(&prepared)?;
+    let mut f = File::create(&out)?;
+    f.write_all(&bytes)?;
+    println!("Wrote {}", out);
+    Ok(())
+}
+
+fn check_team_count(mode: ModeArg, teams: &[String], config: &SimConfig) -> io::Result<()> {
     let required = match mode {
         ModeArg::Single => 2,
         ModeArg::League4 | ModeArg::Knockout4 => 4,
+        ModeArg::League | ModeArg::Knockout => {
+            let min = if matches!(mode, ModeArg::League) {
+                MIN_LEAGUE_TEAMS
+            } else {
+                MIN_KNOCKOUT_TEAMS
+            };
+            if teams.len() < min {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("mode {:?} requires at least {} --team values", mode, min),
+                ));
+            }
+            return Ok(());
+        }
+        ModeArg::Tournament => {
+            if teams.len() < MIN_TOURNAMENT_TEAMS || !teams.len().is_multiple_of(GROUP_SIZE) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "mode {:?} requires a multiple of {} --team values, at least {}",
+                        mode, GROUP_SIZE, MIN_TOURNAMENT_TEAMS
+                    ),
+                ));
+            }
+            return Ok(());
+        }
+        ModeArg::Swiss => {
+            return config
+                .swiss
+                .check(teams.len())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e));
+        }
     };
     if teams.len() != required {
         return Err(io::Error::new(
@@ -162,12 +845,55 @@ fn export_mode(mode: ModeArg, out: String, teams: Vec<String>, base_seed: u64) -
             ),
         ));
     }
+    Ok(())
+}
 
-    let mut rng = Rng::new(derive_seed(base_seed, 1));
-    let prepared = run_simulation(mode.into(), &teams, &mut rng);
-    let bytes = simulation_to_csv_bytes(&prepared)?;
-    let mut f = File::create(&out)?;
-    f.write_all(&bytes)?;
-    println!("Wrote {}", out);
+/// Every team must be known and listed once.
+fn check_teams(teams: &[String]) -> io::Result<()> {
+    for (idx, team) in teams.iter().enumerate() {
+        if team_by_name(team).is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Unknown team: {team}"),
+            ));
+        }
+        if teams[..idx].contains(team) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Team listed twice: {team}"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn batch_mode(
+    mode: ModeArg,
+    teams: Vec<String>,
+    runs: u32,
+    threads: usize,
+    format: FormatArg,
+    config: &SimConfig,
+    base_seed: u64,
+) -> io::Result<()> {
+    check_team_count(mode, &teams, config)?;
+    check_teams(&teams)?;
+    if runs == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--runs must be at least 1",
+        ));
+    }
+
+    let report = run_batch(mode.into(), &teams, config, runs, base_seed, threads);
+    match format {
+        FormatArg::Table => {
+            for line in batch_table_lines(&report) {
+                println!("{}", line);
+            }
+        }
+        FormatArg::Csv => io::stdout().write_all(&batch_to_csv_bytes(&report)?)?,
+        FormatArg::Json => println!("{}", batch_to_json(&report)),
+    }
     Ok(())
 }
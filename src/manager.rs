@@ -0,0 +1,61 @@
+use crate::data::{tactic_by_key, Tactic};
+
+/// What the bench sees when deciding whether to change approach.
+#[derive(Debug, Clone, Copy)]
+pub struct GameState {
+    pub minute: u8,
+    /// Own goals minus opponent goals.
+    pub goal_diff: i16,
+    /// Own players sent off.
+    pub reds: u16,
+    /// Opponent players sent off.
+    pub opp_reds: u16,
+}
+
+/// Trailing sides push from here; two-goal margins (and a man up) trigger changes earlier.
+const CHASE_FROM: u8 = 70;
+const PROTECT_FROM: u8 = 75;
+const EARLY_SWITCH_FROM: u8 = 60;
+
+/// Minutes at which the manager may switch even if nothing else happens.
+pub const DECISION_MINUTES: [u8; 3] = [EARLY_SWITCH_FROM, CHASE_FROM, PROTECT_FROM];
+
+/// Tactic the manager wants for the current game state, with the reason shown in the log.
+/// Falls back to the team's usual tactic when nothing calls for a change.
+fn target_tactic(base: Tactic, state: GameState) -> (Tactic, &'static str) {
+    let men = state.opp_reds as i32 - state.reds as i32;
+
+    if state.goal_diff < 0 {
+        let late = state.minute >= CHASE_FROM
+            || (state.goal_diff <= -2 && state.minute >= EARLY_SWITCH_FROM);
+        if late {
+            return (tactic_by_key("high_press"), "chasing the game");
+        }
+    } else if state.goal_diff > 0 {
+        let protect = state.minute >= PROTECT_FROM
+            || (state.goal_diff >= 2 && state.minute >= EARLY_SWITCH_FROM);
+        if protect || men < 0 {
+            return (tactic_by_key("low_block"), "protecting the lead");
+        }
+    } else if men < 0 {
+        return (tactic_by_key("low_block"), "a man down");
+    } else if men > 0 && state.minute >= EARLY_SWITCH_FROM {
+        return (tactic_by_key("high_press"), "a man up");
+    }
+
+    (base, "back to the game plan")
+}
+
+/// Returns the new tactic and reason if the manager decides to switch this minute.
+pub fn review_tactic(
+    base: Tactic,
+    current: Tactic,
+    state: GameState,
+) -> Option<(Tactic, &'static str)> {
+    let (target, reason) = target_tactic(base, state);
+    if target.key == current.key {
+        None
+    } else {
+        Some((target, reason))
+    }
+}
@@ -0,0 +1,495 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::bracket::DrawKind;
+use crate::data::display_name;
+use crate::sim::{
+    run_knockout, run_league, MatchResult, SimConfig, SimOutcome, StandingsRow, TieRecord,
+    MIN_LEAGUE_TEAMS,
+};
+use crate::tiebreak::tie_break_notes;
+use crate::utils::{csv_escape, Rng};
+
+pub const DEFAULT_PYRAMID_FILE: &str = "soccercloud-pyramid.csv";
+/// Automatic promotion places per division in a new pyramid.
+pub const DEFAULT_PROMOTED: usize = 2;
+/// Teams in each promotion play-off of a new pyramid.
+pub const DEFAULT_PLAYOFF: usize = 4;
+
+/// "Division 1" is the top flight.
+pub fn division_name(idx: usize) -> String {
+    format!("Division {}", idx + 1)
+}
+
+/// How many teams move between neighbouring divisions each season.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MovementRules {
+    /// Top places of a lower division promoted automatically.
+    pub promoted: usize,
+    /// Places after the automatic ones that play off for one more promotion
+    /// (0 for no play-off).
+    pub playoff: usize,
+}
+
+impl Default for MovementRules {
+    fn default() -> Self {
+        Self {
+            promoted: DEFAULT_PROMOTED,
+            playoff: DEFAULT_PLAYOFF,
+        }
+    }
+}
+
+impl MovementRules {
+    /// Teams going up from each lower division, which as many come down to replace.
+    pub fn relegated(self) -> usize {
+        self.promoted + usize::from(self.playoff > 0)
+    }
+
+    pub fn check(self, divisions: &[Vec<String>]) -> Result<(), String> {
+        if divisions.len() < 2 {
+            return Err("a pyramid needs at least 2 divisions".to_string());
+        }
+        if self.relegated() == 0 {
+            return Err("at least one promotion place is needed".to_string());
+        }
+        if self.playoff == 1 {
+            return Err("a play-off needs at least 2 teams".to_string());
+        }
+        for (idx, division) in divisions.iter().enumerate() {
+            let mut needed = 0;
+            if idx > 0 {
+                needed += self.promoted + self.playoff;
+            }
+            if idx + 1 < divisions.len() {
+                needed += self.relegated();
+            }
+            let needed = needed.max(MIN_LEAGUE_TEAMS - 1) + 1;
+            if division.len() < needed {
+                return Err(format!(
+                    "{} has {} teams; {} promotion, {} play-off and {} relegation places need at least {}",
+                    division_name(idx),
+                    division.len(),
+                    self.promoted,
+                    self.playoff,
+                    self.relegated(),
+                    needed
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Divisions, best first, and the rules that move teams between them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pyramid {
+    /// Number of the next season to play, from 1.
+    pub season: u32,
+    pub rules: MovementRules,
+    pub divisions: Vec<Vec<String>>,
+}
+
+impl Pyramid {
+    /// Split `teams` (top division first) into `divisions` of equal size.
+    pub fn new(teams: Vec<String>, divisions: usize, rules: MovementRules) -> Result<Self, String> {
+        if divisions < 2 || !teams.len().is_multiple_of(divisions) {
+            return Err(format!(
+                "{} teams do not split into {} equal divisions",
+                teams.len(),
+                divisions
+            ));
+        }
+        let size = teams.len() / divisions;
+        let pyramid = Self {
+            season: 1,
+            rules,
+            divisions: teams.chunks(size).map(|d| d.to_vec()).collect(),
+        };
+        rules.check(&pyramid.divisions)?;
+        Ok(pyramid)
+    }
+
+    /// Read a saved pyramid; a missing file is reported as `NotFound`.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let invalid = |idx: usize| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: bad pyramid line {}", path.display(), idx + 1),
+            )
+        };
+
+        let mut season = 1;
+        let mut rules = MovementRules::default();
+        let mut divisions: Vec<Vec<String>> = Vec::new();
+        let mut in_teams = false;
+        for (idx, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (key, value) = line.split_once(',').ok_or_else(|| invalid(idx))?;
+            if !in_teams {
+                match key {
+                    "Season" => season = value.parse().map_err(|_| invalid(idx))?,
+                    "Promoted" => rules.promoted = value.parse().map_err(|_| invalid(idx))?,
+                    "Playoff" => rules.playoff = value.parse().map_err(|_| invalid(idx))?,
+                    "Division" => in_teams = true,
+                    _ => return Err(invalid(idx)),
+                }
+                continue;
+            }
+            // Team names may contain commas, so only the first one splits.
+            let division: usize = key.parse().map_err(|_| invalid(idx))?;
+            if division == 0 || division > divisions.len() + 1 {
+                return Err(invalid(idx));
+            }
+            if division > divisions.len() {
+                divisions.push(Vec::new());
+            }
+            let team = value
+                .strip_prefix('"')
+                .and_then(|t| t.strip_suffix('"'))
+                .map(|t| t.replace("\"\"", "\""))
+                .unwrap_or_else(|| value.to_string());
+            divisions[division - 1].push(team);
+        }
+
+        rules.check(&divisions).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {e}", path.display()),
+            )
+        })?;
+        Ok(Self {
+            season,
+            rules,
+            divisions,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = format!(
+            "Season,{}\nPromoted,{}\nPlayoff,{}\nDivision,Team\n",
+            self.season, self.rules.promoted, self.rules.playoff
+        );
+        for (idx, division) in self.divisions.iter().enumerate() {
+            for team in division {
+                out.push_str(&format!("{},{}\n", idx + 1, csv_escape(team)));
+            }
+        }
+        fs::write(path, out)
+    }
+
+    /// Play every division as a league, then the promotion play-offs, and move
+    /// teams up and down ready for the next season.
+    pub fn play_season(&mut self, config: &SimConfig, rng: &mut Rng) -> SeasonReport {
+        let rules = self.rules;
+        let last = self.divisions.len() - 1;
+        let mut results = Vec::new();
+        let mut divisions = Vec::with_capacity(self.divisions.len());
+
+        for (idx, teams) in self.divisions.iter().enumerate() {
+            let prepared = run_league(teams, config, rng);
+            results.extend(prepared.results);
+            let SimOutcome::League { final_table, .. } = prepared.outcome else {
+                unreachable!("run_league returns a league outcome");
+            };
+            let len = final_table.len();
+            let status = (0..len)
+                .map(|pos| {
+                    if idx == 0 && pos == 0 {
+                        Status::Champions
+                    } else if idx > 0 && pos < rules.promoted {
+                        Status::Promoted
+                    } else if idx > 0 && pos < rules.promoted + rules.playoff {
+                        Status::Playoff
+                    } else if idx < last && pos >= len - rules.relegated() {
+                        Status::Relegated
+                    } else {
+                        Status::Stayed
+                    }
+                })
+                .collect();
+            divisions.push(DivisionResult {
+                name: division_name(idx),
+                table: final_table,
+                status,
+                playoff: Vec::new(),
+            });
+        }
+
+        // Play-off candidates are seeded by finishing position, with the final at a
+        // neutral venue.
+        let playoff_config = SimConfig {
+            draw: DrawKind::Seeded,
+            neutral_final: true,
+            ..config.clone()
+        };
+        for division in divisions.iter_mut().skip(1) {
+            let candidates: Vec<String> = division
+                .table
+                .iter()
+                .zip(&division.status)
+                .filter(|(_, s)| **s == Status::Playoff)
+                .map(|(row, _)| row.team.clone())
+                .collect();
+            if candidates.is_empty() {
+                continue;
+            }
+            let prepared = run_knockout(&candidates, &playoff_config, rng);
+            results.extend(prepared.results);
+            let SimOutcome::Knockout { champion, ties } = prepared.outcome else {
+                unreachable!("run_knockout returns a knockout outcome");
+            };
+            if let Some(pos) = division.table.iter().position(|row| row.team == champion) {
+                division.status[pos] = Status::PlayoffWinner;
+            }
+            division.playoff = ties;
+        }
+
+        let mut movements = Vec::new();
+        for (idx, division) in divisions.iter().enumerate() {
+            for (row, status) in division.table.iter().zip(&division.status) {
+                let to = match status {
+                    Status::Promoted | Status::PlayoffWinner => idx - 1,
+                    Status::Relegated => idx + 1,
+                    _ => continue,
+                };
+                movements.push(Movement {
+                    team: row.team.clone(),
+                    from: idx,
+                    to,
+                    reason: status.label(),
+                });
+            }
+        }
+
+        // Stayers keep their finishing order; newcomers join after them.
+        let mut next: Vec<Vec<String>> = divisions
+            .iter()
+            .map(|division| {
+                division
+                    .table
+                    .iter()
+                    .zip(&division.status)
+                    .filter(|(_, s)| !s.moves())
+                    .map(|(row, _)| row.team.clone())
+                    .collect()
+            })
+            .collect();
+        for movement in &movements {
+            next[movement.to].push(movement.team.clone());
+        }
+        self.divisions = next;
+
+        let report = SeasonReport {
+            season: self.season,
+            divisions,
+            movements,
+            results,
+        };
+        self.season += 1;
+        report
+    }
+}
+
+/// What a final position means for the next season.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Champions,
+    Promoted,
+    /// Entered the promotion play-off and lost.
+    Playoff,
+    PlayoffWinner,
+    Relegated,
+    Stayed,
+}
+
+impl Status {
+    pub fn label(self) -> &'static str {
+        match self {
+            Status::Champions => "Champions",
+            Status::Promoted => "Promoted",
+            Status::Playoff => "Play-off",
+            Status::PlayoffWinner => "Promoted via play-off",
+            Status::Relegated => "Relegated",
+            Status::Stayed => "",
+        }
+    }
+
+    fn moves(self) -> bool {
+        matches!(
+            self,
+            Status::Promoted | Status::PlayoffWinner | Status::Relegated
+        )
+    }
+}
+
+/// Final table of one division and how its play-off went.
+#[derive(Debug, Clone)]
+pub struct DivisionResult {
+    pub name: String,
+    pub table: Vec<StandingsRow>,
+    /// One entry per table row.
+    pub status: Vec<Status>,
+    pub playoff: Vec<TieRecord>,
+}
+
+/// A team changing division between seasons.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Movement {
+    pub team: String,
+    pub from: usize,
+    pub to: usize,
+    pub reason: &'static str,
+}
+
+#[derive(Debug, Clone)]
+pub struct SeasonReport {
+    pub season: u32,
+    pub divisions: Vec<DivisionResult>,
+    pub movements: Vec<Movement>,
+    /// Every match played, leagues first, then play-offs.
+    pub results: Vec<MatchResult>,
+}
+
+impl SeasonReport {
+    /// Final tables, play-offs and the movement report as plain text.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("=== Season {} ===", self.season)];
+        for division in &self.divisions {
+            lines.push(String::new());
+            lines.push(division.name.clone());
+            lines.push(format!(
+                "{:>3}  {:<30} {:>2} {:>2} {:>2} {:>2} {:>3} {:>3} {:>4} {:>3}  {}",
+                "#", "Team", "P", "W", "D", "L", "GF", "GA", "GD", "Pts", "Status"
+            ));
+            let rows = division.table.iter().zip(&division.status);
+            for (pos, (row, status)) in rows.enumerate() {
+                let line = format!(
+                    "{:>3}  {:<30} {:>2} {:>2} {:>2} {:>2} {:>3} {:>3} {:>4} {:>3}  {}",
+                    pos + 1,
+                    display_name(&row.team),
+                    row.p,
+                    row.w,
+                    row.d,
+                    row.l,
+                    row.gf,
+                    row.ga,
+                    row.gd,
+                    row.pts,
+                    status.label()
+                );
+                lines.push(line.trim_end().to_string());
+            }
+            for note in tie_break_notes(&division.table) {
+                lines.push(format!("  ({note})"));
+            }
+            if !division.playoff.is_empty() {
+                lines.push(format!("{} play-off", division.name));
+                for tie in &division.playoff {
+                    lines.push(format!("  {}", tie.line));
+                }
+            }
+        }
+
+        lines.push(String::new());
+        lines.push("Movements".to_string());
+        for movement in &self.movements {
+            lines.push(format!(
+                "  {} {}: {} -> {} ({})",
+                if movement.to < movement.from {
+                    "↑"
+                } else {
+                    "↓"
+                },
+                display_name(&movement.team),
+                division_name(movement.from),
+                division_name(movement.to),
+                movement.reason
+            ));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::TEAMS;
+    use crate::engine::EngineKind;
+
+    fn teams(n: usize) -> Vec<String> {
+        TEAMS[..n].iter().map(|t| t.to_string()).collect()
+    }
+
+    fn config() -> SimConfig {
+        SimConfig {
+            engine: EngineKind::Poisson,
+            record_frames: false,
+            ..SimConfig::default()
+        }
+    }
+
+    #[test]
+    fn save_then_load_round_trips() {
+        let mut pyramid = Pyramid::new(teams(16), 2, MovementRules::default()).unwrap();
+        pyramid.season = 4;
+        // Names with commas and quotes survive the CSV escaping.
+        pyramid.divisions[1][0] = "Club, \"The\" Reserves".to_string();
+        let path = std::env::temp_dir().join(format!("pyramid-{}.csv", std::process::id()));
+        pyramid.save(&path).unwrap();
+        let loaded = Pyramid::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), pyramid);
+    }
+
+    #[test]
+    fn load_rejects_a_pyramid_that_breaks_its_rules() {
+        let path = std::env::temp_dir().join(format!("pyramid-bad-{}.csv", std::process::id()));
+        let text = "Season,1\nPromoted,2\nPlayoff,4\nDivision,Team\n1,A\n2,B\n";
+        fs::write(&path, text).unwrap();
+        let err = Pyramid::load(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn movements_balance_between_divisions() {
+        for rules in [
+            MovementRules::default(),
+            MovementRules {
+                promoted: 3,
+                playoff: 0,
+            },
+        ] {
+            let mut pyramid = Pyramid::new(teams(30), 3, rules).unwrap();
+            let mut before: Vec<String> = pyramid.divisions.concat();
+            let mut rng = Rng::new(11);
+            let report = pyramid.play_season(&config(), &mut rng);
+
+            for upper in 0..2 {
+                let down = report
+                    .movements
+                    .iter()
+                    .filter(|m| m.from == upper && m.to == upper + 1)
+                    .count();
+                let up = report
+                    .movements
+                    .iter()
+                    .filter(|m| m.from == upper + 1 && m.to == upper)
+                    .count();
+                assert_eq!(down, rules.relegated());
+                assert_eq!(up, rules.relegated());
+            }
+            assert!(pyramid.divisions.iter().all(|d| d.len() == 10));
+            let mut after = pyramid.divisions.concat();
+            before.sort();
+            after.sort();
+            assert_eq!(before, after);
+            assert_eq!(pyramid.season, 2);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::data::TeamProfile;
+use crate::events::{SetPiece, ShotType};
+use crate::shootout::conversion_chance;
+use crate::shots::{attempt, on_target_rate, sample_xg};
+use crate::sim::{rating_mult, ShotAttempt, TeamStats};
+use crate::squad::{goalkeeper, penalty_order, pick_header};
+use crate::utils::Rng;
+
+/// Share of fouls committed inside the box (penalty) and in shooting range (dangerous free kick).
+const PENALTY_FOUL_SHARE: f64 = 0.035;
+const DANGEROUS_FREE_KICK_SHARE: f64 = 0.15;
+
+/// Where a foul was committed, as far as the attacking side is concerned.
+pub enum FoulSpot {
+    Penalty,
+    DangerousFreeKick,
+    Harmless,
+}
+
+pub fn foul_spot(rng: &mut Rng) -> FoulSpot {
+    let roll = rng.next_f64();
+    if roll < PENALTY_FOUL_SHARE {
+        FoulSpot::Penalty
+    } else if roll < PENALTY_FOUL_SHARE + DANGEROUS_FREE_KICK_SHARE {
+        FoulSpot::DangerousFreeKick
+    } else {
+        FoulSpot::Harmless
+    }
+}
+
+/// Aerial duels at set pieces: attack rating against the defending side's back line.
+fn aerial_edge(atk: TeamProfile, def: TeamProfile) -> f64 {
+    rating_mult(atk.attack, 0.8) / rating_mult(def.defence, 0.8)
+}
+
+/// Header from a delivery into the box.
+fn delivery_attempt(
+    atk_stats: &TeamStats,
+    atk: TeamProfile,
+    def: TeamProfile,
+    set_piece: SetPiece,
+    rng: &mut Rng,
+) -> ShotAttempt {
+    let shooter = pick_header(&atk_stats.players, rng);
+    let xg = sample_xg(ShotType::Header, rng) * aerial_edge(atk, def);
+    attempt(
+        shooter,
+        ShotType::Header,
+        xg,
+        atk.attack,
+        def.goalkeeper,
+        Some(set_piece),
+    )
+}
+
+/// Roughly a third of corners end in an attempt from the delivery.
+pub fn corner_attempt(
+    atk_stats: &TeamStats,
+    atk: TeamProfile,
+    def: TeamProfile,
+    rng: &mut Rng,
+) -> Option<ShotAttempt> {
+    if !rng.chance(0.35) {
+        return None;
+    }
+    Some(delivery_attempt(atk_stats, atk, def, SetPiece::Corner, rng))
+}
+
+/// Dangerous free kicks are either struck directly by the set-piece taker or delivered into the box.
+pub fn free_kick_attempt(
+    atk_stats: &TeamStats,
+    atk: TeamProfile,
+    def: TeamProfile,
+    rng: &mut Rng,
+) -> Option<ShotAttempt> {
+    if rng.chance(0.55) {
+        let shooter = penalty_order(&atk_stats.players)[0];
+        let xg = sample_xg(ShotType::FreeKick, rng) * rating_mult(atk.attack, 0.6);
+        return Some(attempt(
+            shooter,
+            ShotType::FreeKick,
+            xg,
+            atk.attack,
+            def.goalkeeper,
+            Some(SetPiece::FreeKick),
+        ));
+    }
+    if !rng.chance(0.5) {
+        return None;
+    }
+    Some(delivery_attempt(
+        atk_stats,
+        atk,
+        def,
+        SetPiece::FreeKick,
+        rng,
+    ))
+}
+
+/// In-play penalty taken by the side's first-choice taker; the same taker-vs-keeper
+/// model as shootouts decides it.
+pub fn penalty_attempt(
+    atk_stats: &TeamStats,
+    def_stats: &TeamStats,
+    atk: TeamProfile,
+) -> ShotAttempt {
+    let shooter = penalty_order(&atk_stats.players)[0];
+    let keeper = &def_stats.players[goalkeeper(&def_stats.players)];
+    let xg = conversion_chance(atk_stats.players[shooter].rating, keeper.rating);
+    let on_target = on_target_rate(ShotType::Penalty, atk.attack);
+    ShotAttempt {
+        shooter,
+        shot_type: ShotType::Penalty,
+        xg,
+        on_target,
+        conversion: (xg / on_target).min(1.0),
+        set_piece: Some(SetPiece::Penalty),
+    }
+}
@@ -0,0 +1,242 @@
+use crate::data::display_name;
+use crate::events::{MatchEvent, PenaltyKick};
+use crate::sim::{event_frame, MatchResult, SimFrame, TeamStats};
+use crate::squad::{goalkeeper, penalty_order};
+use crate::utils::Rng;
+
+const REGULATION_KICKS: u8 = 5;
+
+#[derive(Debug, Clone)]
+pub struct Shootout {
+    pub home_score: u8,
+    pub away_score: u8,
+    pub home_wins: bool,
+    pub kicks: Vec<PenaltyKick>,
+}
+
+/// Chance a kick goes in, shifted by the taker's and keeper's ratings.
+pub fn conversion_chance(taker_rating: u8, keeper_rating: u8) -> f64 {
+    let edge = taker_rating as f64 - keeper_rating as f64;
+    (0.76 + edge * 0.005).clamp(0.55, 0.92)
+}
+
+struct Side<'a> {
+    team: &'a str,
+    stats: &'a TeamStats,
+    order: Vec<usize>,
+    taken: u8,
+    scored: u8,
+}
+
+impl<'a> Side<'a> {
+    fn new(team: &'a str, stats: &'a TeamStats) -> Self {
+        Self {
+            team,
+            stats,
+            order: penalty_order(&stats.players),
+            taken: 0,
+            scored: 0,
+        }
+    }
+}
+
+/// Kick-by-kick shootout: five each, stopping early once one side cannot catch up,
+/// then sudden death until a round is won. Always produces a winner.
+pub fn penalty_shootout(result: &MatchResult, rng: &mut Rng) -> (Shootout, Vec<SimFrame>) {
+    let mut home = Side::new(&result.home, &result.stats.home);
+    let mut away = Side::new(&result.away, &result.stats.away);
+    let home_first = rng.chance(0.5);
+
+    let scoreboard = |h: u8, a: u8| {
+        format!(
+            "Penalties: {} {}-{} {}",
+            display_name(&result.home),
+            h,
+            a,
+            display_name(&result.away)
+        )
+    };
+
+    let first_team = if home_first {
+        &result.home
+    } else {
+        &result.away
+    };
+    let mut frames = vec![event_frame(
+        scoreboard(0, 0),
+        vec![MatchEvent::ShootoutStart {
+            first_team: first_team.clone(),
+        }],
+    )];
+    let mut kicks: Vec<PenaltyKick> = Vec::new();
+
+    let decided_in_regulation = |home: &Side, away: &Side| {
+        let home_left = REGULATION_KICKS - home.taken;
+        let away_left = REGULATION_KICKS - away.taken;
+        home.scored + home_left < away.scored || away.scored + away_left < home.scored
+    };
+
+    let mut round: u8 = 0;
+    loop {
+        round = round.saturating_add(1);
+        let sudden_death = round > REGULATION_KICKS;
+        let mut decided = false;
+
+        for kicking_home in [home_first, !home_first] {
+            let (kicker, keeper_side) = if kicking_home {
+                (&mut home, &away)
+            } else {
+                (&mut away, &home)
+            };
+
+            let taker_idx = kicker.order[kicker.taken as usize % kicker.order.len()];
+            let taker = &kicker.stats.players[taker_idx];
+            let keeper = &keeper_side.stats.players[goalkeeper(&keeper_side.stats.players)];
+
+            let scored = rng.chance(conversion_chance(taker.rating, keeper.rating));
+            let saved = !scored && rng.chance(0.7);
+            kicker.taken += 1;
+            if scored {
+                kicker.scored += 1;
+            }
+
+            let kick = PenaltyKick {
+                number: kicks.len() as u16 + 1,
+                sudden_death,
+                team: kicker.team.to_string(),
+                taker: taker.name.clone(),
+                keeper: keeper.name.clone(),
+                scored,
+                saved,
+                home_score: home.scored,
+                away_score: away.scored,
+            };
+            frames.push(event_frame(
+                scoreboard(home.scored, away.scored),
+                vec![MatchEvent::PenaltyKick(kick.clone())],
+            ));
+            kicks.push(kick);
+
+            if !sudden_death && decided_in_regulation(&home, &away) {
+                decided = true;
+                break;
+            }
+        }
+
+        if decided || (sudden_death && home.scored != away.scored) {
+            break;
+        }
+    }
+
+    let home_wins = home.scored > away.scored;
+    let winner = if home_wins {
+        &result.home
+    } else {
+        &result.away
+    };
+    frames.push(event_frame(
+        scoreboard(home.scored, away.scored),
+        vec![MatchEvent::ShootoutEnd {
+            winner: winner.clone(),
+            winner_score: home.scored.max(away.scored),
+            loser_score: home.scored.min(away.scored),
+        }],
+    ));
+
+    (
+        Shootout {
+            home_score: home.scored,
+            away_score: away.scored,
+            home_wins,
+            kicks,
+        },
+        frames,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::profile_for;
+    use crate::sim::MatchStats;
+    use crate::squad::squad_for;
+
+    fn lineup(team: &str) -> TeamStats {
+        TeamStats {
+            players: squad_for(team).starter_stats(),
+            ..TeamStats::default()
+        }
+    }
+
+    fn level_match() -> MatchResult {
+        MatchResult {
+            home: "Arsenal".to_string(),
+            away: "Inter".to_string(),
+            home_goals: 1,
+            away_goals: 1,
+            home_profile: profile_for("Arsenal"),
+            away_profile: profile_for("Inter"),
+            stats: MatchStats {
+                home: lineup("Arsenal"),
+                away: lineup("Inter"),
+            },
+            home_possession: 50,
+            away_possession: 50,
+            goals: Vec::new(),
+            tactic_changes: Vec::new(),
+            extra_time: true,
+            neutral_venue: false,
+        }
+    }
+
+    #[test]
+    fn shootouts_follow_the_kick_rules() {
+        let result = level_match();
+        let (mut early, mut sudden) = (0, 0);
+        for seed in 0..400 {
+            let (shootout, frames) = penalty_shootout(&result, &mut Rng::new(seed));
+            let (home, away) = (shootout.home_score, shootout.away_score);
+            assert_ne!(home, away, "seed {seed}");
+            assert_eq!(shootout.home_wins, home > away);
+            assert_eq!(frames.len(), shootout.kicks.len() + 2);
+
+            let regulation: Vec<_> = shootout.kicks.iter().filter(|k| !k.sudden_death).collect();
+            let extra = &shootout.kicks[regulation.len()..];
+            assert!(extra.iter().all(|k| k.sudden_death), "seed {seed}");
+            let (mut home_left, mut away_left) = (REGULATION_KICKS, REGULATION_KICKS);
+            for (idx, kick) in regulation.iter().enumerate() {
+                if kick.team == result.home {
+                    home_left -= 1;
+                } else {
+                    away_left -= 1;
+                }
+                let out_of_reach = kick.home_score + home_left < kick.away_score
+                    || kick.away_score + away_left < kick.home_score;
+                // Regulation stops on the first kick that puts the result out of reach.
+                let last = idx + 1 == regulation.len();
+                assert_eq!(out_of_reach, last && extra.is_empty(), "seed {seed}");
+            }
+
+            if extra.is_empty() {
+                if regulation.len() < 2 * REGULATION_KICKS as usize {
+                    early += 1;
+                }
+                continue;
+            }
+            sudden += 1;
+            assert_eq!(regulation.len(), 2 * REGULATION_KICKS as usize);
+            assert_eq!(extra.len() % 2, 0, "seed {seed}");
+            let rounds = extra.len() / 2;
+            for (idx, round) in extra.chunks(2).enumerate() {
+                assert_ne!(round[0].team, round[1].team);
+                // Sudden death goes on while rounds end level.
+                let level = round[1].home_score == round[1].away_score;
+                assert_eq!(level, idx + 1 < rounds, "seed {seed}");
+            }
+        }
+        assert!(
+            early > 0 && sudden > 0,
+            "early {early}, sudden death {sudden}"
+        );
+    }
+}
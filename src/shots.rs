@@ -0,0 +1,144 @@
+use crate::data::{Formation, Tactic, TeamProfile};
+use crate::events::{SetPiece, ShotType};
+use crate::sim::{rating_mult, ShotAttempt, TeamStats};
+use crate::squad::{pick_header, pick_shooter};
+use crate::utils::Rng;
+
+/// xG range and on-target rate for an average attacker against an average defence.
+struct ShotProfile {
+    xg_min: f64,
+    xg_max: f64,
+    on_target: f64,
+}
+
+fn profile(shot_type: ShotType) -> ShotProfile {
+    let (xg_min, xg_max, on_target) = match shot_type {
+        ShotType::InsideBox => (0.05, 0.20, 0.45),
+        ShotType::LongRange => (0.02, 0.06, 0.32),
+        ShotType::Header => (0.04, 0.14, 0.38),
+        ShotType::OneOnOne => (0.25, 0.45, 0.68),
+        ShotType::Rebound => (0.15, 0.35, 0.50),
+        ShotType::FreeKick => (0.04, 0.09, 0.33),
+        ShotType::Penalty => (0.76, 0.76, 0.88),
+    };
+    ShotProfile {
+        xg_min,
+        xg_max,
+        on_target,
+    }
+}
+
+pub fn sample_xg(shot_type: ShotType, rng: &mut Rng) -> f64 {
+    let p = profile(shot_type);
+    p.xg_min + rng.next_f64() * (p.xg_max - p.xg_min)
+}
+
+/// Better attacking sides hit the target more often.
+pub fn on_target_rate(shot_type: ShotType, attack: u8) -> f64 {
+    (profile(shot_type).on_target * rating_mult(attack, 0.4)).min(0.92)
+}
+
+/// Build an attempt whose goal probability matches its xG, adjusted for the keeper.
+pub fn attempt(
+    shooter: usize,
+    shot_type: ShotType,
+    xg: f64,
+    attack: u8,
+    keeper: u8,
+    set_piece: Option<SetPiece>,
+) -> ShotAttempt {
+    let on_target = on_target_rate(shot_type, attack);
+    ShotAttempt {
+        shooter,
+        shot_type,
+        xg,
+        on_target,
+        conversion: ((xg / on_target) / rating_mult(keeper, 0.8)).min(0.97),
+        set_piece,
+    }
+}
+
+/// Everything that shapes an open-play chance for the attacking side.
+pub struct OpenPlay {
+    pub fast_break: bool,
+    pub atk_tactic: Tactic,
+    pub def_tactic: Tactic,
+    pub atk: TeamProfile,
+    pub def: TeamProfile,
+    pub def_lines: Formation,
+    /// Home advantage for the attacking side (1.0 away or at a neutral venue).
+    pub venue_mult: f64,
+    /// Block multiplier for a defending side down to fewer than eleven.
+    pub def_short_block: f64,
+}
+
+/// Choose the type of an open-play shot. Breaks produce one-on-ones, deep blocks force
+/// more efforts from distance, wide play produces headers.
+fn open_play_type(ctx: &OpenPlay, rng: &mut Rng) -> ShotType {
+    let roll = rng.next_f64();
+    let long_share = 0.26 * ctx.def_tactic.block_mult / ctx.atk_tactic.attack_bias;
+    if ctx.fast_break {
+        if roll < 0.40 {
+            ShotType::OneOnOne
+        } else if roll < 0.50 {
+            ShotType::LongRange
+        } else {
+            ShotType::InsideBox
+        }
+    } else if roll < 0.22 {
+        ShotType::Header
+    } else if roll < 0.22 + long_share {
+        ShotType::LongRange
+    } else {
+        ShotType::InsideBox
+    }
+}
+
+pub fn open_play_attempt(ctx: &OpenPlay, atk_stats: &TeamStats, rng: &mut Rng) -> ShotAttempt {
+    let shot_type = open_play_type(ctx, rng);
+    let shooter = if shot_type == ShotType::Header {
+        pick_header(&atk_stats.players, rng)
+    } else {
+        pick_shooter(&atk_stats.players, rng)
+    };
+
+    let mut xg = sample_xg(shot_type, rng);
+    if shot_type == ShotType::OneOnOne {
+        xg *= ctx.def_lines.counter_exposure();
+    }
+    xg *= ctx.atk_tactic.goal_mult * rating_mult(ctx.atk.attack, 1.2) * ctx.venue_mult;
+    xg /= ctx.def_tactic.block_mult * ctx.def_short_block * rating_mult(ctx.def.defence, 1.2);
+
+    attempt(
+        shooter,
+        shot_type,
+        xg.min(0.9),
+        ctx.atk.attack,
+        ctx.def.goalkeeper,
+        None,
+    )
+}
+
+/// A parried shot sometimes drops to an attacker for a second go. Rebounds from a
+/// set piece keep its tag.
+pub fn rebound_attempt(
+    atk_stats: &TeamStats,
+    atk: TeamProfile,
+    def: TeamProfile,
+    set_piece: Option<SetPiece>,
+    rng: &mut Rng,
+) -> Option<ShotAttempt> {
+    if !rng.chance(0.15) {
+        return None;
+    }
+    let shooter = pick_shooter(&atk_stats.players, rng);
+    let xg = sample_xg(ShotType::Rebound, rng) / rating_mult(def.defence, 0.8);
+    Some(attempt(
+        shooter,
+        ShotType::Rebound,
+        xg,
+        atk.attack,
+        def.goalkeeper,
+        set_piece,
+    ))
+}
@@ -1,14 +1,40 @@
 use std::cmp::Ordering;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
 
+use crate::bracket::{round_name, Bracket, DrawKind};
 use crate::data::{display_name, profile_for, tactic_by_key, TeamProfile};
-use crate::utils::{pad2, Rng};
+use crate::draw::{draw_bracket_from_pots, draw_groups_from_pots, DrawLog, Separation};
+use crate::elo::EloStrength;
+use crate::engine::{EngineKind, MatchEngine};
+use crate::events::{
+    CardKind, MatchEvent, Minute, PenaltyKick, ScoreLine, SetPiece, ShotType, TacticChange,
+};
+use crate::fixtures::{round_robin, Matchday};
+use crate::groups::{best_third_count, draw_groups, group_name, knockout_seeds, Qualifier};
+use crate::manager::{review_tactic, GameState};
+use crate::setpiece::{corner_attempt, foul_spot, free_kick_attempt, penalty_attempt, FoulSpot};
+use crate::shootout::penalty_shootout;
+use crate::shots::{open_play_attempt, rebound_attempt, OpenPlay};
+use crate::squad::{goalkeeper, pick_fouler, squad_for, PlayerMatchStats};
+use crate::swiss::{swiss_schedule, SwissFormat, Zone};
+use crate::tiebreak::{tie_break_notes, Ranking, TieBreakPreset, TieBreaker};
+use crate::utils::{derive_seed, Rng};
+use crate::winprob::{win_probability, LiveState, WinProbability};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SimulationType {
     Single,
     League4,
+    /// Round robin for any number of teams.
+    League,
     Knockout4,
+    /// Single-elimination bracket for any number of teams.
+    Knockout,
+    /// Groups of four feeding a knockout bracket.
+    Tournament,
+    /// One table, each team playing a few opponents drawn from strength pots.
+    Swiss,
 }
 
 impl SimulationType {
@@ -16,11 +42,24 @@ impl SimulationType {
         match self {
             SimulationType::Single => "single",
             SimulationType::League4 => "league4",
+            SimulationType::League => "league",
             SimulationType::Knockout4 => "knockout4",
+            SimulationType::Knockout => "knockout",
+            SimulationType::Tournament => "tournament",
+            SimulationType::Swiss => "swiss",
         }
     }
 }
 
+/// Per-shot-type counters for one side.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShotTypeStats {
+    pub shots: u16,
+    pub on_target: u16,
+    pub goals: u16,
+    pub xg: f64,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct TeamStats {
     pub shots: u16,
@@ -29,9 +68,45 @@ pub struct TeamStats {
     pub corners: u16,
     pub fouls: u16,
     pub yellows: u16,
+    pub reds: u16,
+    /// Dismissals for a second yellow; also counted in `reds`, with both yellows in `yellows`.
+    pub second_yellows: u16,
     pub offsides: u16,
     pub saves: u16,
     pub attacks: u16,
+    pub set_piece_goals: u16,
+    pub set_piece_xg: f64,
+    /// Indexed by [`ShotType::index`].
+    pub shot_types: [ShotTypeStats; 7],
+    pub players: Vec<PlayerMatchStats>,
+}
+
+impl TeamStats {
+    pub fn with_lineup(team: &str) -> Self {
+        Self {
+            players: squad_for(team).starter_stats(),
+            ..Self::default()
+        }
+    }
+
+    fn shorthanded_mult(&self) -> (f64, f64) {
+        shorthanded_mult(self.reds)
+    }
+
+    fn player_name(&self, idx: usize) -> &str {
+        self.players
+            .get(idx)
+            .map(|p| p.name.as_str())
+            .unwrap_or("Unknown")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GoalRecord {
+    pub minute: Minute,
+    pub team: String,
+    pub scorer: String,
+    pub set_piece: Option<SetPiece>,
 }
 
 #[derive(Debug, Clone)]
@@ -40,6 +115,18 @@ pub struct MatchStats {
     pub away: TeamStats,
 }
 
+impl MatchStats {
+    /// Running (goals, cards, fouls) totals used to size stoppage time.
+    fn incidents(&self) -> (u16, u16, u16) {
+        let goals = self.home.players.iter().chain(&self.away.players);
+        (
+            goals.map(|p| p.goals).sum(),
+            self.home.yellows + self.home.reds + self.away.yellows + self.away.reds,
+            self.home.fouls + self.away.fouls,
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MatchResult {
     pub home: String,
@@ -51,6 +138,69 @@ pub struct MatchResult {
     pub stats: MatchStats,
     pub home_possession: u8,
     pub away_possession: u8,
+    pub goals: Vec<GoalRecord>,
+    pub tactic_changes: Vec<TacticChange>,
+    pub extra_time: bool,
+    pub neutral_venue: bool,
+}
+
+impl MatchResult {
+    /// Comma-separated scorer list for one side, e.g. "B. Smith 23', B. Smith 71'".
+    pub fn scorers_text(&self, team: &str) -> String {
+        let items: Vec<String> = self
+            .goals
+            .iter()
+            .filter(|g| g.team == team)
+            .map(|g| {
+                if g.set_piece == Some(SetPiece::Penalty) {
+                    format!("{} {}' (pen)", g.scorer, g.minute)
+                } else {
+                    format!("{} {}'", g.scorer, g.minute)
+                }
+            })
+            .collect();
+        if items.is_empty() {
+            "None".to_string()
+        } else {
+            items.join(", ")
+        }
+    }
+}
+
+/// Results from one venue (home or away) for a league team.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VenueRecord {
+    pub w: u8,
+    pub d: u8,
+    pub l: u8,
+    pub gf: u16,
+    pub ga: u16,
+    pub pts: u8,
+}
+
+impl VenueRecord {
+    fn record(&mut self, scored: u8, conceded: u8) {
+        self.gf += scored as u16;
+        self.ga += conceded as u16;
+        match scored.cmp(&conceded) {
+            Ordering::Greater => {
+                self.w += 1;
+                self.pts += 3;
+            }
+            Ordering::Equal => {
+                self.d += 1;
+                self.pts += 1;
+            }
+            Ordering::Less => self.l += 1,
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{}-{}-{} {}:{} {}pts",
+            self.w, self.d, self.l, self.gf, self.ga, self.pts
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -64,41 +214,139 @@ pub struct StandingsRow {
     pub ga: u16,
     pub gd: i16,
     pub pts: u8,
+    pub home: VenueRecord,
+    pub away: VenueRecord,
+    pub yellows: u16,
+    pub reds: u16,
+    pub second_yellows: u16,
+    /// Rule that placed this team above the next one on equal points.
+    pub tie_break: Option<TieBreaker>,
+}
+
+impl StandingsRow {
+    /// FIFA fair-play deduction: 1 per yellow, 3 for a second-yellow dismissal (its two
+    /// yellows not counted on top) and 4 per straight red.
+    pub fn fair_play_points(&self) -> i32 {
+        let second_yellows = self.second_yellows as i32;
+        let yellows = self.yellows as i32 - 2 * second_yellows;
+        let straight_reds = self.reds as i32 - second_yellows;
+        yellows + 3 * second_yellows + 4 * straight_reds
+    }
+
+    fn record(&mut self, scored: u8, conceded: u8, at_home: bool) {
+        self.p += 1;
+        self.gf += scored as u16;
+        self.ga += conceded as u16;
+        self.gd = self.gf as i16 - self.ga as i16;
+        match scored.cmp(&conceded) {
+            Ordering::Greater => {
+                self.w += 1;
+                self.pts += 3;
+            }
+            Ordering::Equal => {
+                self.d += 1;
+                self.pts += 1;
+            }
+            Ordering::Less => self.l += 1,
+        }
+        if at_home {
+            self.home.record(scored, conceded);
+        } else {
+            self.away.record(scored, conceded);
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct SimFrame {
     pub scoreboard: String,
     pub logs: Vec<String>,
+    pub events: Vec<MatchEvent>,
     pub stats_lines: Option<Vec<String>>,
     pub competition_lines: Option<Vec<String>>,
     pub history_append: Vec<String>,
+    /// Live home/draw/away estimate at this point of a match.
+    pub win_prob: Option<WinProbability>,
 }
 
 #[derive(Debug, Clone)]
 pub enum SimOutcome {
-    Single(MatchResult),
+    Single(Box<MatchResult>),
     League {
         champion: String,
         final_table: Vec<StandingsRow>,
+        fixtures: Vec<LeagueFixture>,
     },
     Knockout {
         champion: String,
+        ties: Vec<TieRecord>,
+    },
+    Tournament {
+        champion: String,
+        groups: Vec<GroupRecord>,
+        ties: Vec<TieRecord>,
     },
+    Swiss {
+        /// Top of the league-phase table.
+        leader: String,
+        table: Vec<StandingsRow>,
+        fixtures: Vec<LeagueFixture>,
+        format: SwissFormat,
+    },
+}
+
+/// Final table and results of one tournament group.
+#[derive(Debug, Clone)]
+pub struct GroupRecord {
+    pub name: String,
+    pub table: Vec<StandingsRow>,
+    pub fixtures: Vec<LeagueFixture>,
+}
+
+/// One played league match and the matchday it belonged to.
+#[derive(Debug, Clone)]
+pub struct LeagueFixture {
+    pub matchday: usize,
+    pub home: String,
+    pub away: String,
+    pub home_goals: u8,
+    pub away_goals: u8,
+}
+
+/// One played knockout tie, kept structured for exporters.
+#[derive(Debug, Clone)]
+pub struct TieRecord {
+    pub stage: String,
+    pub line: String,
+    /// Per-leg results of a two-legged tie; empty for single matches.
+    pub legs: Vec<String>,
+    pub kicks: Vec<PenaltyKick>,
 }
 
 #[derive(Debug, Clone)]
 pub struct PreparedSimulation {
     pub frames: Vec<SimFrame>,
+    /// Every match played, in order.
+    pub results: Vec<MatchResult>,
     pub outcome: SimOutcome,
-    pub history_lines: Vec<String>,
 }
 
 fn chance(rng: &mut Rng, p: f64) -> bool {
     rng.chance(p)
 }
 
-fn possession_pct(result: &MatchResult) -> (u8, u8) {
+/// Attack-share and block multipliers for a side reduced below eleven players.
+pub fn shorthanded_mult(reds: u16) -> (f64, f64) {
+    let missing = reds.min(4) as f64;
+    (1.0 - 0.14 * missing, 1.0 - 0.10 * missing)
+}
+
+/// Scale a 1-99 rating into a multiplier around 1.0 (a 75-rated unit is neutral).
+pub fn rating_mult(rating: u8, scale: f64) -> f64 {
+    (1.0 + (rating as f64 - 75.0) / 100.0 * scale).max(0.2)
+}
+
+pub fn possession_pct(result: &MatchResult) -> (u8, u8) {
     let home_poss_base = (result.stats.home.attacks as f64)
         * if result.home_profile.tactic == "possession" {
             1.15
@@ -120,247 +368,731 @@ fn possession_pct(result: &MatchResult) -> (u8, u8) {
     (home_poss, away_poss)
 }
 
-fn empty_frame(scoreboard: String, logs: Vec<String>) -> SimFrame {
+pub fn empty_frame(scoreboard: String, logs: Vec<String>) -> SimFrame {
     SimFrame {
         scoreboard,
         logs,
+        events: Vec::new(),
         stats_lines: None,
         competition_lines: None,
         history_append: Vec::new(),
+        win_prob: None,
+    }
+}
+
+/// Frame whose log lines are rendered from its events.
+pub fn event_frame(scoreboard: String, events: Vec<MatchEvent>) -> SimFrame {
+    SimFrame {
+        logs: events.iter().filter_map(MatchEvent::describe).collect(),
+        events,
+        ..empty_frame(scoreboard, Vec::new())
+    }
+}
+
+/// One attempt at goal before it is resolved against the goalkeeper.
+pub struct ShotAttempt {
+    pub shooter: usize,
+    pub shot_type: ShotType,
+    pub xg: f64,
+    /// Probability the attempt hits the target.
+    pub on_target: f64,
+    /// Probability an on-target attempt beats this keeper.
+    pub conversion: f64,
+    pub set_piece: Option<SetPiece>,
+}
+
+enum ShotOutcome {
+    Goal(GoalRecord),
+    Saved,
+    Missed,
+}
+
+/// Who is shooting at whom, and when.
+struct Phase<'a> {
+    minute: Minute,
+    atk_team: &'a str,
+    def_team: &'a str,
+}
+
+/// Resolve an attempt: updates both sides' stats and pushes shot/goal/save events.
+fn resolve_shot(
+    phase: &Phase,
+    shot: ShotAttempt,
+    atk_stats: &mut TeamStats,
+    def_stats: &mut TeamStats,
+    events: &mut Vec<MatchEvent>,
+    rng: &mut Rng,
+) -> ShotOutcome {
+    let minute = phase.minute;
+    let xg = shot.xg;
+    let by_type = &mut atk_stats.shot_types[shot.shot_type.index()];
+    by_type.shots += 1;
+    by_type.xg += xg;
+    atk_stats.shots += 1;
+    atk_stats.players[shot.shooter].shots += 1;
+    atk_stats.xg += xg;
+    if shot.set_piece.is_some() {
+        atk_stats.set_piece_xg += xg;
+    }
+
+    let on_target = chance(rng, shot.on_target);
+    if on_target {
+        atk_stats.sot += 1;
+        atk_stats.shot_types[shot.shot_type.index()].on_target += 1;
+    }
+    let is_goal = on_target && chance(rng, shot.conversion);
+    let near_miss = !on_target && chance(rng, 0.25);
+    let shooter = atk_stats.player_name(shot.shooter).to_string();
+    events.push(MatchEvent::Shot {
+        minute,
+        team: phase.atk_team.to_string(),
+        player: shooter.clone(),
+        xg,
+        shot_type: shot.shot_type,
+        on_target,
+        near_miss,
+        set_piece: shot.set_piece,
+    });
+
+    if is_goal {
+        atk_stats.players[shot.shooter].goals += 1;
+        atk_stats.shot_types[shot.shot_type.index()].goals += 1;
+        if shot.set_piece.is_some() {
+            atk_stats.set_piece_goals += 1;
+        }
+        events.push(MatchEvent::Goal {
+            minute,
+            team: phase.atk_team.to_string(),
+            player: shooter.clone(),
+            xg,
+            shot_type: shot.shot_type,
+            set_piece: shot.set_piece,
+        });
+        return ShotOutcome::Goal(GoalRecord {
+            minute,
+            team: phase.atk_team.to_string(),
+            scorer: shooter,
+            set_piece: shot.set_piece,
+        });
+    }
+
+    if on_target {
+        def_stats.saves += 1;
+        let keeper = goalkeeper(&def_stats.players);
+        def_stats.players[keeper].saves += 1;
+        events.push(MatchEvent::Save {
+            minute,
+            team: phase.def_team.to_string(),
+            keeper: def_stats.player_name(keeper).to_string(),
+            xg,
+        });
+        return ShotOutcome::Saved;
+    }
+    ShotOutcome::Missed
+}
+
+/// Added minutes at the end of a period: a base allowance plus time lost to
+/// goal celebrations, cards and fouls during that period.
+fn stoppage_minutes(period_end: u8, goals: u16, cards: u16, fouls: u16, rng: &mut Rng) -> u8 {
+    let base = match period_end {
+        45 => 1.0,
+        90 => 3.0,
+        _ => 0.5,
+    };
+    let lost = goals as f64 * 0.5 + cards as f64 * 0.35 + fouls as f64 * 0.08;
+    (base + lost + rng.next_f64()).floor().clamp(1.0, 10.0) as u8
+}
+
+/// Salt for the live win-probability RNG stream.
+pub const LIVE_ODDS_SALT: u64 = 0x0DD5;
+
+/// Default boost for the home side's attacking share and chance quality.
+pub const DEFAULT_HOME_ADVANTAGE: f64 = 1.12;
+/// Smallest league worth a table.
+pub const MIN_LEAGUE_TEAMS: usize = 3;
+/// A two-team knockout is just the final.
+pub const MIN_KNOCKOUT_TEAMS: usize = 2;
+
+/// Score of an earlier first leg, seen from the home side of the second leg.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FirstLeg {
+    /// Goals the second-leg home side scored away in the first leg.
+    pub home_goals: u8,
+    pub away_goals: u8,
+    /// Away goals break a level aggregate.
+    pub away_goals_rule: bool,
+}
+
+/// How knockout ties are played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieFormat {
+    #[default]
+    SingleMatch,
+    /// Home and away legs decided on aggregate.
+    TwoLegs { away_goals: bool },
+}
+
+impl TieFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            TieFormat::SingleMatch => "single match",
+            TieFormat::TwoLegs { away_goals: false } => "two legs",
+            TieFormat::TwoLegs { away_goals: true } => "two legs, away goals",
+        }
     }
 }
 
-pub fn penalties(rng: &mut Rng) -> (u8, u8, bool) {
-    let mut h = 0u8;
-    let mut a = 0u8;
-    for _ in 0..5 {
-        if chance(rng, 0.76) {
-            h += 1;
+#[derive(Debug, Clone, Copy)]
+pub struct MatchOptions {
+    /// Play 30 minutes of extra time when the tie is level after 90 minutes.
+    pub extra_time: bool,
+    /// Set for the second leg of a two-legged tie.
+    pub first_leg: Option<FirstLeg>,
+    /// Multiplier applied to the home side's attack share and xG (1.0 = none).
+    pub home_advantage: f64,
+    /// Neutral ground: the home advantage is ignored.
+    pub neutral_venue: bool,
+    /// Build per-minute playback frames. Batch runs only need the result.
+    pub record_frames: bool,
+    /// Estimate live win probability on every frame. It plays hundreds of forward
+    /// simulations per minute, so only a single match being watched turns it on.
+    pub live_odds: bool,
+    /// Rating points added to every unit of each side (from Elo; 0 otherwise).
+    pub home_rating_shift: f64,
+    pub away_rating_shift: f64,
+}
+
+impl MatchOptions {
+    pub fn home_factor(self) -> f64 {
+        if self.neutral_venue {
+            1.0
+        } else {
+            self.home_advantage
         }
-        if chance(rng, 0.76) {
-            a += 1;
+    }
+
+    /// Who leads the tie after `home_goals`-`away_goals` in this match, counting any
+    /// first leg and the away-goals rule. `Greater` means the home side is ahead.
+    pub fn tie_standing(self, home_goals: u8, away_goals: u8) -> Ordering {
+        let Some(leg) = self.first_leg else {
+            return home_goals.cmp(&away_goals);
+        };
+        let aggregate = (leg.home_goals + home_goals).cmp(&(leg.away_goals + away_goals));
+        if aggregate == Ordering::Equal && leg.away_goals_rule {
+            leg.home_goals.cmp(&away_goals)
+        } else {
+            aggregate
         }
     }
-    let mut rounds = 0;
-    while h == a && rounds < 20 {
-        if chance(rng, 0.76) {
-            h += 1;
+
+    /// Whether this match goes to extra time after 90 minutes at this score.
+    pub fn needs_extra_time(self, home_goals: u8, away_goals: u8) -> bool {
+        self.extra_time && self.tie_standing(home_goals, away_goals) == Ordering::Equal
+    }
+
+    /// Aggregate appended to second-leg scoreboards.
+    pub fn aggregate_suffix(self, home_goals: u8, away_goals: u8) -> String {
+        match self.first_leg {
+            Some(leg) => format!(
+                " | agg {}-{}",
+                leg.home_goals + home_goals,
+                leg.away_goals + away_goals
+            ),
+            None => String::new(),
         }
-        if chance(rng, 0.76) {
-            a += 1;
+    }
+}
+
+/// Settings that apply to every match of a simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimConfig {
+    pub home_advantage: f64,
+    /// Play single matches on neutral ground.
+    pub neutral_venue: bool,
+    /// Play knockout finals on neutral ground.
+    pub neutral_final: bool,
+    /// Play every league pairing home and away.
+    pub double_round_robin: bool,
+    /// How knockout brackets and tournament groups are drawn.
+    pub draw: DrawKind,
+    /// Teams a pot draw keeps apart.
+    pub separation: Separation,
+    /// Single-match or two-legged knockout ties.
+    pub tie_format: TieFormat,
+    /// Let the best third-placed group teams into the tournament knockout stage.
+    pub best_thirds: bool,
+    /// Matches per team and qualification places of a Swiss league phase.
+    pub swiss: SwissFormat,
+    /// Rules that order teams level on points, in the order applied.
+    pub tie_breakers: Vec<TieBreaker>,
+    pub engine: EngineKind,
+    /// Build playback frames; off for headless batch runs.
+    pub record_frames: bool,
+    /// Use this Elo snapshot for team strength instead of the bare profiles.
+    pub elo: Option<Arc<EloStrength>>,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            home_advantage: DEFAULT_HOME_ADVANTAGE,
+            neutral_venue: false,
+            neutral_final: false,
+            double_round_robin: false,
+            draw: DrawKind::default(),
+            separation: Separation::default(),
+            tie_format: TieFormat::default(),
+            best_thirds: false,
+            swiss: SwissFormat::default(),
+            tie_breakers: TieBreakPreset::default().rules().to_vec(),
+            engine: EngineKind::default(),
+            record_frames: true,
+            elo: None,
         }
-        rounds += 1;
     }
-    (h, a, h > a)
 }
 
-pub fn simulate_match(home: &str, away: &str, rng: &mut Rng) -> (MatchResult, Vec<SimFrame>) {
-    let home_profile = profile_for(home);
-    let away_profile = profile_for(away);
+impl SimConfig {
+    /// Validate a user-supplied home-advantage multiplier.
+    pub fn check_home_advantage(value: f64) -> Result<f64, String> {
+        if (0.5..=2.0).contains(&value) {
+            Ok(value)
+        } else {
+            Err(format!(
+                "home advantage must be between 0.5 and 2.0 (got {value})"
+            ))
+        }
+    }
+
+    /// Profile strength, moved by Elo when playing with ratings.
+    pub fn strength(&self, team: &str) -> f64 {
+        profile_for(team).overall() + self.elo.as_ref().map(|e| e.shift(team)).unwrap_or(0.0)
+    }
 
-    let home_tactic = tactic_by_key(home_profile.tactic);
-    let away_tactic = tactic_by_key(away_profile.tactic);
+    pub fn match_options(&self, home: &str, away: &str, neutral_venue: bool) -> MatchOptions {
+        let shift = |team: &str| self.elo.as_ref().map(|e| e.shift(team)).unwrap_or(0.0);
+        MatchOptions {
+            extra_time: false,
+            first_leg: None,
+            home_advantage: self.home_advantage,
+            neutral_venue,
+            record_frames: self.record_frames,
+            live_odds: false,
+            home_rating_shift: shift(home),
+            away_rating_shift: shift(away),
+        }
+    }
+}
 
-    let mut minute: u8 = 0;
+pub fn simulate_match_with(
+    home: &str,
+    away: &str,
+    options: MatchOptions,
+    rng: &mut Rng,
+) -> (MatchResult, Vec<SimFrame>) {
+    let home_profile = profile_for(home).shifted(options.home_rating_shift);
+    let away_profile = profile_for(away).shifted(options.away_rating_shift);
+
+    let home_base_tactic = tactic_by_key(home_profile.tactic);
+    let away_base_tactic = tactic_by_key(away_profile.tactic);
+    let mut home_tactic = home_base_tactic;
+    let mut away_tactic = away_base_tactic;
+    let home_lines = home_profile.lines();
+    let away_lines = away_profile.lines();
+    let home_factor = options.home_factor();
+
+    let mut minute = Minute::default();
     let mut home_goals: u8 = 0;
     let mut away_goals: u8 = 0;
 
     let mut stats = MatchStats {
-        home: TeamStats::default(),
-        away: TeamStats::default(),
+        home: TeamStats::with_lineup(home),
+        away: TeamStats::with_lineup(away),
     };
+    let mut goals: Vec<GoalRecord> = Vec::new();
+    let mut tactic_changes: Vec<TacticChange> = Vec::new();
 
-    let kickoff = format!(
-        "Kickoff! {} ({}, {}) vs {} ({}, {})",
-        display_name(home),
-        home_profile.formation,
-        home_tactic.label,
-        display_name(away),
-        away_profile.formation,
-        away_tactic.label
-    );
+    let kickoff = MatchEvent::Kickoff {
+        home: home.to_string(),
+        home_formation: home_profile.formation.to_string(),
+        home_tactic: home_tactic.label.to_string(),
+        away: away.to_string(),
+        away_formation: away_profile.formation.to_string(),
+        away_tactic: away_tactic.label.to_string(),
+        neutral_venue: options.neutral_venue,
+    };
 
-    let mut frames = vec![empty_frame(
-        format!(
-            "{} ({}) {} - {} {} ({}) | {}'",
-            display_name(home),
-            home_profile.formation,
+    // Live odds get their own seed so recording frames never changes the result.
+    let odds_seed = derive_seed(rng.clone().next_u64(), LIVE_ODDS_SALT);
+    let mut frames = Vec::new();
+    if options.record_frames {
+        let mut frame = event_frame(
+            format!(
+                "{} ({}) {} - {} {} ({}) | {}'{}",
+                display_name(home),
+                home_profile.formation,
+                home_goals,
+                away_goals,
+                display_name(away),
+                away_profile.formation,
+                minute.padded(),
+                options.aggregate_suffix(home_goals, away_goals)
+            ),
+            vec![kickoff],
+        );
+        let state = LiveState {
+            minute,
             home_goals,
             away_goals,
-            display_name(away),
-            away_profile.formation,
-            pad2(minute)
-        ),
-        vec![kickoff],
-    )];
+            home_tactic,
+            away_tactic,
+            home_reds: 0,
+            away_reds: 0,
+        };
+        if options.live_odds {
+            frame.win_prob = Some(win_probability(home, away, &state, options, odds_seed));
+        }
+        frames.push(frame);
+    }
 
-    while minute < 90 {
-        minute += 1;
-        let pressure_boost = if minute < 15 || minute > 75 { 1.2 } else { 1.0 };
-        let mut logs: Vec<String> = Vec::new();
+    let mut period_end: u8 = 45;
+    let mut stoppage: u8 = 0;
+    let mut period_start_incidents = (0, 0, 0);
+    let mut played_extra_time = false;
+    loop {
+        if minute.base < period_end {
+            minute.base += 1;
+        } else {
+            minute.added += 1;
+        }
+        let clock = minute.base;
+        let extra_time = clock > 90;
+        let pressure_boost = if extra_time {
+            1.0
+        } else if !(15..=75).contains(&clock) {
+            1.2
+        } else {
+            1.0
+        };
+        // Tired legs: fewer attacks per minute once extra time starts.
+        let tempo = if extra_time { 0.85 } else { 1.0 };
+        let mut events: Vec<MatchEvent> = Vec::new();
+
+        if minute.added == 0 && clock == 91 {
+            events.push(MatchEvent::ExtraTimeStart { half: 1 });
+        } else if minute.added == 0 && clock == 106 {
+            events.push(MatchEvent::ExtraTimeStart { half: 2 });
+        }
 
-        let home_bias = home_tactic.attack_bias;
-        let away_bias = away_tactic.attack_bias;
+        let (home_short_atk, home_short_block) = stats.home.shorthanded_mult();
+        let (away_short_atk, away_short_block) = stats.away.shorthanded_mult();
+        let home_bias = home_tactic.attack_bias
+            * home_short_atk
+            * rating_mult(home_profile.midfield, 1.5)
+            * home_lines.midfield_control()
+            * home_factor;
+        let away_bias = away_tactic.attack_bias
+            * away_short_atk
+            * rating_mult(away_profile.midfield, 1.5)
+            * away_lines.midfield_control();
         let home_attacks = rng.next_f64() * (home_bias + away_bias) < home_bias;
 
-        let (atk_team, def_team, atk_prof, def_prof, atk_stats, def_stats) = if home_attacks {
-            (
-                home,
-                away,
-                home_profile,
-                away_profile,
-                &mut stats.home,
-                &mut stats.away,
-            )
+        let (atk_team, def_team, atk_prof, def_prof, atk_stats, def_stats, def_short_block) =
+            if home_attacks {
+                (
+                    home,
+                    away,
+                    home_profile,
+                    away_profile,
+                    &mut stats.home,
+                    &mut stats.away,
+                    away_short_block,
+                )
+            } else {
+                (
+                    away,
+                    home,
+                    away_profile,
+                    home_profile,
+                    &mut stats.away,
+                    &mut stats.home,
+                    home_short_block,
+                )
+            };
+        let (atk_tactic, def_tactic) = if home_attacks {
+            (home_tactic, away_tactic)
         } else {
-            (
-                away,
-                home,
-                away_profile,
-                home_profile,
-                &mut stats.away,
-                &mut stats.home,
-            )
+            (away_tactic, home_tactic)
         };
 
-        let atk_tactic = tactic_by_key(atk_prof.tactic);
-        let def_tactic = tactic_by_key(def_prof.tactic);
+        let atk_lines = atk_prof.lines();
+        let def_lines = def_prof.lines();
+        let phase = Phase {
+            minute,
+            atk_team,
+            def_team,
+        };
+        let mut attempts: VecDeque<ShotAttempt> = VecDeque::new();
 
-        if chance(rng, 0.24 * pressure_boost) {
+        if chance(rng, 0.24 * pressure_boost * tempo) {
             atk_stats.attacks += 1;
             let fast_break = chance(rng, atk_tactic.fast_break);
-            if chance(rng, (if fast_break { 0.75 } else { 0.55 }) * pressure_boost) {
-                atk_stats.shots += 1;
-                let mut xg = if fast_break {
-                    0.20 + rng.next_f64() * 0.25
-                } else {
-                    0.05 + rng.next_f64() * 0.22
+            let shot_rate = if fast_break { 0.75 } else { 0.55 };
+            if chance(rng, shot_rate * pressure_boost * atk_lines.shot_threat()) {
+                let open_play = OpenPlay {
+                    fast_break,
+                    atk_tactic,
+                    def_tactic,
+                    atk: atk_prof,
+                    def: def_prof,
+                    def_lines,
+                    venue_mult: if home_attacks { home_factor } else { 1.0 },
+                    def_short_block,
                 };
-                xg *= atk_tactic.goal_mult;
-                xg /= def_tactic.block_mult;
-
-                let on_target = chance(rng, 0.52);
-                if on_target {
-                    atk_stats.sot += 1;
-                }
-
-                let is_goal = on_target && chance(rng, xg);
-                if is_goal {
-                    if home_attacks {
-                        home_goals += 1;
-                    } else {
-                        away_goals += 1;
-                    }
-                    let finish = if fast_break {
-                        "cut-back finish"
-                    } else {
-                        "drilled low"
-                    };
-                    logs.push(format!(
-                        "{}' GOOOOAL - {} ({}, xG {:.2})",
-                        pad2(minute),
-                        display_name(atk_team),
-                        finish,
-                        xg
-                    ));
-                } else if on_target {
-                    def_stats.saves += 1;
-                    logs.push(format!(
-                        "{}' Big save by {}'s keeper!",
-                        pad2(minute),
-                        display_name(def_team)
-                    ));
-                } else if chance(rng, 0.25) {
-                    logs.push(format!(
-                        "{}' {} fire it just wide.",
-                        pad2(minute),
-                        display_name(atk_team)
-                    ));
-                }
-                atk_stats.xg += xg;
+                attempts.push_back(open_play_attempt(&open_play, atk_stats, rng));
             }
 
             if chance(rng, 0.05 * atk_tactic.attack_bias) {
                 atk_stats.corners += 1;
-                logs.push(format!(
-                    "{}' Corner to {}.",
-                    pad2(minute),
-                    display_name(atk_team)
-                ));
+                events.push(MatchEvent::Corner {
+                    minute,
+                    team: atk_team.to_string(),
+                });
+                attempts.extend(corner_attempt(atk_stats, atk_prof, def_prof, rng));
             }
 
             if chance(rng, 0.035 + 0.02 * atk_tactic.fast_break) {
                 atk_stats.offsides += 1;
-                logs.push(format!(
-                    "{}' Flag up - {} caught offside.",
-                    pad2(minute),
-                    display_name(atk_team)
-                ));
+                events.push(MatchEvent::Offside {
+                    minute,
+                    team: atk_team.to_string(),
+                });
             }
         }
 
         if chance(rng, 0.07 * atk_tactic.press_mult * atk_tactic.foul_mult) {
             def_stats.fouls += 1;
-            if chance(rng, 0.22 * atk_tactic.press_mult) {
+            let fouler = pick_fouler(&def_stats.players, rng);
+            def_stats.players[fouler].fouls += 1;
+            events.push(MatchEvent::Foul {
+                minute,
+                team: def_team.to_string(),
+                player: def_stats.player_name(fouler).to_string(),
+            });
+            match foul_spot(rng) {
+                FoulSpot::Penalty => {
+                    events.push(MatchEvent::PenaltyAwarded {
+                        minute,
+                        team: atk_team.to_string(),
+                        player: def_stats.player_name(fouler).to_string(),
+                    });
+                    attempts.push_back(penalty_attempt(atk_stats, def_stats, atk_prof));
+                }
+                FoulSpot::DangerousFreeKick => {
+                    events.push(MatchEvent::FreeKick {
+                        minute,
+                        team: atk_team.to_string(),
+                    });
+                    attempts.extend(free_kick_attempt(atk_stats, atk_prof, def_prof, rng));
+                }
+                FoulSpot::Harmless => {}
+            }
+            let card = if chance(rng, 0.012 * atk_tactic.press_mult) {
+                def_stats.reds += 1;
+                def_stats.players[fouler].sent_off = true;
+                Some(CardKind::Red)
+            } else if chance(rng, 0.22 * atk_tactic.press_mult) {
                 def_stats.yellows += 1;
-                logs.push(format!(
-                    "{}' Yellow card to {}.",
-                    pad2(minute),
-                    display_name(def_team)
-                ));
+                def_stats.players[fouler].yellows += 1;
+                if def_stats.players[fouler].yellows >= 2 {
+                    def_stats.reds += 1;
+                    def_stats.second_yellows += 1;
+                    def_stats.players[fouler].sent_off = true;
+                    Some(CardKind::SecondYellow)
+                } else {
+                    Some(CardKind::Yellow)
+                }
+            } else {
+                None
+            };
+            if let Some(card) = card {
+                events.push(MatchEvent::Card {
+                    minute,
+                    team: def_team.to_string(),
+                    player: def_stats.player_name(fouler).to_string(),
+                    card,
+                });
             }
         }
 
-        if minute == 45 {
-            logs.push(format!(
-                "Halftime - {} {}-{} {}",
-                display_name(home),
-                home_goals,
-                away_goals,
-                display_name(away)
-            ));
-        }
-
-        if minute == 90 {
-            logs.push(format!(
-                "Full time - {} {}-{} {}",
-                display_name(home),
-                home_goals,
-                away_goals,
-                display_name(away)
-            ));
+        while let Some(shot) = attempts.pop_front() {
+            let set_piece = shot.set_piece;
+            match resolve_shot(&phase, shot, atk_stats, def_stats, &mut events, rng) {
+                ShotOutcome::Goal(goal) => {
+                    if home_attacks {
+                        home_goals += 1;
+                    } else {
+                        away_goals += 1;
+                    }
+                    goals.push(goal);
+                }
+                ShotOutcome::Saved => {
+                    attempts.extend(rebound_attempt(
+                        atk_stats, atk_prof, def_prof, set_piece, rng,
+                    ));
+                }
+                ShotOutcome::Missed => {}
+            }
         }
 
-        frames.push(empty_frame(
-            format!(
-                "{} ({}) {} - {} {} ({}) | {}'",
-                display_name(home),
-                home_profile.formation,
-                home_goals,
-                away_goals,
-                display_name(away),
-                away_profile.formation,
-                pad2(minute)
+        for (team, base, current, state) in [
+            (
+                home,
+                home_base_tactic,
+                &mut home_tactic,
+                GameState {
+                    minute: clock,
+                    goal_diff: home_goals as i16 - away_goals as i16,
+                    reds: stats.home.reds,
+                    opp_reds: stats.away.reds,
+                },
             ),
-            logs,
-        ));
-    }
-
-    let preview_result = MatchResult {
-        home: home.to_string(),
-        away: away.to_string(),
-        home_goals,
-        away_goals,
-        home_profile,
-        away_profile,
-        stats: stats.clone(),
-        home_possession: 50,
-        away_possession: 50,
-    };
-    let (home_poss, away_poss) = possession_pct(&preview_result);
+            (
+                away,
+                away_base_tactic,
+                &mut away_tactic,
+                GameState {
+                    minute: clock,
+                    goal_diff: away_goals as i16 - home_goals as i16,
+                    reds: stats.away.reds,
+                    opp_reds: stats.home.reds,
+                },
+            ),
+        ] {
+            if let Some((tactic, reason)) = review_tactic(base, *current, state) {
+                let change = TacticChange {
+                    minute,
+                    team: team.to_string(),
+                    from: current.label.to_string(),
+                    to: tactic.label.to_string(),
+                    reason: reason.to_string(),
+                };
+                *current = tactic;
+                events.push(MatchEvent::TacticChange(change.clone()));
+                tactic_changes.push(change);
+            }
+        }
 
-    (
-        MatchResult {
+        let score = ScoreLine {
+            home: home.to_string(),
+            away: away.to_string(),
+            home_goals,
+            away_goals,
+        };
+
+        if clock == period_end && minute.added == 0 {
+            let incidents = stats.incidents();
+            stoppage = stoppage_minutes(
+                period_end,
+                incidents.0 - period_start_incidents.0,
+                incidents.1 - period_start_incidents.1,
+                incidents.2 - period_start_incidents.2,
+                rng,
+            );
+            period_start_incidents = incidents;
+            events.push(MatchEvent::StoppageTime {
+                minute,
+                added: stoppage,
+            });
+        }
+
+        let period_over = clock == period_end && minute.added == stoppage;
+        let mut match_over = false;
+        if period_over {
+            match period_end {
+                45 => {
+                    events.push(MatchEvent::HalfTime(score));
+                    period_end = 90;
+                }
+                90 if options.needs_extra_time(home_goals, away_goals) => {
+                    events.push(MatchEvent::NormalTimeEnd(score));
+                    played_extra_time = true;
+                    period_end = 105;
+                }
+                105 => {
+                    events.push(MatchEvent::ExtraTimeHalfTime(score));
+                    period_end = 120;
+                }
+                _ => {
+                    events.push(MatchEvent::FullTime {
+                        score,
+                        after_extra_time: played_extra_time,
+                    });
+                    match_over = true;
+                }
+            }
+        }
+
+        if options.record_frames {
+            let mut frame = event_frame(
+                format!(
+                    "{} ({}) {} - {} {} ({}) | {}'{}",
+                    display_name(home),
+                    home_profile.formation,
+                    home_goals,
+                    away_goals,
+                    display_name(away),
+                    away_profile.formation,
+                    minute.padded(),
+                    options.aggregate_suffix(home_goals, away_goals)
+                ),
+                events,
+            );
+            let state = LiveState {
+                minute,
+                home_goals,
+                away_goals,
+                home_tactic,
+                away_tactic,
+                home_reds: stats.home.reds,
+                away_reds: stats.away.reds,
+            };
+            if options.live_odds {
+                frame.win_prob = Some(win_probability(home, away, &state, options, odds_seed));
+            }
+            frames.push(frame);
+        }
+
+        if match_over {
+            break;
+        }
+        if period_over {
+            minute = Minute {
+                base: clock,
+                added: 0,
+            };
+        }
+    }
+
+    let preview_result = MatchResult {
+        home: home.to_string(),
+        away: away.to_string(),
+        home_goals,
+        away_goals,
+        home_profile,
+        away_profile,
+        stats: stats.clone(),
+        home_possession: 50,
+        away_possession: 50,
+        goals: Vec::new(),
+        tactic_changes: Vec::new(),
+        extra_time: played_extra_time,
+        neutral_venue: options.neutral_venue,
+    };
+    let (home_poss, away_poss) = possession_pct(&preview_result);
+
+    (
+        MatchResult {
             home: home.to_string(),
             away: away.to_string(),
             home_goals,
@@ -370,6 +1102,10 @@ pub fn simulate_match(home: &str, away: &str, rng: &mut Rng) -> (MatchResult, Ve
             stats,
             home_possession: home_poss,
             away_possession: away_poss,
+            goals,
+            tactic_changes,
+            extra_time: played_extra_time,
+            neutral_venue: options.neutral_venue,
         },
         frames,
     )
@@ -379,7 +1115,7 @@ pub fn match_stats_lines(result: &MatchResult) -> Vec<String> {
     let home_tactic = tactic_by_key(result.home_profile.tactic);
     let away_tactic = tactic_by_key(result.away_profile.tactic);
 
-    vec![
+    let mut lines = vec![
         format!(
             "Tactics: {} {} | {} {}",
             display_name(&result.home),
@@ -387,6 +1123,17 @@ pub fn match_stats_lines(result: &MatchResult) -> Vec<String> {
             display_name(&result.away),
             away_tactic.label
         ),
+        format!(
+            "Ratings (ATT/MID/DEF/GK): {}/{}/{}/{} vs {}/{}/{}/{}",
+            result.home_profile.attack,
+            result.home_profile.midfield,
+            result.home_profile.defence,
+            result.home_profile.goalkeeper,
+            result.away_profile.attack,
+            result.away_profile.midfield,
+            result.away_profile.defence,
+            result.away_profile.goalkeeper
+        ),
         format!(
             "Shots (On Target): {} ({}) vs {} ({})",
             result.stats.home.shots,
@@ -402,6 +1149,13 @@ pub fn match_stats_lines(result: &MatchResult) -> Vec<String> {
             "Corners: {} vs {}",
             result.stats.home.corners, result.stats.away.corners
         ),
+        format!(
+            "Set-piece Goals (xG): {} ({:.2}) vs {} ({:.2})",
+            result.stats.home.set_piece_goals,
+            result.stats.home.set_piece_xg,
+            result.stats.away.set_piece_goals,
+            result.stats.away.set_piece_xg
+        ),
         format!(
             "Fouls (Yellows): {} ({}) vs {} ({})",
             result.stats.home.fouls,
@@ -409,6 +1163,10 @@ pub fn match_stats_lines(result: &MatchResult) -> Vec<String> {
             result.stats.away.fouls,
             result.stats.away.yellows
         ),
+        format!(
+            "Red Cards: {} vs {}",
+            result.stats.home.reds, result.stats.away.reds
+        ),
         format!(
             "Offsides: {} vs {}",
             result.stats.home.offsides, result.stats.away.offsides
@@ -421,19 +1179,100 @@ pub fn match_stats_lines(result: &MatchResult) -> Vec<String> {
             "Possession: {}% vs {}%",
             result.home_possession, result.away_possession
         ),
-    ]
+        format!(
+            "Scorers: {} | {}",
+            result.scorers_text(&result.home),
+            result.scorers_text(&result.away)
+        ),
+    ];
+
+    if !result.tactic_changes.is_empty() {
+        lines.push("Tactical changes:".to_string());
+        for change in &result.tactic_changes {
+            lines.push(format!(
+                "  {}' {}: {} -> {} ({})",
+                change.minute.padded(),
+                display_name(&change.team),
+                change.from,
+                change.to,
+                change.reason
+            ));
+        }
+    }
+
+    for (team, team_stats) in [
+        (&result.home, &result.stats.home),
+        (&result.away, &result.stats.away),
+    ] {
+        lines.push(format!("Shot types - {}:", display_name(team)));
+        for shot_type in ShotType::ALL {
+            let t = team_stats.shot_types[shot_type.index()];
+            if t.shots == 0 {
+                continue;
+            }
+            lines.push(format!(
+                "  {:<16} {} shots, {} on target, {} goals, xG {:.2}",
+                shot_type.label(),
+                t.shots,
+                t.on_target,
+                t.goals,
+                t.xg
+            ));
+        }
+    }
+
+    for (team, team_stats) in [
+        (&result.home, &result.stats.home),
+        (&result.away, &result.stats.away),
+    ] {
+        lines.push(format!("Players - {}:", display_name(team)));
+        for p in team_stats.players.iter().filter(|p| p.involved()) {
+            lines.push(format!("  {}", p.summary_line()));
+        }
+    }
+
+    lines
 }
 
-fn standings_cmp(a: &StandingsRow, b: &StandingsRow) -> Ordering {
-    b.pts
-        .cmp(&a.pts)
-        .then(b.gd.cmp(&a.gd))
-        .then(b.gf.cmp(&a.gf))
-        .then(a.team.cmp(&b.team))
+/// Add a finished match, cards included, to both teams' rows.
+fn record_match(table: &mut BTreeMap<String, StandingsRow>, res: &MatchResult) {
+    for (team, scored, conceded, at_home, stats) in [
+        (
+            &res.home,
+            res.home_goals,
+            res.away_goals,
+            true,
+            &res.stats.home,
+        ),
+        (
+            &res.away,
+            res.away_goals,
+            res.home_goals,
+            false,
+            &res.stats.away,
+        ),
+    ] {
+        let row = table.get_mut(team).expect("team in table");
+        row.record(scored, conceded, at_home);
+        row.yellows += stats.yellows;
+        row.reds += stats.reds;
+        row.second_yellows += stats.second_yellows;
+    }
+}
+
+/// "Tie-breaks" footer for a table panel; empty when no tie-breaker was needed.
+fn tie_break_lines(rows: &[StandingsRow]) -> Vec<String> {
+    let notes = tie_break_notes(rows);
+    if notes.is_empty() {
+        return notes;
+    }
+    let mut out = vec![String::new(), "TIE-BREAKS".to_string()];
+    out.extend(notes);
+    out
 }
 
 fn league_table_lines(rows: &[StandingsRow]) -> Vec<String> {
-    let mut out = Vec::with_capacity(rows.len() + 2);
+    let mut out = Vec::with_capacity(rows.len() * 2 + 5);
     out.push("TEAM                         P  W  D  L  GF GA GD PTS".to_string());
     out.push("--------------------------------------------------------".to_string());
     for r in rows {
@@ -442,24 +1281,196 @@ fn league_table_lines(rows: &[StandingsRow]) -> Vec<String> {
             r.team, r.p, r.w, r.d, r.l, r.gf, r.ga, r.gd, r.pts
         ));
     }
+    out.extend(tie_break_lines(rows));
+    out.push(String::new());
+    out.push("HOME / AWAY                  W-D-L GF:GA PTS".to_string());
+    for r in rows {
+        out.push(format!(
+            "{:<28} H {} | A {}",
+            r.team,
+            r.home.summary(),
+            r.away.summary()
+        ));
+    }
     out
 }
 
-fn knockout_bracket_lines(
-    semi1: Option<&str>,
-    semi2: Option<&str>,
-    final_line: Option<&str>,
-    champion: Option<&str>,
-) -> Vec<String> {
-    vec![
-        "Knockout Bracket".to_string(),
-        format!("Semi 1: {}", semi1.unwrap_or("TBD")),
-        "            \\".to_string(),
-        format!("            +-- Final: {}", final_line.unwrap_or("TBD")),
-        "            /".to_string(),
-        format!("Semi 2: {}", semi2.unwrap_or("TBD")),
-        format!("Champion: {}", champion.unwrap_or("TBD")),
-    ]
+struct KnockoutTie {
+    winner: String,
+    line: String,
+    /// Every match played in the tie, the deciding one last.
+    legs: Vec<MatchResult>,
+    frames: Vec<SimFrame>,
+    shootout_lines: Vec<String>,
+    record: TieRecord,
+}
+
+/// Play a knockout tie. A single match goes to extra time on a draw. Two legs swap
+/// venues and only the second leg can go to extra time, when the aggregate (and away
+/// goals, if used) leaves the tie level. Penalties settle anything still level.
+fn play_knockout_tie(
+    label: &str,
+    home: &str,
+    away: &str,
+    engine: &dyn MatchEngine,
+    format: TieFormat,
+    options: MatchOptions,
+    rng: &mut Rng,
+) -> KnockoutTie {
+    let mut frames = Vec::new();
+    let mut legs = Vec::new();
+    let mut leg_lines = Vec::new();
+
+    // The deciding match: the only one, or the second leg with the first carried over.
+    let (decider_home, decider_away, decider_options) = match format {
+        TieFormat::SingleMatch => (
+            home,
+            away,
+            MatchOptions {
+                extra_time: true,
+                ..options
+            },
+        ),
+        TieFormat::TwoLegs { away_goals } => {
+            let (first, mut first_frames) = engine.play(home, away, options, rng);
+            frames.append(&mut first_frames);
+            let first_line = format!(
+                "{} 1st leg: {} {}-{} {}",
+                label,
+                display_name(home),
+                first.home_goals,
+                first.away_goals,
+                display_name(away)
+            );
+            if options.record_frames {
+                frames.push(SimFrame {
+                    scoreboard: format!("{} 1st leg complete - 2nd leg next", label),
+                    logs: vec![first_line.clone()],
+                    events: Vec::new(),
+                    stats_lines: Some(match_stats_lines(&first)),
+                    competition_lines: None,
+                    history_append: vec![first_line.clone()],
+                    win_prob: None,
+                });
+            }
+            leg_lines.push(first_line);
+
+            let second_options = MatchOptions {
+                extra_time: true,
+                first_leg: Some(FirstLeg {
+                    home_goals: first.away_goals,
+                    away_goals: first.home_goals,
+                    away_goals_rule: away_goals,
+                }),
+                home_rating_shift: options.away_rating_shift,
+                away_rating_shift: options.home_rating_shift,
+                ..options
+            };
+            legs.push(first);
+            (away, home, second_options)
+        }
+    };
+
+    let (result, mut match_frames) = engine.play(decider_home, decider_away, decider_options, rng);
+    frames.append(&mut match_frames);
+
+    let mut suffix = Vec::new();
+    let mut shootout_lines = Vec::new();
+    let mut kicks = Vec::new();
+    if let Some(leg) = decider_options.first_leg {
+        leg_lines.push(format!(
+            "{} 2nd leg: {} {}-{} {}",
+            label,
+            display_name(decider_home),
+            result.home_goals,
+            result.away_goals,
+            display_name(decider_away)
+        ));
+        // Both legs read from the perspective of the tie's first-named side.
+        suffix.push(format!(
+            "legs {}-{}, {}-{}",
+            leg.away_goals, leg.home_goals, result.away_goals, result.home_goals
+        ));
+    }
+    if result.extra_time {
+        suffix.push("aet".to_string());
+    }
+    let decider_home_wins = match decider_options.tie_standing(result.home_goals, result.away_goals)
+    {
+        Ordering::Equal => {
+            let (shootout, mut pen_frames) = penalty_shootout(&result, rng);
+            if options.record_frames {
+                frames.append(&mut pen_frames);
+            }
+            // A second-leg shootout is kicked at the first-named side's opponent.
+            suffix.push(if decider_options.first_leg.is_some() {
+                format!("pens {}-{}", shootout.away_score, shootout.home_score)
+            } else {
+                format!("pens {}-{}", shootout.home_score, shootout.away_score)
+            });
+            shootout_lines = shootout
+                .kicks
+                .iter()
+                .map(|k| format!("{} pens - {}", label, k.describe()))
+                .collect();
+            kicks = shootout.kicks;
+            shootout.home_wins
+        }
+        standing => {
+            if let Some(leg) = decider_options.first_leg {
+                if leg.home_goals + result.home_goals == leg.away_goals + result.away_goals {
+                    suffix.push("away goals".to_string());
+                }
+            }
+            standing == Ordering::Greater
+        }
+    };
+    let winner = if decider_home_wins {
+        decider_home
+    } else {
+        decider_away
+    };
+
+    let suffix = if suffix.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", suffix.join(", "))
+    };
+    let line = match decider_options.first_leg {
+        Some(leg) => format!(
+            "{}: {} {}-{} {} on aggregate{}",
+            label,
+            display_name(home),
+            leg.away_goals + result.away_goals,
+            leg.home_goals + result.home_goals,
+            display_name(away),
+            suffix
+        ),
+        None => format!(
+            "{}: {} {}-{} {}{}",
+            label,
+            display_name(home),
+            result.home_goals,
+            result.away_goals,
+            display_name(away),
+            suffix
+        ),
+    };
+    legs.push(result);
+
+    KnockoutTie {
+        winner: winner.to_string(),
+        record: TieRecord {
+            stage: label.to_string(),
+            line: line.clone(),
+            legs: leg_lines,
+            kicks,
+        },
+        line,
+        legs,
+        frames,
+        shootout_lines,
+    }
 }
 
 fn init_table(teams: &[String]) -> BTreeMap<String, StandingsRow> {
@@ -477,345 +1488,993 @@ fn init_table(teams: &[String]) -> BTreeMap<String, StandingsRow> {
                 ga: 0,
                 gd: 0,
                 pts: 0,
+                home: VenueRecord::default(),
+                away: VenueRecord::default(),
+                yellows: 0,
+                reds: 0,
+                second_yellows: 0,
+                tie_break: None,
             },
         );
     }
     map
 }
 
-pub fn run_single(teams: &[String], rng: &mut Rng) -> PreparedSimulation {
+pub fn run_single(teams: &[String], config: &SimConfig, rng: &mut Rng) -> PreparedSimulation {
     let home = teams[0].clone();
     let away = teams[1].clone();
-    let (result, frames) = simulate_match(&home, &away, rng);
-    let stats_lines = match_stats_lines(&result);
-    let mut frames = frames;
-    frames.push(SimFrame {
-        scoreboard: format!(
-            "{} {}-{} {} | FT",
-            display_name(&result.home),
-            result.home_goals,
-            result.away_goals,
-            display_name(&result.away)
-        ),
-        logs: Vec::new(),
-        stats_lines: Some(stats_lines.clone()),
-        competition_lines: None,
-        history_append: Vec::new(),
-    });
+    let options = MatchOptions {
+        live_odds: config.record_frames,
+        ..config.match_options(&home, &away, config.neutral_venue)
+    };
+    let (result, mut frames) = config.engine.engine().play(&home, &away, options, rng);
+    if config.record_frames {
+        frames.push(SimFrame {
+            scoreboard: format!(
+                "{} {}-{} {} | FT",
+                display_name(&result.home),
+                result.home_goals,
+                result.away_goals,
+                display_name(&result.away)
+            ),
+            logs: Vec::new(),
+            events: Vec::new(),
+            stats_lines: Some(match_stats_lines(&result)),
+            competition_lines: None,
+            history_append: Vec::new(),
+            win_prob: None,
+        });
+    }
     PreparedSimulation {
         frames,
-        outcome: SimOutcome::Single(result),
-        history_lines: vec![],
+        results: vec![result.clone()],
+        outcome: SimOutcome::Single(Box::new(result)),
     }
 }
 
-pub fn run_league4(teams: &[String], rng: &mut Rng) -> PreparedSimulation {
-    let fixtures = vec![
-        (teams[0].clone(), teams[1].clone()),
-        (teams[2].clone(), teams[3].clone()),
-        (teams[0].clone(), teams[2].clone()),
-        (teams[1].clone(), teams[3].clone()),
-        (teams[0].clone(), teams[3].clone()),
-        (teams[1].clone(), teams[2].clone()),
-    ];
-
-    let mut table = init_table(teams);
+pub fn run_league(teams: &[String], config: &SimConfig, rng: &mut Rng) -> PreparedSimulation {
+    let matchdays = round_robin(teams.len(), config.double_round_robin);
     let mut frames = Vec::new();
-    let mut history = Vec::new();
-    let mut last_stats = Vec::new();
-
-    let mut initial_table: Vec<StandingsRow> = table.values().cloned().collect();
-    initial_table.sort_by(standings_cmp);
-    frames.push(SimFrame {
-        scoreboard: "League created - waiting for Matchday 1".to_string(),
-        logs: vec!["League table initialized".to_string()],
-        stats_lines: None,
-        competition_lines: Some(league_table_lines(&initial_table)),
-        history_append: Vec::new(),
-    });
 
-    for (idx, (home, away)) in fixtures.iter().enumerate() {
-        frames.push(empty_frame(
-            format!("Running League Match {}/{}", idx + 1, fixtures.len()),
-            vec![format!(
-                "League fixture {}/{}: {} vs {}",
-                idx + 1,
-                fixtures.len(),
-                display_name(home),
-                display_name(away)
-            )],
-        ));
-
-        let (res, mut match_frames) = simulate_match(home, away, rng);
-        frames.append(&mut match_frames);
-        last_stats = match_stats_lines(&res);
-
-        {
-            let home_row = table.get_mut(home).expect("home in table");
-            home_row.p += 1;
-            home_row.gf += res.home_goals as u16;
-            home_row.ga += res.away_goals as u16;
-            home_row.gd = home_row.gf as i16 - home_row.ga as i16;
-            if res.home_goals > res.away_goals {
-                home_row.w += 1;
-                home_row.pts += 3;
-            } else if res.home_goals == res.away_goals {
-                home_row.d += 1;
-                home_row.pts += 1;
-            } else {
-                home_row.l += 1;
-            }
-        }
-
-        {
-            let away_row = table.get_mut(away).expect("away in table");
-            away_row.p += 1;
-            away_row.gf += res.away_goals as u16;
-            away_row.ga += res.home_goals as u16;
-            away_row.gd = away_row.gf as i16 - away_row.ga as i16;
-            if res.away_goals > res.home_goals {
-                away_row.w += 1;
-                away_row.pts += 3;
-            } else if res.away_goals == res.home_goals {
-                away_row.d += 1;
-                away_row.pts += 1;
-            } else {
-                away_row.l += 1;
-            }
-        }
-
-        history.push(format!(
-            "{} {}-{} {}",
-            display_name(home),
-            res.home_goals,
-            res.away_goals,
-            display_name(away)
-        ));
-
-        let mut snapshot: Vec<StandingsRow> = table.values().cloned().collect();
-        snapshot.sort_by(standings_cmp);
+    if config.record_frames {
+        let initial_table: Vec<StandingsRow> = init_table(teams).into_values().collect();
         frames.push(SimFrame {
-            scoreboard: format!("League table updated after Match {}", idx + 1),
-            logs: vec!["Standings updated".to_string()],
-            stats_lines: Some(last_stats.clone()),
-            competition_lines: Some(league_table_lines(&snapshot)),
-            history_append: vec![history.last().cloned().unwrap_or_default()],
+            scoreboard: "League created - waiting for Matchday 1".to_string(),
+            logs: vec![format!(
+                "League table initialized: {} teams, {} matchdays{}",
+                teams.len(),
+                matchdays.len(),
+                if config.double_round_robin {
+                    " (home and away)"
+                } else {
+                    ""
+                }
+            )],
+            events: Vec::new(),
+            stats_lines: None,
+            competition_lines: Some(league_table_lines(&initial_table)),
+            history_append: Vec::new(),
+            win_prob: None,
         });
     }
 
-    let mut final_table: Vec<StandingsRow> = table.into_values().collect();
-    final_table.sort_by(standings_cmp);
+    let phase = play_table_phase(
+        teams,
+        &matchdays,
+        config,
+        "League",
+        &league_table_lines,
+        &mut frames,
+        rng,
+    );
+    let final_table = phase.table;
     let champion = final_table[0].team.clone();
-    history.push(format!(
+    let summary = format!(
         "Champion: {} with {} pts",
         display_name(&champion),
         final_table[0].pts
-    ));
-
-    let competition = league_table_lines(&final_table);
+    );
 
-    frames.push(SimFrame {
-        scoreboard: format!("League complete - Champion {}", display_name(&champion)),
-        logs: vec!["League finished".to_string()],
-        stats_lines: Some(last_stats.clone()),
-        competition_lines: Some(competition.clone()),
-        history_append: vec![history.last().cloned().unwrap_or_default()],
-    });
+    if config.record_frames {
+        frames.push(SimFrame {
+            scoreboard: format!("League complete - Champion {}", display_name(&champion)),
+            logs: vec!["League finished".to_string()],
+            events: Vec::new(),
+            stats_lines: Some(phase.last_stats),
+            competition_lines: Some(league_table_lines(&final_table)),
+            history_append: vec![summary],
+            win_prob: None,
+        });
+    }
 
     PreparedSimulation {
         frames,
+        results: phase.results,
         outcome: SimOutcome::League {
             champion,
             final_table,
+            fixtures: phase.fixtures,
         },
-        history_lines: history,
     }
 }
 
-pub fn run_knockout4(teams: &[String], rng: &mut Rng) -> PreparedSimulation {
-    let semis = vec![
-        (teams[0].clone(), teams[3].clone()),
-        (teams[1].clone(), teams[2].clone()),
-    ];
-    let mut winners = Vec::new();
-    let mut history = Vec::new();
+/// Everything a single-table phase produced, with the table in final order.
+struct TablePhase {
+    results: Vec<MatchResult>,
+    fixtures: Vec<LeagueFixture>,
+    table: Vec<StandingsRow>,
+    last_stats: Vec<String>,
+}
+
+/// Play `matchdays` in order into one table, rendering it with `panel` after every match.
+fn play_table_phase(
+    teams: &[String],
+    matchdays: &[Matchday],
+    config: &SimConfig,
+    label: &str,
+    panel: &dyn Fn(&[StandingsRow]) -> Vec<String>,
+    frames: &mut Vec<SimFrame>,
+    rng: &mut Rng,
+) -> TablePhase {
+    let total_matches: usize = matchdays.iter().map(|md| md.fixtures.len()).sum();
+    let ranking = Ranking::new(&config.tie_breakers, teams, rng);
+    let mut table = init_table(teams);
+    let mut results = Vec::new();
+    let mut fixtures = Vec::with_capacity(total_matches);
+    let mut last_stats = Vec::new();
+
+    let mut played = 0;
+    for (md_idx, matchday) in matchdays.iter().enumerate() {
+        let md_number = md_idx + 1;
+        if config.record_frames {
+            let mut logs = vec![format!("Matchday {}/{}", md_number, matchdays.len())];
+            if let Some(bye) = matchday.bye {
+                logs.push(format!("{} has a bye", display_name(&teams[bye])));
+            }
+            let mut frame = empty_frame(format!("Matchday {} kicking off", md_number), logs);
+            frame.history_append = vec![format!("Matchday {}", md_number)];
+            frames.push(frame);
+        }
+
+        for &(home_idx, away_idx) in &matchday.fixtures {
+            let home = &teams[home_idx];
+            let away = &teams[away_idx];
+            played += 1;
+            if config.record_frames {
+                frames.push(empty_frame(
+                    format!("Running {} Match {}/{}", label, played, total_matches),
+                    vec![format!(
+                        "Matchday {}, fixture {}/{}: {} vs {}",
+                        md_number,
+                        played,
+                        total_matches,
+                        display_name(home),
+                        display_name(away)
+                    )],
+                ));
+            }
+
+            let (res, mut match_frames) = config.engine.engine().play(
+                home,
+                away,
+                config.match_options(home, away, false),
+                rng,
+            );
+            frames.append(&mut match_frames);
+
+            record_match(&mut table, &res);
+
+            fixtures.push(LeagueFixture {
+                matchday: md_number,
+                home: home.clone(),
+                away: away.clone(),
+                home_goals: res.home_goals,
+                away_goals: res.away_goals,
+            });
+
+            if config.record_frames {
+                last_stats = match_stats_lines(&res);
+                let mut snapshot: Vec<StandingsRow> = table.values().cloned().collect();
+                ranking.sort(&mut snapshot, &fixtures);
+                frames.push(SimFrame {
+                    scoreboard: format!("{} table updated after Match {}", label, played),
+                    logs: vec!["Standings updated".to_string()],
+                    events: Vec::new(),
+                    stats_lines: Some(last_stats.clone()),
+                    competition_lines: Some(panel(&snapshot)),
+                    history_append: vec![format!(
+                        "{} {}-{} {}",
+                        display_name(home),
+                        res.home_goals,
+                        res.away_goals,
+                        display_name(away)
+                    )],
+                    win_prob: None,
+                });
+            }
+            results.push(res);
+        }
+    }
+
+    let mut final_table: Vec<StandingsRow> = table.into_values().collect();
+    ranking.sort(&mut final_table, &fixtures);
+    TablePhase {
+        results,
+        fixtures,
+        table: final_table,
+        last_stats,
+    }
+}
+
+/// One frame announcing the pots, then one per ball drawn.
+fn push_draw_frames(title: &str, log: &DrawLog, frames: &mut Vec<SimFrame>) {
+    let mut intro = empty_frame(format!("{title} - pots"), log.intro_lines());
+    intro.history_append = vec![format!("{title} - {}", log.separation_line())];
+    frames.push(intro);
+    let total = log.steps.len();
+    for (idx, step) in log.steps.iter().enumerate() {
+        frames.push(SimFrame {
+            scoreboard: format!("{title} - ball {}/{}", idx + 1, total),
+            logs: vec![step.line.clone()],
+            events: Vec::new(),
+            stats_lines: None,
+            competition_lines: Some(step.board.clone()),
+            history_append: vec![step.line.clone()],
+            win_prob: None,
+        });
+    }
+}
+
+pub fn run_knockout(teams: &[String], config: &SimConfig, rng: &mut Rng) -> PreparedSimulation {
     let mut frames = Vec::new();
-    let mut semi1_line: Option<String> = None;
-    let mut semi2_line: Option<String> = None;
+    let mut bracket = if config.draw == DrawKind::Pots {
+        let (seeds, log) = draw_bracket_from_pots(teams, config.separation, rng);
+        if config.record_frames {
+            push_draw_frames("Knockout draw", &log, &mut frames);
+        }
+        Bracket::seeded(&seeds)
+    } else {
+        Bracket::draw(teams, config.draw, rng)
+    };
+    let mut results = Vec::new();
+
+    if config.record_frames {
+        let byes: Vec<String> = bracket.rounds[0]
+            .iter()
+            .filter(|t| t.is_bye())
+            .filter_map(|t| t.winner.as_deref().map(display_name))
+            .collect();
+        let mut logs = vec![format!(
+            "{} ready ({}, {})",
+            round_name(bracket.rounds[0].len() * 2),
+            config.draw.label(),
+            config.tie_format.label()
+        )];
+        if !byes.is_empty() {
+            logs.push(format!("Byes: {}", byes.join(", ")));
+        }
+        frames.push(SimFrame {
+            scoreboard: "Knockout bracket initialized".to_string(),
+            logs,
+            events: Vec::new(),
+            stats_lines: None,
+            competition_lines: Some(bracket.lines()),
+            history_append: Vec::new(),
+            win_prob: None,
+        });
+    }
 
-    frames.push(SimFrame {
-        scoreboard: "Knockout bracket initialized".to_string(),
-        logs: vec!["Semi-finals ready".to_string()],
-        stats_lines: None,
-        competition_lines: Some(knockout_bracket_lines(None, None, None, None)),
-        history_append: Vec::new(),
-    });
+    let ties = play_bracket(
+        &mut bracket,
+        config,
+        false,
+        &[],
+        &mut frames,
+        &mut results,
+        rng,
+    );
+    let champion = bracket.champion().expect("final played").to_string();
 
-    for (idx, (home, away)) in semis.iter().enumerate() {
-        frames.push(empty_frame(
-            format!("Running Semi-final {}/2", idx + 1),
-            vec![format!(
-                "Semi {}: {} vs {}",
-                idx + 1,
-                display_name(home),
-                display_name(away)
-            )],
-        ));
+    PreparedSimulation {
+        frames,
+        results,
+        outcome: SimOutcome::Knockout { champion, ties },
+    }
+}
 
-        let (res, mut semi_frames) = simulate_match(home, away, rng);
-        frames.append(&mut semi_frames);
+/// Bracket followed by any extra panel lines, such as final group tables.
+fn bracket_panel(bracket: &Bracket, footer: &[String]) -> Vec<String> {
+    let mut lines = bracket.lines();
+    if !footer.is_empty() {
+        lines.push(String::new());
+        lines.extend(footer.iter().cloned());
+    }
+    lines
+}
 
-        let line_text;
+/// Play every tie left in `bracket`, appending frames and results. `neutral` stages
+/// every tie on neutral ground; neutral ties are always single matches.
+fn play_bracket(
+    bracket: &mut Bracket,
+    config: &SimConfig,
+    neutral: bool,
+    footer: &[String],
+    frames: &mut Vec<SimFrame>,
+    results: &mut Vec<MatchResult>,
+    rng: &mut Rng,
+) -> Vec<TieRecord> {
+    let round_count = bracket.rounds.len();
+    let mut ties = Vec::new();
+
+    for round in 0..round_count {
+        let is_final = round + 1 == round_count;
+        for idx in 0..bracket.rounds[round].len() {
+            let slot = &bracket.rounds[round][idx];
+            if slot.is_bye() {
+                continue;
+            }
+            let label = slot.label.clone();
+            let home = slot.home.clone().expect("both sides drawn before kickoff");
+            let away = slot.away.clone().expect("both sides drawn before kickoff");
+
+            if config.record_frames {
+                frames.push(empty_frame(
+                    format!("Running {}", label),
+                    vec![format!(
+                        "{}: {} vs {}",
+                        label,
+                        display_name(&home),
+                        display_name(&away)
+                    )],
+                ));
+            }
 
-        let winner = if res.home_goals == res.away_goals {
-            let (ph, pa, home_wins) = penalties(rng);
-            line_text = format!(
-                "Semi {}: {} {}-{} {} (pens {}-{})",
-                idx + 1,
-                display_name(home),
-                res.home_goals,
-                res.away_goals,
-                display_name(away),
-                ph,
-                pa
-            );
-            history.push(line_text.clone());
-            if home_wins {
-                home.clone()
+            let neutral = neutral || (is_final && config.neutral_final);
+            let format = if neutral {
+                TieFormat::SingleMatch
             } else {
-                away.clone()
-            }
-        } else if res.home_goals > res.away_goals {
-            line_text = format!(
-                "Semi {}: {} {}-{} {}",
-                idx + 1,
-                display_name(home),
-                res.home_goals,
-                res.away_goals,
-                display_name(away)
-            );
-            history.push(line_text.clone());
-            home.clone()
-        } else {
-            line_text = format!(
-                "Semi {}: {} {}-{} {}",
-                idx + 1,
-                display_name(home),
-                res.home_goals,
-                res.away_goals,
-                display_name(away)
+                config.tie_format
+            };
+            let tie = play_knockout_tie(
+                &label,
+                &home,
+                &away,
+                config.engine.engine(),
+                format,
+                config.match_options(&home, &away, neutral),
+                rng,
             );
-            history.push(line_text.clone());
-            away.clone()
-        };
+            let decider = tie.legs.last().expect("a tie plays at least one match");
+            frames.extend(tie.frames);
+            ties.push(tie.record);
+            bracket.set_result(round, idx, tie.winner, tie.line.clone());
+
+            if config.record_frames && !is_final {
+                frames.push(SimFrame {
+                    scoreboard: format!("{} complete", label),
+                    logs: vec!["Bracket updated".to_string()],
+                    events: Vec::new(),
+                    stats_lines: Some(match_stats_lines(decider)),
+                    competition_lines: Some(bracket_panel(bracket, footer)),
+                    history_append: std::iter::once(tie.line)
+                        .chain(tie.shootout_lines)
+                        .collect(),
+                    win_prob: None,
+                });
+            } else if config.record_frames {
+                let champion = bracket.champion().unwrap_or_default();
+                frames.push(SimFrame {
+                    scoreboard: format!("Knockout complete - {}", display_name(champion)),
+                    logs: vec!["Final complete".to_string()],
+                    events: Vec::new(),
+                    stats_lines: Some(match_stats_lines(decider)),
+                    competition_lines: Some(bracket_panel(bracket, footer)),
+                    history_append: std::iter::once(tie.line)
+                        .chain(tie.shootout_lines)
+                        .chain(std::iter::once(format!(
+                            "Champion: {} 🏆",
+                            display_name(champion)
+                        )))
+                        .collect(),
+                    win_prob: None,
+                });
+            }
+            results.extend(tie.legs);
+        }
+    }
+    ties
+}
 
-        if idx == 0 {
-            semi1_line = Some(line_text.clone());
-        } else {
-            semi2_line = Some(line_text.clone());
+/// Compact group table for the competition panel; `cutoff` rows above the line go through.
+fn group_table_lines(name: &str, rows: &[StandingsRow], cutoff: Option<usize>) -> Vec<String> {
+    let mut out = Vec::with_capacity(rows.len() + 3);
+    out.push(format!(
+        "{:<28}  P  W  D  L  GF GA GD PTS",
+        name.to_uppercase()
+    ));
+    for (pos, r) in rows.iter().enumerate() {
+        if cutoff == Some(pos) {
+            out.push("- - - - - - - - - - - - - - - - - - - - - - - -".to_string());
         }
+        out.push(format!(
+            "{:<28} {:>2} {:>2} {:>2} {:>2} {:>3} {:>2} {:>3} {:>3}",
+            r.team, r.p, r.w, r.d, r.l, r.gf, r.ga, r.gd, r.pts
+        ));
+    }
+    out.extend(
+        tie_break_notes(rows)
+            .into_iter()
+            .map(|note| format!("  ({note})")),
+    );
+    out.push(String::new());
+    out
+}
+
+fn groups_panel(
+    names: &[String],
+    tables: &[Vec<StandingsRow>],
+    cutoff: Option<usize>,
+) -> Vec<String> {
+    names
+        .iter()
+        .zip(tables)
+        .flat_map(|(name, rows)| group_table_lines(name, rows, cutoff))
+        .collect()
+}
+
+/// Group stage of four-team round robins on neutral ground, then a knockout bracket
+/// seeded from the group positions: winners first, then runners-up, then any best
+/// third-placed teams.
+pub fn run_tournament(teams: &[String], config: &SimConfig, rng: &mut Rng) -> PreparedSimulation {
+    let mut frames = Vec::new();
+    let draw = if config.draw == DrawKind::Pots {
+        let (groups, log) = draw_groups_from_pots(teams, config.separation, rng);
+        if config.record_frames {
+            push_draw_frames("Group draw", &log, &mut frames);
+        }
+        groups
+    } else {
+        draw_groups(teams, config.draw, rng)
+    };
+    let ranking = Ranking::new(&config.tie_breakers, teams, rng);
+    let names: Vec<String> = (0..draw.len()).map(group_name).collect();
+    let matchdays = round_robin(draw[0].len(), false);
+    let mut tables: Vec<BTreeMap<String, StandingsRow>> =
+        draw.iter().map(|group| init_table(group)).collect();
+    let mut group_fixtures: Vec<Vec<LeagueFixture>> = vec![Vec::new(); draw.len()];
+    let mut results = Vec::new();
+    let mut last_stats = Vec::new();
+
+    let sorted = |tables: &[BTreeMap<String, StandingsRow>],
+                  fixtures: &[Vec<LeagueFixture>]|
+     -> Vec<Vec<StandingsRow>> {
+        tables
+            .iter()
+            .zip(fixtures)
+            .map(|(t, f)| {
+                let mut rows: Vec<StandingsRow> = t.values().cloned().collect();
+                ranking.sort(&mut rows, f);
+                rows
+            })
+            .collect()
+    };
 
+    if config.record_frames {
+        let mut logs = vec![format!(
+            "Tournament drawn: {} groups of {} ({})",
+            draw.len(),
+            draw[0].len(),
+            config.draw.label()
+        )];
+        for (name, group) in names.iter().zip(&draw) {
+            let members: Vec<String> = group.iter().map(|t| display_name(t)).collect();
+            logs.push(format!("{}: {}", name, members.join(", ")));
+        }
         frames.push(SimFrame {
-            scoreboard: format!("Semi-final {} complete", idx + 1),
-            logs: vec!["Bracket updated".to_string()],
-            stats_lines: Some(match_stats_lines(&res)),
-            competition_lines: Some(knockout_bracket_lines(
-                semi1_line.as_deref(),
-                semi2_line.as_deref(),
-                None,
+            scoreboard: "Tournament created - waiting for the group stage".to_string(),
+            logs,
+            events: Vec::new(),
+            stats_lines: None,
+            competition_lines: Some(groups_panel(
+                &names,
+                &sorted(&tables, &group_fixtures),
                 None,
             )),
-            history_append: vec![line_text],
+            history_append: Vec::new(),
+            win_prob: None,
         });
-        winners.push(winner);
     }
 
-    frames.push(empty_frame(
-        "Running Final".to_string(),
-        vec![format!(
-            "Final: {} vs {}",
-            display_name(&winners[0]),
-            display_name(&winners[1])
-        )],
-    ));
+    for (md_idx, matchday) in matchdays.iter().enumerate() {
+        let md_number = md_idx + 1;
+        let header = format!("Group stage - Matchday {}", md_number);
+        if config.record_frames {
+            let mut frame = empty_frame(format!("{} kicking off", header), vec![header.clone()]);
+            frame.history_append = vec![header];
+            frames.push(frame);
+        }
 
-    let (final_res, mut final_frames) = simulate_match(&winners[0], &winners[1], rng);
-    frames.append(&mut final_frames);
-    let last_stats = match_stats_lines(&final_res);
-
-    let final_line;
-    let champion = if final_res.home_goals == final_res.away_goals {
-        let (ph, pa, home_wins) = penalties(rng);
-        final_line = format!(
-            "Final: {} {}-{} {} (pens {}-{})",
-            display_name(&winners[0]),
-            final_res.home_goals,
-            final_res.away_goals,
-            display_name(&winners[1]),
-            ph,
-            pa
-        );
-        history.push(final_line.clone());
-        if home_wins {
-            winners[0].clone()
-        } else {
-            winners[1].clone()
-        }
-    } else if final_res.home_goals > final_res.away_goals {
-        final_line = format!(
-            "Final: {} {}-{} {}",
-            display_name(&winners[0]),
-            final_res.home_goals,
-            final_res.away_goals,
-            display_name(&winners[1])
-        );
-        history.push(final_line.clone());
-        winners[0].clone()
+        for (g, group) in draw.iter().enumerate() {
+            for &(home_idx, away_idx) in &matchday.fixtures {
+                let home = &group[home_idx];
+                let away = &group[away_idx];
+                if config.record_frames {
+                    frames.push(empty_frame(
+                        format!("Running {} Matchday {}", names[g], md_number),
+                        vec![format!(
+                            "{}: {} vs {}",
+                            names[g],
+                            display_name(home),
+                            display_name(away)
+                        )],
+                    ));
+                }
+
+                let (res, mut match_frames) = config.engine.engine().play(
+                    home,
+                    away,
+                    config.match_options(home, away, true),
+                    rng,
+                );
+                frames.append(&mut match_frames);
+
+                record_match(&mut tables[g], &res);
+                group_fixtures[g].push(LeagueFixture {
+                    matchday: md_number,
+                    home: home.clone(),
+                    away: away.clone(),
+                    home_goals: res.home_goals,
+                    away_goals: res.away_goals,
+                });
+
+                if config.record_frames {
+                    last_stats = match_stats_lines(&res);
+                    let panel = groups_panel(&names, &sorted(&tables, &group_fixtures), None);
+                    frames.push(SimFrame {
+                        scoreboard: format!("{} table updated", names[g]),
+                        logs: vec!["Group tables updated".to_string()],
+                        events: Vec::new(),
+                        stats_lines: Some(last_stats.clone()),
+                        competition_lines: Some(panel),
+                        history_append: vec![format!(
+                            "{}: {} {}-{} {}",
+                            names[g],
+                            display_name(home),
+                            res.home_goals,
+                            res.away_goals,
+                            display_name(away)
+                        )],
+                        win_prob: None,
+                    });
+                }
+                results.push(res);
+            }
+        }
+    }
+
+    let final_tables = sorted(&tables, &group_fixtures);
+    let thirds_through = if config.best_thirds {
+        best_third_count(draw.len())
     } else {
-        final_line = format!(
-            "Final: {} {}-{} {}",
-            display_name(&winners[0]),
-            final_res.home_goals,
-            final_res.away_goals,
-            display_name(&winners[1])
-        );
-        history.push(final_line.clone());
-        winners[1].clone()
+        0
     };
+    let by_position = |position: usize| -> Vec<Qualifier> {
+        // Teams from different groups never met, so head-to-head rules pass over them.
+        let mut rows: Vec<StandingsRow> = final_tables
+            .iter()
+            .filter_map(|rows| rows.get(position - 1).cloned())
+            .collect();
+        ranking.sort(&mut rows, &[]);
+        rows.into_iter()
+            .map(|r| Qualifier {
+                group: final_tables
+                    .iter()
+                    .position(|g| g.iter().any(|t| t.team == r.team))
+                    .expect("team in a group"),
+                team: r.team,
+                position,
+            })
+            .collect()
+    };
+    let thirds = by_position(3);
+    let seeds: Vec<Qualifier> = by_position(1)
+        .into_iter()
+        .chain(by_position(2))
+        .chain(thirds.iter().take(thirds_through).cloned())
+        .collect();
+    let seeds = knockout_seeds(seeds);
+    let seed_names: Vec<String> = seeds.iter().map(|q| q.team.clone()).collect();
+    let mut bracket = Bracket::seeded(&seed_names);
+
+    let mut footer = groups_panel(&names, &final_tables, Some(2));
+    if thirds_through > 0 {
+        footer.push("BEST THIRD-PLACED TEAMS".to_string());
+        for (rank, q) in thirds.iter().enumerate() {
+            footer.push(format!(
+                "{:>2}. {:<24} {}{}",
+                rank + 1,
+                q.team,
+                q.code(),
+                if rank < thirds_through {
+                    " - through"
+                } else {
+                    ""
+                }
+            ));
+        }
+    }
 
-    let champion_line = format!("Champion: {} 🏆", display_name(&champion));
-    history.push(champion_line.clone());
-
-    frames.push(SimFrame {
-        scoreboard: format!("Knockout complete - {}", display_name(&champion)),
-        logs: vec!["Final complete".to_string()],
-        stats_lines: Some(last_stats.clone()),
-        competition_lines: Some(knockout_bracket_lines(
-            semi1_line.as_deref(),
-            semi2_line.as_deref(),
-            Some(&final_line),
-            Some(&champion_line),
-        )),
-        history_append: vec![final_line.clone(), champion_line.clone()],
+    if config.record_frames {
+        let codes: Vec<String> = seeds
+            .iter()
+            .map(|q| format!("{} {}", q.code(), display_name(&q.team)))
+            .collect();
+        let stage = format!(
+            "{} ready ({} through{})",
+            round_name(bracket.rounds[0].len() * 2),
+            seeds.len(),
+            if thirds_through > 0 {
+                format!(", {} best thirds", thirds_through)
+            } else {
+                String::new()
+            }
+        );
+        let mut frame = SimFrame {
+            scoreboard: "Group stage complete".to_string(),
+            logs: vec![stage.clone(), format!("Qualified: {}", codes.join(", "))],
+            events: Vec::new(),
+            stats_lines: Some(last_stats),
+            competition_lines: Some(bracket_panel(&bracket, &footer)),
+            history_append: vec![stage],
+            win_prob: None,
+        };
+        if seeds.len() < bracket.rounds[0].len() * 2 {
+            let byes: Vec<String> = bracket.rounds[0]
+                .iter()
+                .filter(|t| t.is_bye())
+                .filter_map(|t| t.winner.as_deref().map(display_name))
+                .collect();
+            frame.logs.push(format!("Byes: {}", byes.join(", ")));
+        }
+        frames.push(frame);
+    }
+
+    let ties = play_bracket(
+        &mut bracket,
+        config,
+        true,
+        &footer,
+        &mut frames,
+        &mut results,
+        rng,
+    );
+    let champion = bracket.champion().expect("final played").to_string();
+    let groups = names
+        .into_iter()
+        .zip(final_tables)
+        .zip(group_fixtures)
+        .map(|((name, table), fixtures)| GroupRecord {
+            name,
+            table,
+            fixtures,
+        })
+        .collect();
+
+    PreparedSimulation {
+        frames,
+        results,
+        outcome: SimOutcome::Tournament {
+            champion,
+            groups,
+            ties,
+        },
+    }
+}
+
+/// League-phase table with a line where each qualification zone starts.
+fn swiss_table_lines(rows: &[StandingsRow], format: SwissFormat) -> Vec<String> {
+    let mut out = Vec::with_capacity(rows.len() + 4);
+    out.push("POS TEAM                        P  W  D  L  GF GA GD PTS".to_string());
+    let mut zone = None;
+    for (pos, r) in rows.iter().enumerate() {
+        let here = format.zone(rows.len(), pos);
+        if zone != Some(here) {
+            out.push(format!("-- {} --", here.label()));
+            zone = Some(here);
+        }
+        out.push(format!(
+            "{:>3} {:<26} {:>2} {:>2} {:>2} {:>2} {:>3} {:>2} {:>3} {:>3}",
+            pos + 1,
+            r.team,
+            r.p,
+            r.w,
+            r.d,
+            r.l,
+            r.gf,
+            r.ga,
+            r.gd,
+            r.pts
+        ));
+    }
+    out.extend(tie_break_lines(rows));
+    out
+}
+
+/// Swiss league phase: teams are potted by strength, each plays two opponents from
+/// every pot (one home, one away), and the single table decides who qualifies
+/// directly, who goes into the play-off and who is out.
+pub fn run_swiss(teams: &[String], config: &SimConfig, rng: &mut Rng) -> PreparedSimulation {
+    let format = config.swiss;
+    let mut by_strength: Vec<usize> = (0..teams.len()).collect();
+    by_strength.sort_by(|&a, &b| {
+        config
+            .strength(&teams[b])
+            .total_cmp(&config.strength(&teams[a]))
+            .then(teams[a].cmp(&teams[b]))
     });
+    let pots: Vec<Vec<usize>> = by_strength
+        .chunks(teams.len() / format.pots())
+        .map(<[usize]>::to_vec)
+        .collect();
+    let matchdays = swiss_schedule(&pots, rng);
+    let panel = |rows: &[StandingsRow]| swiss_table_lines(rows, format);
+    let mut frames = Vec::new();
+
+    if config.record_frames {
+        let (direct, playoff) = format.zones(teams.len());
+        let mut logs = vec![format!(
+            "League phase drawn: {} teams, {} matches each over {} matchdays",
+            teams.len(),
+            format.matches,
+            matchdays.len()
+        )];
+        for (idx, pot) in pots.iter().enumerate() {
+            let members: Vec<String> = pot.iter().map(|&t| display_name(&teams[t])).collect();
+            logs.push(format!("Pot {}: {}", idx + 1, members.join(", ")));
+        }
+        logs.push(format!(
+            "Top {} qualify, {}-{} play-off",
+            direct,
+            direct + 1,
+            direct + playoff
+        ));
+        let initial_table: Vec<StandingsRow> = init_table(teams).into_values().collect();
+        frames.push(SimFrame {
+            scoreboard: "League phase drawn - waiting for Matchday 1".to_string(),
+            logs,
+            events: Vec::new(),
+            stats_lines: None,
+            competition_lines: Some(panel(&initial_table)),
+            history_append: Vec::new(),
+            win_prob: None,
+        });
+    }
+
+    let phase = play_table_phase(
+        teams,
+        &matchdays,
+        config,
+        "League phase",
+        &panel,
+        &mut frames,
+        rng,
+    );
+    let table = phase.table;
+    let leader = table[0].team.clone();
+
+    if config.record_frames {
+        let mut history = vec![format!(
+            "League phase winner: {} with {} pts",
+            display_name(&leader),
+            table[0].pts
+        )];
+        for zone in [Zone::Direct, Zone::Playoff] {
+            let names: Vec<String> = table
+                .iter()
+                .enumerate()
+                .filter(|(pos, _)| format.zone(table.len(), *pos) == zone)
+                .map(|(_, r)| display_name(&r.team))
+                .collect();
+            if !names.is_empty() {
+                history.push(format!("{}: {}", zone.label(), names.join(", ")));
+            }
+        }
+        frames.push(SimFrame {
+            scoreboard: format!("League phase complete - {} top", display_name(&leader)),
+            logs: vec!["League phase finished".to_string()],
+            events: Vec::new(),
+            stats_lines: Some(phase.last_stats),
+            competition_lines: Some(panel(&table)),
+            history_append: history,
+            win_prob: None,
+        });
+    }
 
     PreparedSimulation {
         frames,
-        outcome: SimOutcome::Knockout {
-            champion: champion.clone(),
+        results: phase.results,
+        outcome: SimOutcome::Swiss {
+            leader,
+            table,
+            fixtures: phase.fixtures,
+            format,
         },
-        history_lines: history,
     }
 }
 
 pub fn run_simulation(
     sim_type: SimulationType,
     teams: &[String],
+    config: &SimConfig,
     rng: &mut Rng,
 ) -> PreparedSimulation {
     match sim_type {
-        SimulationType::Single => run_single(teams, rng),
-        SimulationType::League4 => run_league4(teams, rng),
-        SimulationType::Knockout4 => run_knockout4(teams, rng),
+        SimulationType::Single => run_single(teams, config, rng),
+        SimulationType::League4 | SimulationType::League => run_league(teams, config, rng),
+        SimulationType::Knockout4 | SimulationType::Knockout => run_knockout(teams, config, rng),
+        SimulationType::Tournament => run_tournament(teams, config, rng),
+        SimulationType::Swiss => run_swiss(teams, config, rng),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    /// Score after 90 minutes and the goals added in extra time, which only count
+    /// when the match needs it.
+    type ScriptedScore = ((u8, u8), (u8, u8));
+
+    /// Plays one scripted score per match, in order.
+    struct Scripted(RefCell<VecDeque<ScriptedScore>>);
+
+    impl Scripted {
+        fn new(script: &[ScriptedScore]) -> Self {
+            Scripted(RefCell::new(script.iter().copied().collect()))
+        }
+    }
+
+    impl MatchEngine for Scripted {
+        fn play(
+            &self,
+            home: &str,
+            away: &str,
+            options: MatchOptions,
+            _rng: &mut Rng,
+        ) -> (MatchResult, Vec<SimFrame>) {
+            let ((mut h, mut a), (et_h, et_a)) =
+                self.0.borrow_mut().pop_front().expect("scripted match");
+            let extra_time = options.needs_extra_time(h, a);
+            if extra_time {
+                h += et_h;
+                a += et_a;
+            }
+            let result = MatchResult {
+                home: home.to_string(),
+                away: away.to_string(),
+                home_goals: h,
+                away_goals: a,
+                home_profile: profile_for(home),
+                away_profile: profile_for(away),
+                stats: MatchStats {
+                    home: TeamStats::default(),
+                    away: TeamStats::default(),
+                },
+                home_possession: 50,
+                away_possession: 50,
+                goals: Vec::new(),
+                tactic_changes: Vec::new(),
+                extra_time,
+                neutral_venue: options.neutral_venue,
+            };
+            (result, Vec::new())
+        }
+    }
+
+    fn options() -> MatchOptions {
+        SimConfig::default().match_options("Arsenal", "Inter", false)
+    }
+
+    fn second_leg(home_goals: u8, away_goals: u8, away_goals_rule: bool) -> MatchOptions {
+        MatchOptions {
+            extra_time: true,
+            first_leg: Some(FirstLeg {
+                home_goals,
+                away_goals,
+                away_goals_rule,
+            }),
+            ..options()
+        }
+    }
+
+    fn play(format: TieFormat, script: &[ScriptedScore]) -> KnockoutTie {
+        let engine = Scripted::new(script);
+        let mut rng = Rng::new(7);
+        play_knockout_tie(
+            "Final",
+            "Arsenal",
+            "Inter",
+            &engine,
+            format,
+            options(),
+            &mut rng,
+        )
+    }
+
+    #[test]
+    fn away_goals_break_a_level_aggregate() {
+        // Second-leg hosts lost 1-2 away, so lead on away goals after a 1-0 win.
+        let leg = second_leg(1, 2, true);
+        assert_eq!(leg.tie_standing(1, 0), Ordering::Greater);
+        assert!(!leg.needs_extra_time(1, 0));
+        // A 2-1 win makes it 3-3 with one away goal each: still level.
+        assert_eq!(leg.tie_standing(2, 1), Ordering::Equal);
+        assert!(leg.needs_extra_time(2, 1));
+        // Without the rule a level aggregate stays level.
+        let plain = second_leg(1, 2, false);
+        assert_eq!(plain.tie_standing(1, 0), Ordering::Equal);
+        assert!(plain.needs_extra_time(1, 0));
+    }
+
+    #[test]
+    fn single_match_goes_to_extra_time_only_when_level() {
+        let single = MatchOptions {
+            extra_time: true,
+            ..options()
+        };
+        assert!(single.needs_extra_time(1, 1));
+        assert!(!single.needs_extra_time(2, 1));
+        assert!(!options().needs_extra_time(1, 1));
+    }
+
+    #[test]
+    fn two_legged_tie_decided_on_away_goals() {
+        // Arsenal win 2-1 at home, Inter win 1-0 at home: 2-2, Inter have the away goal.
+        let tie = play(
+            TieFormat::TwoLegs { away_goals: true },
+            &[((2, 1), (0, 0)), ((1, 0), (0, 0))],
+        );
+        assert_eq!(tie.winner, "Inter");
+        assert_eq!(tie.legs.len(), 2);
+        assert!(!tie.legs[1].extra_time);
+        assert!(tie.line.contains("2-2"), "{}", tie.line);
+        assert!(tie.line.contains("away goals"), "{}", tie.line);
+    }
+
+    #[test]
+    fn level_aggregate_without_away_goals_goes_to_extra_time() {
+        // Same legs, no away-goals rule: Arsenal score the only goal of extra time.
+        let tie = play(
+            TieFormat::TwoLegs { away_goals: false },
+            &[((2, 1), (0, 0)), ((1, 0), (0, 1))],
+        );
+        assert_eq!(tie.winner, "Arsenal");
+        assert!(tie.legs[1].extra_time);
+        assert!(tie.line.contains("3-2"), "{}", tie.line);
+        assert!(tie.line.contains("aet"), "{}", tie.line);
+        assert!(!tie.line.contains("away goals"), "{}", tie.line);
+    }
+
+    #[test]
+    fn aggregate_lead_wins_without_extra_time() {
+        let tie = play(
+            TieFormat::TwoLegs { away_goals: true },
+            &[((3, 0), (0, 0)), ((2, 1), (0, 0))],
+        );
+        assert_eq!(tie.winner, "Arsenal");
+        assert!(!tie.legs[1].extra_time);
+        assert!(!tie.line.contains("away goals"), "{}", tie.line);
+    }
+
+    #[test]
+    fn single_match_settled_in_extra_time() {
+        let tie = play(TieFormat::SingleMatch, &[((1, 1), (0, 1))]);
+        assert_eq!(tie.winner, "Inter");
+        assert_eq!(tie.legs.len(), 1);
+        assert!(tie.legs[0].extra_time);
+        assert!(tie.line.contains("1-2"), "{}", tie.line);
+        assert!(tie.line.contains("aet"), "{}", tie.line);
     }
 }
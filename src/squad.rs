@@ -0,0 +1,545 @@
+use crate::data::{profile_for, team_flag};
+use crate::utils::{splitmix64, Rng};
+
+pub const SQUAD_SIZE: usize = 18;
+pub const STARTERS: usize = 11;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Position {
+    Goalkeeper,
+    Defender,
+    Midfielder,
+    Forward,
+}
+
+impl Position {
+    pub fn short(self) -> &'static str {
+        match self {
+            Position::Goalkeeper => "GK",
+            Position::Defender => "DF",
+            Position::Midfielder => "MF",
+            Position::Forward => "FW",
+        }
+    }
+
+    fn shot_weight(self) -> f64 {
+        match self {
+            Position::Goalkeeper => 0.0,
+            Position::Defender => 0.6,
+            Position::Midfielder => 2.0,
+            Position::Forward => 4.5,
+        }
+    }
+
+    fn header_weight(self) -> f64 {
+        match self {
+            Position::Goalkeeper => 0.0,
+            Position::Defender => 2.5,
+            Position::Midfielder => 1.0,
+            Position::Forward => 3.5,
+        }
+    }
+
+    fn foul_weight(self) -> f64 {
+        match self {
+            Position::Goalkeeper => 0.1,
+            Position::Defender => 3.0,
+            Position::Midfielder => 2.2,
+            Position::Forward => 0.8,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Player {
+    pub name: String,
+    pub number: u8,
+    pub position: Position,
+    /// Individual quality on a 1-99 scale; drives penalty taking and goalkeeping.
+    pub rating: u8,
+}
+
+/// Per-player counters for one match. Index order matches the starting XI.
+#[derive(Debug, Clone)]
+pub struct PlayerMatchStats {
+    pub name: String,
+    pub number: u8,
+    pub position: Position,
+    pub rating: u8,
+    pub shots: u16,
+    pub goals: u16,
+    pub saves: u16,
+    pub fouls: u16,
+    pub yellows: u16,
+    pub sent_off: bool,
+}
+
+impl PlayerMatchStats {
+    pub fn involved(&self) -> bool {
+        self.shots + self.goals + self.saves + self.fouls + self.yellows > 0 || self.sent_off
+    }
+
+    pub fn summary_line(&self) -> String {
+        format!(
+            "#{} {} ({}) - shots {}, goals {}, saves {}, fouls {}, yellows {}{}",
+            self.number,
+            self.name,
+            self.position.short(),
+            self.shots,
+            self.goals,
+            self.saves,
+            self.fouls,
+            self.yellows,
+            if self.sent_off { ", SENT OFF" } else { "" }
+        )
+    }
+
+    fn from_player(p: &Player) -> Self {
+        Self {
+            name: p.name.clone(),
+            number: p.number,
+            position: p.position,
+            rating: p.rating,
+            shots: 0,
+            goals: 0,
+            saves: 0,
+            fouls: 0,
+            yellows: 0,
+            sent_off: false,
+        }
+    }
+}
+
+/// Match-day squad: the first `STARTERS` players are the starting XI, the rest is the bench.
+#[derive(Debug, Clone)]
+pub struct Squad {
+    pub players: Vec<Player>,
+}
+
+impl Squad {
+    pub fn starters(&self) -> &[Player] {
+        &self.players[..STARTERS.min(self.players.len())]
+    }
+
+    pub fn starter_stats(&self) -> Vec<PlayerMatchStats> {
+        self.starters()
+            .iter()
+            .map(PlayerMatchStats::from_player)
+            .collect()
+    }
+}
+
+const JAPANESE: &[&str] = &[
+    "Tanaka",
+    "Suzuki",
+    "Sato",
+    "Takahashi",
+    "Watanabe",
+    "Ito",
+    "Yamamoto",
+    "Nakamura",
+    "Kobayashi",
+    "Kato",
+    "Yoshida",
+    "Yamada",
+    "Sasaki",
+    "Matsumoto",
+    "Inoue",
+    "Kimura",
+    "Hayashi",
+    "Shimizu",
+    "Mori",
+    "Endo",
+];
+const ENGLISH: &[&str] = &[
+    "Smith", "Jones", "Taylor", "Brown", "Walker", "Wright", "Robinson", "Thompson", "Hughes",
+    "Edwards", "Green", "Hall", "Wood", "Harris", "Clarke", "Turner", "Mitchell", "Campbell",
+    "Stewart", "Bennett",
+];
+const SPANISH: &[&str] = &[
+    "Garcia",
+    "Fernandez",
+    "Gonzalez",
+    "Rodriguez",
+    "Lopez",
+    "Martinez",
+    "Sanchez",
+    "Perez",
+    "Gomez",
+    "Martin",
+    "Diaz",
+    "Torres",
+    "Ramirez",
+    "Ruiz",
+    "Alvarez",
+    "Romero",
+    "Navarro",
+    "Molina",
+    "Castro",
+    "Ortiz",
+];
+const PORTUGUESE: &[&str] = &[
+    "Silva",
+    "Santos",
+    "Ferreira",
+    "Pereira",
+    "Oliveira",
+    "Costa",
+    "Rodrigues",
+    "Almeida",
+    "Nunes",
+    "Carvalho",
+    "Gomes",
+    "Lopes",
+    "Moreira",
+    "Sousa",
+    "Ribeiro",
+    "Mendes",
+    "Cardoso",
+    "Teixeira",
+    "Araujo",
+    "Barbosa",
+];
+const GERMAN: &[&str] = &[
+    "Muller",
+    "Schmidt",
+    "Schneider",
+    "Fischer",
+    "Weber",
+    "Meyer",
+    "Wagner",
+    "Becker",
+    "Schulz",
+    "Hoffmann",
+    "Koch",
+    "Richter",
+    "Klein",
+    "Wolf",
+    "Neumann",
+    "Schwarz",
+    "Kruger",
+    "Braun",
+    "Hartmann",
+    "Lange",
+];
+const FRENCH: &[&str] = &[
+    "Martin", "Bernard", "Dubois", "Thomas", "Robert", "Richard", "Petit", "Durand", "Leroy",
+    "Moreau", "Simon", "Laurent", "Lefebvre", "Michel", "Fontaine", "Girard", "Mercier", "Blanc",
+    "Guerin", "Dupont",
+];
+const ITALIAN: &[&str] = &[
+    "Rossi", "Russo", "Ferrari", "Esposito", "Bianchi", "Romano", "Colombo", "Ricci", "Marino",
+    "Greco", "Bruno", "Gallo", "Conti", "De Luca", "Mancini", "Costa", "Giordano", "Rizzo",
+    "Lombardi", "Moretti",
+];
+const DUTCH: &[&str] = &[
+    "de Jong",
+    "Jansen",
+    "de Vries",
+    "van den Berg",
+    "van Dijk",
+    "Bakker",
+    "Visser",
+    "Smit",
+    "Meijer",
+    "de Boer",
+    "Mulder",
+    "de Groot",
+    "Bos",
+    "Vos",
+    "Peters",
+    "Hendriks",
+    "Dekker",
+    "Brouwer",
+    "de Wit",
+    "Dijkstra",
+];
+const NORDIC: &[&str] = &[
+    "Hansen",
+    "Johansen",
+    "Olsen",
+    "Larsen",
+    "Andersen",
+    "Pedersen",
+    "Nilsen",
+    "Kristiansen",
+    "Jensen",
+    "Karlsson",
+    "Nilsson",
+    "Eriksson",
+    "Lindqvist",
+    "Berg",
+    "Haugen",
+    "Dahl",
+    "Lund",
+    "Holm",
+    "Strand",
+    "Sorensen",
+];
+const SLAVIC: &[&str] = &[
+    "Kovac",
+    "Horvat",
+    "Novak",
+    "Nowak",
+    "Kowalski",
+    "Wisniewski",
+    "Jovanovic",
+    "Petrovic",
+    "Nikolic",
+    "Markovic",
+    "Shevchenko",
+    "Kovalenko",
+    "Bondarenko",
+    "Dvorak",
+    "Svoboda",
+    "Novotny",
+    "Babic",
+    "Peric",
+    "Lewandowski",
+    "Zielinski",
+];
+const TURKISH: &[&str] = &[
+    "Yilmaz", "Kaya", "Demir", "Sahin", "Celik", "Yildiz", "Yildirim", "Ozturk", "Aydin",
+    "Ozdemir", "Arslan", "Dogan", "Kilic", "Aslan", "Cetin", "Kara", "Koc", "Kurt", "Ozkan",
+    "Simsek",
+];
+const ARABIC: &[&str] = &[
+    "Hassan",
+    "Ali",
+    "Mohamed",
+    "Ibrahim",
+    "Mahmoud",
+    "Abdullah",
+    "Khalil",
+    "Mansour",
+    "Saleh",
+    "Haddad",
+    "Nasser",
+    "Karim",
+    "Youssef",
+    "Amrani",
+    "Bensaid",
+    "Al-Dosari",
+    "Al-Shehri",
+    "Hamdi",
+    "Farouk",
+    "Rahimi",
+];
+const AFRICAN: &[&str] = &[
+    "Mensah",
+    "Owusu",
+    "Diallo",
+    "Ndiaye",
+    "Sarr",
+    "Okafor",
+    "Adeyemi",
+    "Eze",
+    "Mbappe",
+    "Toure",
+    "Kone",
+    "Traore",
+    "Nkosi",
+    "Dlamini",
+    "Mokoena",
+    "Aboubakar",
+    "Onana",
+    "Boateng",
+    "Asante",
+    "Bamba",
+];
+const KOREAN: &[&str] = &[
+    "Kim", "Lee", "Park", "Choi", "Jung", "Kang", "Cho", "Yoon", "Jang", "Lim", "Han", "Oh", "Seo",
+    "Shin", "Kwon", "Hwang", "Ahn", "Song", "Hong", "Son",
+];
+const CHINESE: &[&str] = &[
+    "Wang", "Li", "Zhang", "Liu", "Chen", "Yang", "Huang", "Zhao", "Wu", "Zhou", "Xu", "Sun", "Ma",
+    "Zhu", "Hu", "Guo", "He", "Gao", "Lin", "Luo",
+];
+const PERSIAN: &[&str] = &[
+    "Ahmadi",
+    "Hosseini",
+    "Mohammadi",
+    "Rezaei",
+    "Moradi",
+    "Karimi",
+    "Jafari",
+    "Rahimi",
+    "Hashemi",
+    "Ghasemi",
+    "Sadeghi",
+    "Taheri",
+    "Azizi",
+    "Kazemi",
+    "Ebrahimi",
+    "Safari",
+    "Nouri",
+    "Shojaei",
+    "Amiri",
+    "Bagheri",
+];
+
+fn surname_pool(flag: &str) -> &'static [&'static str] {
+    match flag {
+        "🇯🇵" => JAPANESE,
+        "🇪🇸" | "🇦🇷" | "🇺🇾" | "🇨🇴" | "🇨🇱" | "🇵🇪" | "🇪🇨" | "🇵🇾" | "🇧🇴" | "🇻🇪" | "🇲🇽" | "🇨🇷"
+        | "🇵🇦" | "🇭🇳" => SPANISH,
+        "🇵🇹" | "🇧🇷" => PORTUGUESE,
+        "🇩🇪" | "🇦🇹" | "🇨🇭" => GERMAN,
+        "🇫🇷" | "🇧🇪" => FRENCH,
+        "🇮🇹" => ITALIAN,
+        "🇳🇱" => DUTCH,
+        "🇩🇰" | "🇸🇪" | "🇳🇴" => NORDIC,
+        "🇭🇷" | "🇵🇱" | "🇷🇸" | "🇺🇦" | "🇨🇿" => SLAVIC,
+        "🇹🇷" => TURKISH,
+        "🇲🇦" | "🇪🇬" | "🇩🇿" | "🇹🇳" | "🇸🇦" | "🇶🇦" | "🇮🇶" | "🇦🇪" => {
+            ARABIC
+        }
+        "🇸🇳" | "🇳🇬" | "🇬🇭" | "🇨🇲" | "🇨🇮" | "🇿🇦" => AFRICAN,
+        "🇰🇷" => KOREAN,
+        "🇨🇳" => CHINESE,
+        "🇮🇷" => PERSIAN,
+        _ => ENGLISH,
+    }
+}
+
+/// Stable per-team seed so a club always fields the same generated squad.
+fn team_seed(team: &str) -> u64 {
+    let mut h: u64 = 0xCBF29CE484222325;
+    for b in team.as_bytes() {
+        h ^= *b as u64;
+        h = h.wrapping_mul(0x100000001B3);
+    }
+    splitmix64(h)
+}
+
+pub fn squad_for(team: &str) -> Squad {
+    let mut rng = Rng::new(team_seed(team));
+    let pool = surname_pool(team_flag(team));
+    let profile = profile_for(team);
+    let lines = profile.lines();
+
+    let mut positions = vec![Position::Goalkeeper];
+    positions.extend(std::iter::repeat_n(
+        Position::Defender,
+        lines.defenders as usize,
+    ));
+    positions.extend(std::iter::repeat_n(
+        Position::Midfielder,
+        lines.midfielders as usize,
+    ));
+    positions.extend(std::iter::repeat_n(
+        Position::Forward,
+        lines.forwards as usize,
+    ));
+    positions.extend([
+        Position::Goalkeeper,
+        Position::Defender,
+        Position::Defender,
+        Position::Midfielder,
+        Position::Midfielder,
+        Position::Forward,
+        Position::Forward,
+    ]);
+
+    let mut numbers: Vec<u8> = (2..=30).collect();
+    let mut used_names: Vec<String> = Vec::with_capacity(SQUAD_SIZE);
+    let mut players = Vec::with_capacity(SQUAD_SIZE);
+
+    for (idx, position) in positions.into_iter().enumerate() {
+        let number = if idx == 0 {
+            1
+        } else {
+            numbers.remove(rng.range_usize(numbers.len()))
+        };
+
+        let mut name = String::new();
+        for _ in 0..8 {
+            let initial = (b'A' + rng.range_usize(26) as u8) as char;
+            name = format!("{}. {}", initial, pool[rng.range_usize(pool.len())]);
+            if !used_names.contains(&name) {
+                break;
+            }
+        }
+        used_names.push(name.clone());
+        let unit_rating = match position {
+            Position::Goalkeeper => profile.goalkeeper,
+            Position::Defender => profile.defence,
+            Position::Midfielder => profile.midfield,
+            Position::Forward => profile.attack,
+        };
+        let bench_drop = if idx >= STARTERS { 4 } else { 0 };
+        let rating =
+            (unit_rating as i16 + rng.range_usize(11) as i16 - 5 - bench_drop).clamp(40, 99) as u8;
+
+        players.push(Player {
+            name,
+            number,
+            position,
+            rating,
+        });
+    }
+
+    Squad { players }
+}
+
+/// Pick a player index from `players` weighted by position, skipping anyone sent off.
+fn pick_weighted(
+    players: &[PlayerMatchStats],
+    rng: &mut Rng,
+    weight: impl Fn(Position) -> f64,
+) -> usize {
+    let weight_of = |p: &PlayerMatchStats| {
+        if p.sent_off {
+            0.0
+        } else {
+            weight(p.position)
+        }
+    };
+    let total: f64 = players.iter().map(weight_of).sum();
+    let mut roll = rng.next_f64() * total;
+    for (idx, p) in players.iter().enumerate() {
+        roll -= weight_of(p);
+        if roll < 0.0 {
+            return idx;
+        }
+    }
+    players.iter().rposition(|p| !p.sent_off).unwrap_or(0)
+}
+
+pub fn pick_shooter(players: &[PlayerMatchStats], rng: &mut Rng) -> usize {
+    pick_weighted(players, rng, Position::shot_weight)
+}
+
+/// Attacker who gets on the end of a cross or set-piece delivery.
+pub fn pick_header(players: &[PlayerMatchStats], rng: &mut Rng) -> usize {
+    pick_weighted(players, rng, Position::header_weight)
+}
+
+pub fn pick_fouler(players: &[PlayerMatchStats], rng: &mut Rng) -> usize {
+    pick_weighted(players, rng, Position::foul_weight)
+}
+
+/// The keeper on the pitch; if the goalkeeper was sent off, an outfield player goes in goal.
+pub fn goalkeeper(players: &[PlayerMatchStats]) -> usize {
+    players
+        .iter()
+        .position(|p| p.position == Position::Goalkeeper && !p.sent_off)
+        .or_else(|| players.iter().position(|p| !p.sent_off))
+        .unwrap_or(0)
+}
+
+/// Shootout order for the players still on the pitch: forwards first, best takers first
+/// within each line, goalkeeper last.
+pub fn penalty_order(players: &[PlayerMatchStats]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..players.len())
+        .filter(|&i| !players[i].sent_off)
+        .collect();
+    order.sort_by(|&a, &b| {
+        let (pa, pb) = (&players[a], &players[b]);
+        pb.position
+            .shot_weight()
+            .total_cmp(&pa.position.shot_weight())
+            .then(pb.rating.cmp(&pa.rating))
+    });
+    order
+}
@@ -0,0 +1,339 @@
+use crate::fixtures::{round_robin, Matchday};
+use crate::utils::Rng;
+
+/// Matches per team in a Swiss league phase unless told otherwise.
+pub const DEFAULT_SWISS_MATCHES: usize = 8;
+
+/// Where a final league-phase position leads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zone {
+    Direct,
+    Playoff,
+    Eliminated,
+}
+
+impl Zone {
+    pub fn label(self) -> &'static str {
+        match self {
+            Zone::Direct => "Qualified",
+            Zone::Playoff => "Play-off",
+            Zone::Eliminated => "Eliminated",
+        }
+    }
+}
+
+/// Shape of a Swiss league phase: every team plays `matches` opponents, two from
+/// each of `matches / 2` strength pots (one home, one away).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwissFormat {
+    pub matches: usize,
+    /// Top positions that qualify directly; defaults from the team count.
+    pub direct: Option<usize>,
+    /// Positions after the direct places that go into the play-off.
+    pub playoff: Option<usize>,
+}
+
+impl Default for SwissFormat {
+    fn default() -> Self {
+        Self {
+            matches: DEFAULT_SWISS_MATCHES,
+            direct: None,
+            playoff: None,
+        }
+    }
+}
+
+impl SwissFormat {
+    pub fn pots(self) -> usize {
+        self.matches / 2
+    }
+
+    /// Team counts grow in steps that keep pots equal and the field even.
+    pub fn team_step(self) -> usize {
+        let pots = self.pots().max(1);
+        if pots.is_multiple_of(2) {
+            pots
+        } else {
+            pots * 2
+        }
+    }
+
+    /// Smallest field where every pot has three teams, so no pairing repeats.
+    pub fn min_teams(self) -> usize {
+        let step = self.team_step();
+        (self.pots() * 3).div_ceil(step) * step
+    }
+
+    pub fn check(self, team_count: usize) -> Result<(), String> {
+        if self.matches < 2 || !self.matches.is_multiple_of(2) {
+            return Err(format!(
+                "Swiss matches per team must be an even number of at least 2 (got {})",
+                self.matches
+            ));
+        }
+        if team_count < self.min_teams() || !team_count.is_multiple_of(self.team_step()) {
+            return Err(format!(
+                "Swiss with {} matches per team needs a multiple of {} teams, at least {} (got {team_count})",
+                self.matches,
+                self.team_step(),
+                self.min_teams()
+            ));
+        }
+        let (direct, playoff) = self.zones(team_count);
+        if direct + playoff > team_count {
+            return Err(format!(
+                "{direct} direct and {playoff} play-off places do not fit {team_count} teams"
+            ));
+        }
+        Ok(())
+    }
+
+    /// (direct, play-off) place counts. By default the direct places are the largest
+    /// power of two within the top quarter and the play-off takes twice as many.
+    pub fn zones(self, team_count: usize) -> (usize, usize) {
+        let quarter = (team_count / 4).max(1);
+        let default_direct = 1 << quarter.ilog2();
+        let direct = self.direct.unwrap_or(default_direct);
+        let playoff = self
+            .playoff
+            .unwrap_or((direct * 2).min(team_count.saturating_sub(direct)));
+        (direct, playoff)
+    }
+
+    /// Zone for a 0-based final position.
+    pub fn zone(self, team_count: usize, position: usize) -> Zone {
+        let (direct, playoff) = self.zones(team_count);
+        if position < direct {
+            Zone::Direct
+        } else if position < direct + playoff {
+            Zone::Playoff
+        } else {
+            Zone::Eliminated
+        }
+    }
+}
+
+/// League-phase schedule for `pots` of team indices (equal sizes, at least three each).
+///
+/// Pots meet in a round robin of their own. Each pairing of pots p and q is one
+/// alternating cycle p0-q0-p1-q1-..., so every team hosts one side from the other pot
+/// and visits another; each pot also plays a cycle of its own. Cycles of even length
+/// split into two matchdays. An odd-sized pot cannot, so its cycle joins the first
+/// cross-pot pairing and the four matchdays of that block are found by backtracking.
+pub fn swiss_schedule(pots: &[Vec<usize>], rng: &mut Rng) -> Vec<Matchday> {
+    let pot_rounds = round_robin(pots.len(), false);
+    let size = pots[0].len();
+    let mut matchdays = Vec::new();
+
+    let mut shuffled: Vec<Vec<usize>> = pots.to_vec();
+    for pot in &mut shuffled {
+        rng.shuffle(pot);
+    }
+    let own_cycle = |pot: &[usize]| -> Vec<(usize, usize)> {
+        (0..pot.len())
+            .map(|i| (pot[i], pot[(i + 1) % pot.len()]))
+            .collect()
+    };
+
+    // Pots that have not yet played their own cycle.
+    let mut own_pending: Vec<bool> = vec![true; pots.len()];
+
+    for (round_idx, round) in pot_rounds.iter().enumerate() {
+        let mut blocks: Vec<(Vec<(usize, usize)>, usize)> = Vec::new();
+        for &(p, q) in &round.fixtures {
+            let (a, b) = (&shuffled[p], &shuffled[q]);
+            let mut edges: Vec<(usize, usize)> = (0..size)
+                .flat_map(|i| [(a[i], b[i]), (b[i], a[(i + 1) % size])])
+                .collect();
+            if size % 2 == 1 && round_idx == 0 {
+                // Interleaving the two pot cycles keeps the search short.
+                edges.extend(
+                    (0..size).flat_map(|i| [(a[i], a[(i + 1) % size]), (b[i], b[(i + 1) % size])]),
+                );
+                own_pending[p] = false;
+                own_pending[q] = false;
+                blocks.push((edges, 4));
+            } else {
+                blocks.push((edges, 2));
+            }
+        }
+        if let Some(p) = round.bye {
+            blocks.push((own_cycle(&shuffled[p]), 2));
+            own_pending[p] = false;
+        }
+        push_blocks(&blocks, &mut matchdays);
+    }
+
+    // Even pot counts leave a final round where every pot plays its own cycle.
+    let rest: Vec<(Vec<(usize, usize)>, usize)> = own_pending
+        .iter()
+        .enumerate()
+        .filter(|(_, pending)| **pending)
+        .map(|(p, _)| (own_cycle(&shuffled[p]), 2))
+        .collect();
+    if !rest.is_empty() {
+        push_blocks(&rest, &mut matchdays);
+    }
+
+    rng.shuffle(&mut matchdays);
+    matchdays
+}
+
+/// Colour each block's edges into its matchday count and play the blocks side by side.
+/// Pots are the same size, so every block in a round spans the same matchdays.
+fn push_blocks(blocks: &[(Vec<(usize, usize)>, usize)], matchdays: &mut Vec<Matchday>) {
+    let width = blocks.iter().map(|(_, days)| *days).max().unwrap_or(0);
+    let first = matchdays.len();
+    matchdays.extend((0..width).map(|_| Matchday {
+        fixtures: Vec::new(),
+        bye: None,
+    }));
+
+    for (edges, days) in blocks {
+        let colours = colour_edges(edges, *days).expect("league-phase blocks are colourable");
+        for (edge, colour) in edges.iter().zip(colours) {
+            matchdays[first + colour].fixtures.push(*edge);
+        }
+    }
+}
+
+/// Assign each edge one of `colours` matchdays so no team plays twice in a matchday.
+fn colour_edges(edges: &[(usize, usize)], colours: usize) -> Option<Vec<usize>> {
+    fn place(
+        idx: usize,
+        edges: &[(usize, usize)],
+        colours: usize,
+        assigned: &mut Vec<usize>,
+    ) -> bool {
+        let Some(&(home, away)) = edges.get(idx) else {
+            return true;
+        };
+        for colour in 0..colours {
+            let clash = edges[..idx]
+                .iter()
+                .zip(assigned.iter())
+                .any(|(&(h, a), &c)| {
+                    c == colour && (h == home || h == away || a == home || a == away)
+                });
+            if clash {
+                continue;
+            }
+            assigned.push(colour);
+            if place(idx + 1, edges, colours, assigned) {
+                return true;
+            }
+            assigned.pop();
+        }
+        false
+    }
+
+    let mut assigned = Vec::with_capacity(edges.len());
+    place(0, edges, colours, &mut assigned).then_some(assigned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pots(format: SwissFormat, team_count: usize) -> Vec<Vec<usize>> {
+        let size = team_count / format.pots();
+        (0..format.pots())
+            .map(|p| (p * size..(p + 1) * size).collect())
+            .collect()
+    }
+
+    #[test]
+    fn every_team_meets_two_per_pot_home_and_away() {
+        for matches in [2, 4, 6, 8, 10] {
+            let format = SwissFormat {
+                matches,
+                ..SwissFormat::default()
+            };
+            let mut team_count = format.min_teams();
+            while team_count <= 48 {
+                let pots = pots(format, team_count);
+                let pot_of = |team: usize| team / pots[0].len();
+                let matchdays = swiss_schedule(&pots, &mut Rng::new(team_count as u64));
+                for md in &matchdays {
+                    let mut seen = vec![false; team_count];
+                    for &(h, a) in &md.fixtures {
+                        assert!(!seen[h] && !seen[a], "{matches}/{team_count}: {md:?}");
+                        seen[h] = true;
+                        seen[a] = true;
+                    }
+                }
+                let fixtures: Vec<(usize, usize)> = matchdays
+                    .iter()
+                    .flat_map(|md| md.fixtures.clone())
+                    .collect();
+                for team in 0..team_count {
+                    let mut opponents: Vec<usize> = Vec::new();
+                    for pot in 0..format.pots() {
+                        let home: Vec<usize> = fixtures
+                            .iter()
+                            .filter(|&&(h, a)| h == team && pot_of(a) == pot)
+                            .map(|&(_, a)| a)
+                            .collect();
+                        let away: Vec<usize> = fixtures
+                            .iter()
+                            .filter(|&&(h, a)| a == team && pot_of(h) == pot)
+                            .map(|&(h, _)| h)
+                            .collect();
+                        assert_eq!((home.len(), away.len()), (1, 1), "{matches}/{team_count}");
+                        opponents.extend(home.into_iter().chain(away));
+                    }
+                    assert_eq!(opponents.len(), matches);
+                    opponents.sort_unstable();
+                    opponents.dedup();
+                    assert_eq!(
+                        opponents.len(),
+                        matches,
+                        "{matches}/{team_count}: team {team}"
+                    );
+                    assert!(!opponents.contains(&team));
+                }
+                team_count += format.team_step();
+            }
+        }
+    }
+
+    #[test]
+    fn check_rejects_odd_and_oversized_match_counts() {
+        let format = |matches| SwissFormat {
+            matches,
+            ..SwissFormat::default()
+        };
+        assert!(format(8).check(36).is_ok());
+        assert!(format(7).check(36).is_err());
+        assert!(format(0).check(36).is_err());
+        // Too many matches for the field: some pairings would repeat.
+        assert!(format(26).check(36).is_err());
+        // Team counts must keep the pots equal.
+        assert!(format(8).check(34).is_err());
+    }
+
+    #[test]
+    fn zones_split_direct_playoff_and_out() {
+        let format = SwissFormat::default();
+        assert_eq!(format.zones(36), (8, 16));
+        assert_eq!(format.zone(36, 0), Zone::Direct);
+        assert_eq!(format.zone(36, 7), Zone::Direct);
+        assert_eq!(format.zone(36, 8), Zone::Playoff);
+        assert_eq!(format.zone(36, 23), Zone::Playoff);
+        assert_eq!(format.zone(36, 24), Zone::Eliminated);
+
+        let custom = SwissFormat {
+            direct: Some(4),
+            playoff: Some(0),
+            ..SwissFormat::default()
+        };
+        assert_eq!(custom.zones(16), (4, 0));
+        assert_eq!(custom.zone(16, 4), Zone::Eliminated);
+        let too_many = SwissFormat {
+            direct: Some(12),
+            playoff: Some(8),
+            ..SwissFormat::default()
+        };
+        assert!(too_many.check(16).is_err());
+    }
+}
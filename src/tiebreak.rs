@@ -0,0 +1,498 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BTreeMap;
+
+use crate::sim::{LeagueFixture, StandingsRow};
+use crate::utils::Rng;
+
+/// One way to separate teams level on points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreaker {
+    GoalDifference,
+    GoalsFor,
+    /// Points from matches between the teams still level.
+    HeadToHeadPoints,
+    HeadToHeadGoalDifference,
+    HeadToHeadGoalsFor,
+    /// Goals scored away from home in matches between the teams still level.
+    HeadToHeadAwayGoals,
+    /// Goals scored away from home over the whole competition.
+    AwayGoals,
+    Wins,
+    AwayWins,
+    /// Fewest FIFA card points: 1 per yellow, 3 per second-yellow red, 4 per straight red.
+    FairPlay,
+    /// Drawing of lots, made once per competition from the seeded RNG.
+    Lots,
+}
+
+impl TieBreaker {
+    pub const ALL: [TieBreaker; 11] = [
+        TieBreaker::GoalDifference,
+        TieBreaker::GoalsFor,
+        TieBreaker::HeadToHeadPoints,
+        TieBreaker::HeadToHeadGoalDifference,
+        TieBreaker::HeadToHeadGoalsFor,
+        TieBreaker::HeadToHeadAwayGoals,
+        TieBreaker::AwayGoals,
+        TieBreaker::Wins,
+        TieBreaker::AwayWins,
+        TieBreaker::FairPlay,
+        TieBreaker::Lots,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TieBreaker::GoalDifference => "gd",
+            TieBreaker::GoalsFor => "gf",
+            TieBreaker::HeadToHeadPoints => "h2h-points",
+            TieBreaker::HeadToHeadGoalDifference => "h2h-gd",
+            TieBreaker::HeadToHeadGoalsFor => "h2h-gf",
+            TieBreaker::HeadToHeadAwayGoals => "h2h-away-goals",
+            TieBreaker::AwayGoals => "away-goals",
+            TieBreaker::Wins => "wins",
+            TieBreaker::AwayWins => "away-wins",
+            TieBreaker::FairPlay => "fair-play",
+            TieBreaker::Lots => "lots",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TieBreaker::GoalDifference => "goal difference",
+            TieBreaker::GoalsFor => "goals scored",
+            TieBreaker::HeadToHeadPoints => "head-to-head points",
+            TieBreaker::HeadToHeadGoalDifference => "head-to-head goal difference",
+            TieBreaker::HeadToHeadGoalsFor => "head-to-head goals",
+            TieBreaker::HeadToHeadAwayGoals => "head-to-head away goals",
+            TieBreaker::AwayGoals => "away goals",
+            TieBreaker::Wins => "wins",
+            TieBreaker::AwayWins => "away wins",
+            TieBreaker::FairPlay => "fair play",
+            TieBreaker::Lots => "drawing of lots",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<TieBreaker> {
+        TieBreaker::ALL.into_iter().find(|t| t.as_str() == value)
+    }
+
+    fn head_to_head(self) -> bool {
+        matches!(
+            self,
+            TieBreaker::HeadToHeadPoints
+                | TieBreaker::HeadToHeadGoalDifference
+                | TieBreaker::HeadToHeadGoalsFor
+                | TieBreaker::HeadToHeadAwayGoals
+        )
+    }
+}
+
+/// Published rulesets, each ending in a drawing of lots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreakPreset {
+    /// Goal difference, goals scored, then head-to-head points and away goals.
+    #[default]
+    PremierLeague,
+    /// UEFA group stage: head-to-head first, then overall record and fair play.
+    Uefa,
+    /// FIFA World Cup groups: overall goals first, then head-to-head and fair play.
+    Fifa,
+}
+
+impl TieBreakPreset {
+    pub const ALL: [TieBreakPreset; 3] = [
+        TieBreakPreset::PremierLeague,
+        TieBreakPreset::Uefa,
+        TieBreakPreset::Fifa,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TieBreakPreset::PremierLeague => "premier-league",
+            TieBreakPreset::Uefa => "uefa",
+            TieBreakPreset::Fifa => "fifa",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TieBreakPreset::PremierLeague => "Premier League",
+            TieBreakPreset::Uefa => "UEFA",
+            TieBreakPreset::Fifa => "FIFA",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<TieBreakPreset> {
+        TieBreakPreset::ALL
+            .into_iter()
+            .find(|p| p.as_str() == value)
+    }
+
+    pub fn rules(self) -> &'static [TieBreaker] {
+        use TieBreaker::*;
+        match self {
+            TieBreakPreset::PremierLeague => &[
+                GoalDifference,
+                GoalsFor,
+                HeadToHeadPoints,
+                HeadToHeadAwayGoals,
+                Lots,
+            ],
+            TieBreakPreset::Uefa => &[
+                HeadToHeadPoints,
+                HeadToHeadGoalDifference,
+                HeadToHeadGoalsFor,
+                GoalDifference,
+                GoalsFor,
+                AwayGoals,
+                Wins,
+                AwayWins,
+                FairPlay,
+                Lots,
+            ],
+            TieBreakPreset::Fifa => &[
+                GoalDifference,
+                GoalsFor,
+                HeadToHeadPoints,
+                HeadToHeadGoalDifference,
+                HeadToHeadGoalsFor,
+                FairPlay,
+                Lots,
+            ],
+        }
+    }
+
+    /// The preset with exactly these rules, if any.
+    pub fn matching(rules: &[TieBreaker]) -> Option<TieBreakPreset> {
+        TieBreakPreset::ALL.into_iter().find(|p| p.rules() == rules)
+    }
+}
+
+/// A preset name or a comma-separated list of rules, e.g. `uefa` or `h2h-points,gd,lots`.
+pub fn parse_tie_breakers(value: &str) -> Result<Vec<TieBreaker>, String> {
+    if let Some(preset) = TieBreakPreset::parse(value) {
+        return Ok(preset.rules().to_vec());
+    }
+    value
+        .split(',')
+        .map(|key| {
+            TieBreaker::parse(key.trim()).ok_or_else(|| {
+                let presets: Vec<&str> = TieBreakPreset::ALL.iter().map(|p| p.as_str()).collect();
+                let rules: Vec<&str> = TieBreaker::ALL.iter().map(|t| t.as_str()).collect();
+                format!(
+                    "unknown tie-breaker '{}' (presets: {}; rules: {})",
+                    key.trim(),
+                    presets.join(", "),
+                    rules.join(", ")
+                )
+            })
+        })
+        .collect()
+}
+
+/// Preset name, or "Custom" followed by the rule keys.
+pub fn tie_breakers_label(rules: &[TieBreaker]) -> String {
+    match TieBreakPreset::matching(rules) {
+        Some(preset) => preset.label().to_string(),
+        None => {
+            let keys: Vec<&str> = rules.iter().map(|t| t.as_str()).collect();
+            format!("Custom ({})", keys.join(", "))
+        }
+    }
+}
+
+/// Tie-breakers for one competition, with its lots already drawn.
+#[derive(Debug, Clone)]
+pub struct Ranking {
+    rules: Vec<TieBreaker>,
+    lots: BTreeMap<String, usize>,
+}
+
+impl Ranking {
+    /// Lots are drawn for `teams` only when the rules use them, so other rulesets
+    /// leave the RNG untouched.
+    pub fn new(rules: &[TieBreaker], teams: &[String], rng: &mut Rng) -> Self {
+        let mut lots = BTreeMap::new();
+        if rules.contains(&TieBreaker::Lots) {
+            let mut order = teams.to_vec();
+            rng.shuffle(&mut order);
+            // The first team drawn ranks highest.
+            lots = order
+                .into_iter()
+                .enumerate()
+                .map(|(idx, team)| (team, teams.len() - idx))
+                .collect();
+        }
+        Self {
+            rules: rules.to_vec(),
+            lots,
+        }
+    }
+
+    /// Sort `rows` by points, then the tie-breakers, marking each row with the rule
+    /// that put it above the next one. `fixtures` are the matches played so far,
+    /// used for head-to-head records. Teams level on every rule stay in name order.
+    pub fn sort(&self, rows: &mut [StandingsRow], fixtures: &[LeagueFixture]) {
+        rows.sort_by(|a, b| b.pts.cmp(&a.pts).then(a.team.cmp(&b.team)));
+        for row in rows.iter_mut() {
+            row.tie_break = None;
+        }
+        let mut start = 0;
+        while start < rows.len() {
+            let pts = rows[start].pts;
+            let end = start + rows[start..].iter().take_while(|r| r.pts == pts).count();
+            if end - start > 1 {
+                self.separate(&mut rows[start..end], fixtures);
+            }
+            start = end;
+        }
+    }
+
+    /// Order `rows`, all level so far, by the first rule that tells any of them apart.
+    /// Groups still level start again from the top, so head-to-head records are
+    /// recounted among fewer teams.
+    fn separate(&self, rows: &mut [StandingsRow], fixtures: &[LeagueFixture]) {
+        for &rule in &self.rules {
+            let keys = self.keys(rule, rows, fixtures);
+            if keys.iter().all(|k| *k == keys[0]) {
+                continue;
+            }
+            let mut keyed: Vec<(i32, StandingsRow)> =
+                keys.into_iter().zip(rows.iter().cloned()).collect();
+            keyed.sort_by_key(|(key, _)| Reverse(*key));
+
+            let mut start = 0;
+            while start < keyed.len() {
+                let key = keyed[start].0;
+                let end = start + keyed[start..].iter().take_while(|(k, _)| *k == key).count();
+                for (slot, (_, row)) in rows[start..end].iter_mut().zip(&keyed[start..end]) {
+                    *slot = row.clone();
+                }
+                if end - start > 1 {
+                    self.separate(&mut rows[start..end], fixtures);
+                }
+                if end < rows.len() {
+                    rows[end - 1].tie_break = Some(rule);
+                }
+                start = end;
+            }
+            return;
+        }
+    }
+
+    /// Value of `rule` for each row; higher ranks first.
+    fn keys(
+        &self,
+        rule: TieBreaker,
+        rows: &[StandingsRow],
+        fixtures: &[LeagueFixture],
+    ) -> Vec<i32> {
+        if rule.head_to_head() {
+            let level: Vec<&str> = rows.iter().map(|r| r.team.as_str()).collect();
+            let mut records: BTreeMap<&str, [i32; 4]> = BTreeMap::new();
+            for f in fixtures {
+                if !level.contains(&f.home.as_str()) || !level.contains(&f.away.as_str()) {
+                    continue;
+                }
+                let (hg, ag) = (f.home_goals as i32, f.away_goals as i32);
+                let (home_pts, away_pts) = match hg.cmp(&ag) {
+                    Ordering::Greater => (3, 0),
+                    Ordering::Equal => (1, 1),
+                    Ordering::Less => (0, 3),
+                };
+                // [points, goal difference, goals for, away goals]
+                let home = records.entry(f.home.as_str()).or_default();
+                home[0] += home_pts;
+                home[1] += hg - ag;
+                home[2] += hg;
+                let away = records.entry(f.away.as_str()).or_default();
+                away[0] += away_pts;
+                away[1] += ag - hg;
+                away[2] += ag;
+                away[3] += ag;
+            }
+            let idx = match rule {
+                TieBreaker::HeadToHeadPoints => 0,
+                TieBreaker::HeadToHeadGoalDifference => 1,
+                TieBreaker::HeadToHeadGoalsFor => 2,
+                _ => 3,
+            };
+            return rows
+                .iter()
+                .map(|r| {
+                    records
+                        .get(r.team.as_str())
+                        .map(|rec| rec[idx])
+                        .unwrap_or(0)
+                })
+                .collect();
+        }
+
+        rows.iter()
+            .map(|r| match rule {
+                TieBreaker::GoalDifference => r.gd as i32,
+                TieBreaker::GoalsFor => r.gf as i32,
+                TieBreaker::AwayGoals => r.away.gf as i32,
+                TieBreaker::Wins => r.w as i32,
+                TieBreaker::AwayWins => r.away.w as i32,
+                TieBreaker::FairPlay => -r.fair_play_points(),
+                TieBreaker::Lots => self.lots.get(&r.team).copied().unwrap_or(0) as i32,
+                _ => 0,
+            })
+            .collect()
+    }
+}
+
+/// "A above B on head-to-head points" for every pair of neighbours split by a tie-breaker.
+pub fn tie_break_notes(rows: &[StandingsRow]) -> Vec<String> {
+    rows.windows(2)
+        .filter_map(|pair| {
+            pair[0].tie_break.map(|rule| {
+                format!(
+                    "{} above {} on {}",
+                    pair[0].team,
+                    pair[1].team,
+                    rule.label()
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::VenueRecord;
+
+    /// Table row with only the totals the presets read filled in.
+    fn row(team: &str, pts: u8, gf: u16, ga: u16) -> StandingsRow {
+        StandingsRow {
+            team: team.to_string(),
+            p: 0,
+            w: 0,
+            d: 0,
+            l: 0,
+            gf,
+            ga,
+            gd: gf as i16 - ga as i16,
+            pts,
+            home: VenueRecord::default(),
+            away: VenueRecord::default(),
+            yellows: 0,
+            reds: 0,
+            second_yellows: 0,
+            tie_break: None,
+        }
+    }
+
+    fn fixture(home: &str, away: &str, home_goals: u8, away_goals: u8) -> LeagueFixture {
+        LeagueFixture {
+            matchday: 1,
+            home: home.to_string(),
+            away: away.to_string(),
+            home_goals,
+            away_goals,
+        }
+    }
+
+    fn ranked(preset: TieBreakPreset, rows: &mut [StandingsRow], fixtures: &[LeagueFixture]) {
+        let teams: Vec<String> = rows.iter().map(|r| r.team.clone()).collect();
+        Ranking::new(preset.rules(), &teams, &mut Rng::new(1)).sort(rows, fixtures);
+    }
+
+    fn order(rows: &[StandingsRow]) -> Vec<&str> {
+        rows.iter().map(|r| r.team.as_str()).collect()
+    }
+
+    /// Spain have the better goal difference, Italy won the meeting between them.
+    fn head_to_head_tie() -> (Vec<StandingsRow>, Vec<LeagueFixture>) {
+        let rows = vec![
+            row("Italy", 6, 5, 3),
+            row("Spain", 6, 7, 3),
+            row("Japan", 4, 3, 5),
+        ];
+        let fixtures = vec![
+            fixture("Spain", "Italy", 0, 1),
+            fixture("Spain", "Japan", 4, 0),
+            fixture("Italy", "Japan", 1, 1),
+        ];
+        (rows, fixtures)
+    }
+
+    #[test]
+    fn premier_league_and_fifa_rank_on_goal_difference_first() {
+        for preset in [TieBreakPreset::PremierLeague, TieBreakPreset::Fifa] {
+            let (mut rows, fixtures) = head_to_head_tie();
+            ranked(preset, &mut rows, &fixtures);
+            assert_eq!(order(&rows), ["Spain", "Italy", "Japan"], "{preset:?}");
+            assert_eq!(rows[0].tie_break, Some(TieBreaker::GoalDifference));
+            assert_eq!(rows[1].tie_break, None);
+        }
+    }
+
+    #[test]
+    fn uefa_ranks_on_head_to_head_first() {
+        let (mut rows, fixtures) = head_to_head_tie();
+        ranked(TieBreakPreset::Uefa, &mut rows, &fixtures);
+        assert_eq!(order(&rows), ["Italy", "Spain", "Japan"]);
+        assert_eq!(rows[0].tie_break, Some(TieBreaker::HeadToHeadPoints));
+        assert_eq!(
+            tie_break_notes(&rows),
+            ["Italy above Spain on head-to-head points"]
+        );
+    }
+
+    #[test]
+    fn fifa_falls_back_to_fair_play_points() {
+        // Level on everything, including a three-way head-to-head cycle.
+        let mut rows = vec![
+            row("Ghana", 3, 1, 1),
+            row("Iran", 3, 1, 1),
+            row("Peru", 3, 1, 1),
+        ];
+        let fixtures = vec![
+            fixture("Ghana", "Iran", 1, 0),
+            fixture("Iran", "Peru", 1, 0),
+            fixture("Peru", "Ghana", 1, 0),
+        ];
+        // 4 yellows and a second-yellow red: 2 + 3 = 5 points.
+        rows[0].yellows = 4;
+        rows[0].reds = 1;
+        rows[0].second_yellows = 1;
+        // 2 yellows and a straight red: 2 + 4 = 6 points.
+        rows[1].yellows = 2;
+        rows[1].reds = 1;
+        // 4 yellows: 4 points.
+        rows[2].yellows = 4;
+        assert_eq!(
+            rows.iter()
+                .map(|r| r.fair_play_points())
+                .collect::<Vec<_>>(),
+            [5, 6, 4]
+        );
+
+        ranked(TieBreakPreset::Fifa, &mut rows, &fixtures);
+        assert_eq!(order(&rows), ["Peru", "Ghana", "Iran"]);
+        assert_eq!(rows[0].tie_break, Some(TieBreaker::FairPlay));
+        assert_eq!(rows[1].tie_break, Some(TieBreaker::FairPlay));
+    }
+
+    #[test]
+    fn parses_presets_and_rule_lists() {
+        assert_eq!(
+            parse_tie_breakers("uefa").unwrap(),
+            TieBreakPreset::Uefa.rules()
+        );
+        let custom = parse_tie_breakers("h2h-points, gd,lots").unwrap();
+        assert_eq!(
+            custom,
+            [
+                TieBreaker::HeadToHeadPoints,
+                TieBreaker::GoalDifference,
+                TieBreaker::Lots
+            ]
+        );
+        assert_eq!(tie_breakers_label(&custom), "Custom (h2h-points, gd, lots)");
+        assert!(parse_tie_breakers("gd,coin-toss").is_err());
+    }
+}
@@ -46,6 +46,7 @@ pub fn render(f: &mut Frame<'_>, area: Rect, app: &App) {
     let detail_text = if let Some(inst) = app.selected_instance() {
         let status = match &inst.status {
             SimStatus::Pending => "pending",
+            SimStatus::Preparing => "preparing",
             SimStatus::Running { .. } => "running",
             SimStatus::Completed => "completed",
         };
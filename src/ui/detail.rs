@@ -3,6 +3,7 @@ use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
 
 use crate::app::App;
+use crate::ui::widgets::{win_prob_bar, win_prob_title};
 
 pub fn render(f: &mut Frame<'_>, area: Rect, app: &App) {
     let Some(inst) = app.selected_instance() else {
@@ -12,9 +13,14 @@ pub fn render(f: &mut Frame<'_>, area: Rect, app: &App) {
         return;
     };
 
+    let bar_height = if inst.win_prob.is_some() { 3 } else { 0 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(8)])
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(bar_height),
+            Constraint::Min(8),
+        ])
         .split(area);
 
     let score = Paragraph::new(inst.scoreboard.clone())
@@ -26,10 +32,20 @@ pub fn render(f: &mut Frame<'_>, area: Rect, app: &App) {
         .style(Style::default().fg(Color::White).bg(Color::Black));
     f.render_widget(score, chunks[0]);
 
+    if let Some(prob) = &inst.win_prob {
+        let inner_width = chunks[1].width.saturating_sub(2);
+        let bar = Paragraph::new(win_prob_bar(prob, inner_width)).block(
+            Block::default()
+                .title(win_prob_title(prob))
+                .borders(Borders::ALL),
+        );
+        f.render_widget(bar, chunks[1]);
+    }
+
     let middle = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(chunks[1]);
+        .split(chunks[2]);
 
     let logs: Vec<ListItem> = inst
         .logs
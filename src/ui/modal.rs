@@ -1,9 +1,11 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
 
 use crate::app::{App, CreateDraft, OverlayModal};
+use crate::bracket::DrawKind;
 use crate::data::TEAMS;
+use crate::groups::GROUP_SIZE;
 
 pub fn render_create(f: &mut Frame<'_>, area: Rect, app: &App, draft: &CreateDraft) {
     let popup = centered_rect(70, 70, area);
@@ -21,13 +23,63 @@ pub fn render_create(f: &mut Frame<'_>, area: Rect, app: &App, draft: &CreateDra
         .constraints([
             Constraint::Length(2),
             Constraint::Min(4),
-            Constraint::Length(2),
+            Constraint::Length(3),
         ])
         .split(popup);
 
+    let draw = if draft.draw == DrawKind::Pots {
+        format!("pots ({})", draft.separation.label())
+    } else {
+        draft.draw.as_str().to_string()
+    };
+    let venue = if draft.is_knockout() {
+        format!(
+            " | draw: {} | ties: {} | final: {}",
+            draw,
+            draft.tie_format.label(),
+            if draft.neutral_final {
+                "neutral"
+            } else {
+                "home"
+            }
+        )
+    } else if draft.is_tournament() {
+        format!(
+            " | {} groups | draw: {} | best thirds: {}",
+            draft.slots.len() / GROUP_SIZE,
+            draw,
+            if draft.best_thirds { "on" } else { "off" }
+        )
+    } else if draft.is_swiss() {
+        let (direct, playoff) = draft.swiss.zones(draft.slots.len());
+        format!(
+            " | {} matches, {} pots | top {} qualify, next {} play-off",
+            draft.swiss.matches,
+            draft.swiss.pots(),
+            direct,
+            playoff
+        )
+    } else if draft.is_league() {
+        if draft.double_round_robin {
+            " | home and away".to_string()
+        } else {
+            " | single round robin".to_string()
+        }
+    } else {
+        String::new()
+    };
+    let tie_break = if draft.is_league() || draft.is_tournament() || draft.is_swiss() {
+        format!(" | tie-break: {}", draft.tie_break.label())
+    } else {
+        String::new()
+    };
     let top = Paragraph::new(format!(
-        "Mode: {} | next-seed={} | select slot with up/down",
+        "Mode: {}{}{} | engine: {} | strength: {} | next-seed={} | select slot with up/down",
         draft.mode_label(),
+        venue,
+        tie_break,
+        draft.engine.label(),
+        if draft.use_elo { "elo" } else { "profile" },
         app.next_instance_seed_preview()
     ));
     f.render_widget(top, inner[0]);
@@ -36,7 +88,7 @@ pub fn render_create(f: &mut Frame<'_>, area: Rect, app: &App, draft: &CreateDra
     for (i, slot) in draft.slots.iter().enumerate() {
         let slot_name = slot_label(draft.slots.len(), i);
         let content = if slot.is_cpu {
-            format!("{}: CPU auto-fill", slot_name)
+            format!("{}: CPU auto-fill ({})", slot_name, draft.pool.label())
         } else {
             format!("{}: MANUAL -> {}", slot_name, TEAMS[slot.team_idx])
         };
@@ -50,12 +102,19 @@ pub fn render_create(f: &mut Frame<'_>, area: Rect, app: &App, draft: &CreateDra
         }
         rows.push(item);
     }
-    let list = List::new(rows).block(Block::default().title("Team Slots").borders(Borders::ALL));
-    f.render_widget(list, inner[1]);
+    let list = List::new(rows).block(
+        Block::default()
+            .title(format!("Team Slots ({})", draft.slots.len()))
+            .borders(Borders::ALL),
+    );
+    // Long leagues scroll to keep the selected slot visible.
+    let mut state = ListState::default().with_selected(Some(draft.selected_slot));
+    f.render_stateful_widget(list, inner[1], &mut state);
 
     let help = Paragraph::new(
-        "m=manual, p=cpu, [ / ] or left/right change manual team, Enter=create, Esc=cancel",
-    );
+        "m=manual, p=cpu, [ / ] or left/right change manual team, f=neutral final (knockout), e=engine, r=elo strength, d=home and away (league), s=seeded/random/pot draw (knockout/tournament), c=pot draw separation, t=single/two legs/away goals (knockout), b=best thirds (tournament), x=matches per team (swiss), g=tie-break preset (league/tournament/swiss), o=CPU auto-fill pool, +/- teams (league/knockout/tournament/swiss), Enter=create, Esc=cancel",
+    )
+    .wrap(ratatui::widgets::Wrap { trim: true });
     f.render_widget(help, inner[2]);
 }
 
@@ -157,20 +216,17 @@ fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     horizontal[1]
 }
 
-fn slot_label(total: usize, i: usize) -> &'static str {
+fn slot_label(total: usize, i: usize) -> String {
     if total == 2 {
         match i {
             0 => "Home",
             1 => "Away",
             _ => "Slot",
         }
+        .to_string()
+    } else if i < 26 {
+        format!("Team {}", (b'A' + i as u8) as char)
     } else {
-        match i {
-            0 => "Team A",
-            1 => "Team B",
-            2 => "Team C",
-            3 => "Team D",
-            _ => "Slot",
-        }
+        format!("Team {}", i + 1)
     }
 }
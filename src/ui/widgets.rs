@@ -1,9 +1,50 @@
+use ratatui::prelude::*;
+
+use crate::data::display_name;
 use crate::instance::SimStatus;
+use crate::winprob::WinProbability;
 
 pub fn status_badge(status: &SimStatus) -> &'static str {
     match status {
         SimStatus::Pending => "PENDING",
+        SimStatus::Preparing => "PREPARING",
         SimStatus::Running { .. } => "RUNNING",
         SimStatus::Completed => "COMPLETED",
     }
 }
+
+/// Title for the win-probability bar, e.g. "Win probability 63' - Japan 48% | Draw 27% | Iran 25%".
+pub fn win_prob_title(prob: &WinProbability) -> String {
+    format!(
+        "Win probability {}' - {} {:.0}% | Draw {:.0}% | {} {:.0}%",
+        prob.minute,
+        display_name(&prob.home),
+        prob.home_win * 100.0,
+        prob.draw * 100.0,
+        display_name(&prob.away),
+        prob.away_win * 100.0
+    )
+}
+
+/// Home/draw/away split as one coloured bar `width` cells wide.
+pub fn win_prob_bar(prob: &WinProbability, width: u16) -> Line<'static> {
+    let width = width as usize;
+    let home = ((prob.home_win * width as f64).round() as usize).min(width);
+    let away = ((prob.away_win * width as f64).round() as usize).min(width - home);
+    let draw = width - home - away;
+
+    let segment = |cells: usize, pct: f64, color: Color| {
+        let label = format!("{:.0}%", pct * 100.0);
+        let text = if cells >= label.len() + 2 {
+            format!("{:^cells$}", label)
+        } else {
+            " ".repeat(cells)
+        };
+        Span::styled(text, Style::default().fg(Color::Black).bg(color))
+    };
+    Line::from(vec![
+        segment(home, prob.home_win, Color::Green),
+        segment(draw, prob.draw, Color::Gray),
+        segment(away, prob.away_win, Color::Red),
+    ])
+}
@@ -47,6 +47,14 @@ impl Rng {
         }
         (self.next_u64() % (upper_exclusive as u64)) as usize
     }
+
+    /// Fisher-Yates shuffle.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.range_usize(i + 1);
+            items.swap(i, j);
+        }
+    }
 }
 
 pub fn derive_seed(base_seed: u64, salt: u64) -> u64 {
@@ -8,12 +8,26 @@ use actix_web::{web, App as ActixApp, HttpResponse, HttpServer, Responder};
 use serde::{Deserialize, Serialize};
 
 use crate::app::Speed;
-use crate::data::{display_name, TEAMS};
+use crate::bracket::DrawKind;
+use crate::data::{display_name, TeamFilter, TEAMS, TEAMS_DATA};
+use crate::draw::Separation;
+use crate::elo::{EloStore, RatingsConfig};
+use crate::engine::EngineKind;
+use crate::events::MatchEvent;
+use crate::groups::{GROUP_SIZE, MIN_TOURNAMENT_TEAMS};
 use crate::instance::{SimStatus, SimulationInstance};
-use crate::sim::SimulationType;
+use crate::sim::{
+    SimConfig, SimulationType, TieFormat, DEFAULT_HOME_ADVANTAGE, MIN_KNOCKOUT_TEAMS,
+    MIN_LEAGUE_TEAMS,
+};
+use crate::swiss::SwissFormat;
+use crate::tiebreak::{parse_tie_breakers, tie_breakers_label};
 use crate::utils::{derive_seed, Rng};
+use crate::winprob::WinProbability;
 
 const WEB_PORT: u16 = 9009;
+/// Largest field a web-created simulation may use; bigger competitions stay on the CLI.
+const MAX_WEB_TEAMS: usize = 48;
 
 #[derive(Clone)]
 struct SharedState {
@@ -26,16 +40,18 @@ struct WebState {
     demo: bool,
     next_id: usize,
     instances: Vec<SimulationInstance>,
+    ratings: RatingsConfig,
 }
 
 impl WebState {
-    fn new(base_seed: u64, speed: Speed, demo: bool) -> Self {
+    fn new(base_seed: u64, speed: Speed, demo: bool, ratings: RatingsConfig) -> Self {
         Self {
             base_seed,
             speed,
             demo,
             next_id: 0,
             instances: Vec::new(),
+            ratings,
         }
     }
 
@@ -49,6 +65,11 @@ impl WebState {
             if matches!(inst.status, SimStatus::Running { .. }) {
                 inst.tick(frames);
             }
+            if let Some(results) = inst.take_results_for_ratings() {
+                if let Err(e) = self.ratings.record(&results) {
+                    eprintln!("Ratings update failed for sim-{}: {e}", inst.id);
+                }
+            }
         }
     }
 
@@ -76,12 +97,37 @@ struct ErrorDto {
 struct TeamDto {
     name: String,
     display_name: String,
+    kind: String,
+    country: String,
+    confederation: String,
+    league: Option<String>,
+}
+
+/// Team filters for `GET /api/teams` and the CPU auto-fill pool.
+#[derive(Debug, Default, Deserialize)]
+struct TeamQuery {
+    kind: Option<String>,
+    confed: Option<String>,
+    country: Option<String>,
+    league: Option<String>,
+}
+
+impl TeamQuery {
+    fn filter(&self) -> Result<TeamFilter, String> {
+        TeamFilter::parse(
+            self.kind.as_deref(),
+            self.confed.as_deref(),
+            self.country.as_deref(),
+            self.league.as_deref(),
+        )
+    }
 }
 
 #[derive(Debug, Serialize)]
 struct SimulationSummaryDto {
     id: usize,
     mode: String,
+    engine: String,
     status: String,
     seed: u64,
     teams: Vec<String>,
@@ -95,6 +141,7 @@ struct SimulationSummaryDto {
 struct SimulationDetailDto {
     id: usize,
     mode: String,
+    engine: String,
     status: String,
     seed: u64,
     teams: Vec<String>,
@@ -103,9 +150,12 @@ struct SimulationDetailDto {
     scoreboard: String,
     outcome: String,
     logs: Vec<String>,
+    events: Vec<MatchEvent>,
     stats_lines: Vec<String>,
     competition_lines: Vec<String>,
     history_lines: Vec<String>,
+    win_probability: Option<WinProbability>,
+    tie_break: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -113,6 +163,28 @@ struct CreateSimulationRequest {
     mode: String,
     teams: Option<Vec<String>>,
     auto_fill: Option<bool>,
+    /// Teams auto-fill draws from; defaults to every team.
+    pool: Option<TeamQuery>,
+    home_advantage: Option<f64>,
+    neutral_venue: Option<bool>,
+    neutral_final: Option<bool>,
+    engine: Option<String>,
+    use_elo: Option<bool>,
+    /// Team count for modes "league" and "knockout"; defaults to the number of teams
+    /// given, or 4.
+    team_count: Option<usize>,
+    double_round_robin: Option<bool>,
+    draw: Option<String>,
+    /// Teams a pot draw keeps apart: "none", "country" or "confederation".
+    separation: Option<String>,
+    two_legged: Option<bool>,
+    away_goals: Option<bool>,
+    best_thirds: Option<bool>,
+    swiss_matches: Option<usize>,
+    direct: Option<usize>,
+    playoff: Option<usize>,
+    /// Tie-breaker preset or comma-separated rule list for standings tables.
+    tie_break: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -129,7 +201,11 @@ fn sim_type_label(sim_type: SimulationType) -> &'static str {
     match sim_type {
         SimulationType::Single => "Single Match",
         SimulationType::League4 => "4-Team League",
+        SimulationType::League => "Round-Robin League",
         SimulationType::Knockout4 => "4-Team Knockout",
+        SimulationType::Knockout => "Knockout Bracket",
+        SimulationType::Tournament => "Group Stage Tournament",
+        SimulationType::Swiss => "Swiss League Phase",
     }
 }
 
@@ -137,21 +213,89 @@ fn mode_to_sim_type(mode: &str) -> Option<SimulationType> {
     match mode {
         "single" => Some(SimulationType::Single),
         "league4" => Some(SimulationType::League4),
+        "league" => Some(SimulationType::League),
         "knockout4" => Some(SimulationType::Knockout4),
+        "knockout" => Some(SimulationType::Knockout),
+        "tournament" => Some(SimulationType::Tournament),
+        "swiss" => Some(SimulationType::Swiss),
         _ => None,
     }
 }
 
-fn required_team_count(sim_type: SimulationType) -> usize {
+fn swiss_format(payload: &CreateSimulationRequest) -> SwissFormat {
+    let defaults = SwissFormat::default();
+    SwissFormat {
+        matches: payload.swiss_matches.unwrap_or(defaults.matches),
+        direct: payload.direct,
+        playoff: payload.playoff,
+    }
+}
+
+fn required_team_count(
+    sim_type: SimulationType,
+    payload: &CreateSimulationRequest,
+) -> Result<usize, String> {
     match sim_type {
-        SimulationType::Single => 2,
-        SimulationType::League4 | SimulationType::Knockout4 => 4,
+        SimulationType::Single => Ok(2),
+        SimulationType::League4 | SimulationType::Knockout4 => Ok(4),
+        SimulationType::League | SimulationType::Knockout => {
+            let min = if sim_type == SimulationType::League {
+                MIN_LEAGUE_TEAMS
+            } else {
+                MIN_KNOCKOUT_TEAMS
+            };
+            let provided = payload.teams.as_ref().map(Vec::len).unwrap_or(0);
+            let count = payload
+                .team_count
+                .unwrap_or(if provided >= min { provided } else { 4 });
+            if (min..=MAX_WEB_TEAMS).contains(&count) {
+                Ok(count)
+            } else {
+                Err(format!(
+                    "mode {} needs between {} and {} teams (got {count})",
+                    sim_type.as_str(),
+                    min,
+                    MAX_WEB_TEAMS
+                ))
+            }
+        }
+        SimulationType::Tournament => {
+            let provided = payload.teams.as_ref().map(Vec::len).unwrap_or(0);
+            let count = payload
+                .team_count
+                .unwrap_or(provided.max(MIN_TOURNAMENT_TEAMS));
+            if (MIN_TOURNAMENT_TEAMS..=MAX_WEB_TEAMS).contains(&count)
+                && count.is_multiple_of(GROUP_SIZE)
+            {
+                Ok(count)
+            } else {
+                Err(format!(
+                    "mode tournament needs a multiple of {} teams between {} and {} (got {count})",
+                    GROUP_SIZE, MIN_TOURNAMENT_TEAMS, MAX_WEB_TEAMS
+                ))
+            }
+        }
+        SimulationType::Swiss => {
+            let format = swiss_format(payload);
+            let provided = payload.teams.as_ref().map(Vec::len).unwrap_or(0);
+            let count = payload
+                .team_count
+                .unwrap_or(provided.max(format.pots() * 4));
+            if count > MAX_WEB_TEAMS {
+                return Err(format!(
+                    "mode swiss accepts at most {} teams (got {count})",
+                    MAX_WEB_TEAMS
+                ));
+            }
+            format.check(count).map(|_| count)
+        }
     }
 }
 
 fn status_label(status: &SimStatus) -> &'static str {
     match status {
         SimStatus::Pending => "pending",
+        SimStatus::Preparing => "preparing",
         SimStatus::Running { .. } => "running",
         SimStatus::Completed => "completed",
     }
@@ -166,6 +310,26 @@ fn simulation_title(sim: &SimulationInstance) -> String {
                 "Match".to_string()
             }
         }
+        SimulationType::League => format!(
+            "{}-Team League{}",
+            sim.teams.len(),
+            if sim.config.double_round_robin {
+                " (home and away)"
+            } else {
+                ""
+            }
+        ),
+        SimulationType::Knockout => format!("{}-Team Knockout", sim.teams.len()),
+        SimulationType::Tournament => format!(
+            "{}-Team Tournament ({} groups)",
+            sim.teams.len(),
+            sim.teams.len() / GROUP_SIZE
+        ),
+        SimulationType::Swiss => format!(
+            "{}-Team Swiss League Phase ({} matches each)",
+            sim.teams.len(),
+            sim.config.swiss.matches
+        ),
         _ => sim_type_label(sim.sim_type).to_string(),
     }
 }
@@ -174,6 +338,7 @@ fn to_summary(sim: &SimulationInstance) -> SimulationSummaryDto {
     SimulationSummaryDto {
         id: sim.id,
         mode: sim.sim_type.as_str().to_string(),
+        engine: sim.config.engine.as_str().to_string(),
         status: status_label(&sim.status).to_string(),
         seed: sim.seed,
         teams: sim.teams.clone(),
@@ -188,6 +353,7 @@ fn to_detail(sim: &SimulationInstance) -> SimulationDetailDto {
     SimulationDetailDto {
         id: sim.id,
         mode: sim.sim_type.as_str().to_string(),
+        engine: sim.config.engine.as_str().to_string(),
         status: status_label(&sim.status).to_string(),
         seed: sim.seed,
         teams: sim.teams.clone(),
@@ -196,9 +362,12 @@ fn to_detail(sim: &SimulationInstance) -> SimulationDetailDto {
         scoreboard: sim.scoreboard.clone(),
         outcome: sim.outcome_summary(),
         logs: vecdeque_to_vec(&sim.logs),
+        events: sim.events.iter().cloned().collect(),
         stats_lines: sim.stats_lines.clone(),
         competition_lines: sim.competition_lines.clone(),
         history_lines: sim.history_lines.clone(),
+        win_probability: sim.win_prob.clone(),
+        tie_break: tie_breakers_label(&sim.config.tie_breakers),
     }
 }
 
@@ -208,11 +377,12 @@ fn vecdeque_to_vec(items: &VecDeque<String>) -> Vec<String> {
 
 fn resolve_teams(
     sim_type: SimulationType,
+    required: usize,
     provided_teams: Option<Vec<String>>,
     auto_fill: bool,
+    pool: &TeamFilter,
     seed: u64,
 ) -> Result<Vec<String>, String> {
-    let required = required_team_count(sim_type);
     let mut selected = provided_teams.unwrap_or_default();
     let mut seen = HashSet::new();
 
@@ -242,19 +412,22 @@ fn resolve_teams(
     }
 
     if auto_fill {
-        let mut pool: Vec<&str> = TEAMS
-            .iter()
-            .copied()
+        let mut remaining: Vec<&str> = pool
+            .teams()
+            .into_iter()
             .filter(|team| !seen.contains(*team))
             .collect();
 
         let mut rng = Rng::new(seed);
         while selected.len() < required {
-            if pool.is_empty() {
-                return Err("Not enough teams available for auto-fill".to_string());
+            if remaining.is_empty() {
+                return Err(format!(
+                    "Not enough {} available for auto-fill",
+                    pool.label()
+                ));
             }
-            let i = rng.range_usize(pool.len());
-            selected.push(pool.remove(i).to_string());
+            let i = rng.range_usize(remaining.len());
+            selected.push(remaining.remove(i).to_string());
         }
     }
 
@@ -294,12 +467,21 @@ async fn api_config(state: web::Data<SharedState>) -> impl Responder {
     HttpResponse::Ok().json(ConfigResponse { demo: guard.demo })
 }
 
-async fn api_teams() -> impl Responder {
-    let items: Vec<TeamDto> = TEAMS
+async fn api_teams(query: web::Query<TeamQuery>) -> impl Responder {
+    let filter = match query.filter() {
+        Ok(filter) => filter,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorDto { error: e }),
+    };
+    let items: Vec<TeamDto> = TEAMS_DATA
         .iter()
+        .filter(|team| filter.matches(team))
         .map(|team| TeamDto {
-            name: (*team).to_string(),
-            display_name: display_name(team),
+            name: team.name.to_string(),
+            display_name: display_name(team.name),
+            kind: team.kind.as_str().to_string(),
+            country: team.country.to_string(),
+            confederation: team.confederation.as_str().to_string(),
+            league: team.league.map(str::to_string),
         })
         .collect();
     HttpResponse::Ok().json(items)
@@ -369,12 +551,108 @@ async fn api_create_simulation(
     let id = guard.next_id;
     let seed = guard.next_seed();
     let auto_fill = payload.auto_fill.unwrap_or(true);
-    let teams = match resolve_teams(sim_type, payload.teams.clone(), auto_fill, seed) {
+    let required = match required_team_count(sim_type, &payload) {
+        Ok(v) => v,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorDto { error: e }),
+    };
+    let pool = match payload.pool.as_ref().map(TeamQuery::filter).transpose() {
+        Ok(pool) => pool.unwrap_or_default(),
+        Err(e) => return HttpResponse::BadRequest().json(ErrorDto { error: e }),
+    };
+    let teams = match resolve_teams(
+        sim_type,
+        required,
+        payload.teams.clone(),
+        auto_fill,
+        &pool,
+        seed,
+    ) {
         Ok(v) => v,
         Err(e) => return HttpResponse::BadRequest().json(ErrorDto { error: e }),
     };
 
-    let sim = SimulationInstance::new(id, sim_type, teams, seed);
+    let home_advantage = match payload.home_advantage {
+        Some(v) => match SimConfig::check_home_advantage(v) {
+            Ok(v) => v,
+            Err(e) => return HttpResponse::BadRequest().json(ErrorDto { error: e }),
+        },
+        None => DEFAULT_HOME_ADVANTAGE,
+    };
+    let engine = match payload.engine.as_deref() {
+        Some(key) => match EngineKind::parse(key) {
+            Some(engine) => engine,
+            None => {
+                return HttpResponse::BadRequest().json(ErrorDto {
+                    error: format!("Unsupported engine: {key}"),
+                })
+            }
+        },
+        None => EngineKind::default(),
+    };
+    let draw = match payload.draw.as_deref() {
+        Some(key) => match DrawKind::parse(key) {
+            Some(draw) => draw,
+            None => {
+                return HttpResponse::BadRequest().json(ErrorDto {
+                    error: format!("Unsupported draw: {key}"),
+                })
+            }
+        },
+        None => DrawKind::default(),
+    };
+    let separation = match payload.separation.as_deref() {
+        Some(key) => match Separation::parse(key) {
+            Some(separation) => separation,
+            None => {
+                return HttpResponse::BadRequest().json(ErrorDto {
+                    error: format!("Unsupported separation: {key}"),
+                })
+            }
+        },
+        None => Separation::default(),
+    };
+    let tie_breakers = match payload.tie_break.as_deref() {
+        Some(value) => match parse_tie_breakers(value) {
+            Ok(rules) => rules,
+            Err(e) => return HttpResponse::BadRequest().json(ErrorDto { error: e }),
+        },
+        None => SimConfig::default().tie_breakers,
+    };
+    let elo = if payload.use_elo.unwrap_or(false) {
+        match EloStore::load(&guard.ratings.path) {
+            Ok(store) => Some(Arc::new(store.strength())),
+            Err(e) => {
+                return HttpResponse::InternalServerError().json(ErrorDto {
+                    error: format!("Could not load ratings: {e}"),
+                })
+            }
+        }
+    } else {
+        None
+    };
+    let config = SimConfig {
+        home_advantage,
+        neutral_venue: payload.neutral_venue.unwrap_or(false),
+        neutral_final: payload.neutral_final.unwrap_or(false),
+        double_round_robin: payload.double_round_robin.unwrap_or(false),
+        draw,
+        separation,
+        tie_format: if payload.two_legged.unwrap_or(false) {
+            TieFormat::TwoLegs {
+                away_goals: payload.away_goals.unwrap_or(false),
+            }
+        } else {
+            TieFormat::SingleMatch
+        },
+        best_thirds: payload.best_thirds.unwrap_or(false),
+        swiss: swiss_format(&payload),
+        tie_breakers,
+        engine,
+        elo,
+        ..SimConfig::default()
+    };
+
+    let sim = SimulationInstance::new(id, sim_type, teams, seed, config);
     guard.instances.push(sim);
     guard.next_id += 1;
 
@@ -386,6 +664,42 @@ async fn api_start_simulation(
     state: web::Data<SharedState>,
 ) -> impl Responder {
     let id = path.into_inner();
+    let job = {
+        let mut guard = match state.inner.lock() {
+            Ok(g) => g,
+            Err(_) => {
+                return HttpResponse::InternalServerError().json(ErrorDto {
+                    error: "state lock poisoned".to_string(),
+                })
+            }
+        };
+        let Some(sim) = guard.simulation_mut(id) else {
+            return HttpResponse::NotFound().json(ErrorDto {
+                error: format!("simulation {id} not found"),
+            });
+        };
+        match sim.start() {
+            Some(job) => job,
+            None => return HttpResponse::Ok().json(to_summary(sim)),
+        }
+    };
+
+    // Big competitions take a while, so simulate on the blocking pool without the lock held.
+    let prepared = match web::block(move || job.run()).await {
+        Ok(prepared) => prepared,
+        Err(_) => {
+            // Leave it startable again rather than stuck in `Preparing`.
+            if let Ok(mut guard) = state.inner.lock() {
+                if let Some(sim) = guard.simulation_mut(id) {
+                    sim.abort_start();
+                }
+            }
+            return HttpResponse::InternalServerError().json(ErrorDto {
+                error: format!("simulation {id} failed to run"),
+            });
+        }
+    };
+
     let mut guard = match state.inner.lock() {
         Ok(g) => g,
         Err(_) => {
@@ -394,15 +708,13 @@ async fn api_start_simulation(
             })
         }
     };
-
-    if let Some(sim) = guard.simulation_mut(id) {
-        sim.start();
-        return HttpResponse::Ok().json(to_summary(sim));
-    }
-
-    HttpResponse::NotFound().json(ErrorDto {
-        error: format!("simulation {id} not found"),
-    })
+    let Some(sim) = guard.simulation_mut(id) else {
+        return HttpResponse::NotFound().json(ErrorDto {
+            error: format!("simulation {id} was deleted while it ran"),
+        });
+    };
+    sim.begin(prepared);
+    HttpResponse::Ok().json(to_summary(sim))
 }
 
 async fn api_clone_simulation(
@@ -503,9 +815,15 @@ async fn api_export_csv(path: web::Path<usize>, state: web::Data<SharedState>) -
         .body(csv)
 }
 
-pub fn run_web_server(base_seed: u64, speed: Speed, listen_open: bool, demo: bool) -> io::Result<()> {
+pub fn run_web_server(
+    base_seed: u64,
+    speed: Speed,
+    listen_open: bool,
+    demo: bool,
+    ratings: RatingsConfig,
+) -> io::Result<()> {
     let shared = SharedState {
-        inner: Arc::new(Mutex::new(WebState::new(base_seed, speed, demo))),
+        inner: Arc::new(Mutex::new(WebState::new(base_seed, speed, demo, ratings))),
     };
 
     let ticker = shared.clone();
@@ -0,0 +1,236 @@
+use std::cmp::Ordering;
+
+use serde::Serialize;
+
+use crate::data::{profile_for, tactic_by_key, Tactic, TeamProfile};
+use crate::engine::goal_expectation;
+use crate::events::Minute;
+use crate::manager::{review_tactic, GameState, DECISION_MINUTES};
+use crate::sim::{shorthanded_mult, MatchOptions};
+use crate::utils::Rng;
+
+/// Forward simulations per estimate.
+const SAMPLES: u32 = 500;
+
+/// Home/draw/away chances from a given point in a match. In knockout ties `draw`
+/// means still level after extra time, i.e. going to penalties, and in a second leg
+/// the chances are for winning the tie on aggregate.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WinProbability {
+    pub minute: Minute,
+    pub home: String,
+    pub away: String,
+    pub home_win: f64,
+    pub draw: f64,
+    pub away_win: f64,
+}
+
+/// Where a match stands at the end of a minute.
+#[derive(Debug, Clone, Copy)]
+pub struct LiveState {
+    pub minute: Minute,
+    pub home_goals: u8,
+    pub away_goals: u8,
+    pub home_tactic: Tactic,
+    pub away_tactic: Tactic,
+    pub home_reds: u16,
+    pub away_reds: u16,
+}
+
+/// Per-minute scoring chances for both sides under the current tactics and numbers.
+fn minute_rates(
+    home: TeamProfile,
+    away: TeamProfile,
+    state: &LiveState,
+    home_factor: f64,
+) -> (f64, f64) {
+    let (home_atk, home_block) = shorthanded_mult(state.home_reds);
+    let (away_atk, away_block) = shorthanded_mult(state.away_reds);
+    let home_xg = goal_expectation(
+        home,
+        state.home_tactic,
+        away,
+        state.away_tactic,
+        home_factor,
+    );
+    let away_xg = goal_expectation(away, state.away_tactic, home, state.home_tactic, 1.0);
+    (
+        home_xg * home_atk / away_block / 90.0,
+        away_xg * away_atk / home_block / 90.0,
+    )
+}
+
+/// Let both benches react to the score as the match manager would.
+fn review(base: Tactic, current: Tactic, state: GameState) -> Tactic {
+    review_tactic(base, current, state)
+        .map(|(tactic, _)| tactic)
+        .unwrap_or(current)
+}
+
+/// Estimate the final outcome by playing out the rest of the match many times from
+/// `state`, with scoring rates from ratings, tactics, venue and red cards. Goals arrive
+/// as a Poisson process, so each run only steps from goal to goal. Passing the
+/// same `seed` every minute reuses the random draws, so the estimate only moves when
+/// the state does.
+pub fn win_probability(
+    home: &str,
+    away: &str,
+    state: &LiveState,
+    options: MatchOptions,
+    seed: u64,
+) -> WinProbability {
+    let mut rng = Rng::new(seed);
+    let home_profile = profile_for(home).shifted(options.home_rating_shift);
+    let away_profile = profile_for(away).shifted(options.away_rating_shift);
+    let home_base = tactic_by_key(home_profile.tactic);
+    let away_base = tactic_by_key(away_profile.tactic);
+    let home_factor = options.home_factor();
+
+    let (mut home_wins, mut draws, mut away_wins) = (0u32, 0u32, 0u32);
+    for _ in 0..SAMPLES {
+        let mut sim = *state;
+        let mut end = if sim.minute.base > 90 { 120.0 } else { 90.0 };
+        let mut clock = sim.minute.base as f64;
+        loop {
+            if clock >= end {
+                if end == 90.0 && options.needs_extra_time(sim.home_goals, sim.away_goals) {
+                    end = 120.0;
+                    continue;
+                }
+                break;
+            }
+
+            let diff = sim.home_goals as i16 - sim.away_goals as i16;
+            let minute = clock as u8;
+            sim.home_tactic = review(
+                home_base,
+                sim.home_tactic,
+                GameState {
+                    minute,
+                    goal_diff: diff,
+                    reds: sim.home_reds,
+                    opp_reds: sim.away_reds,
+                },
+            );
+            sim.away_tactic = review(
+                away_base,
+                sim.away_tactic,
+                GameState {
+                    minute,
+                    goal_diff: -diff,
+                    reds: sim.away_reds,
+                    opp_reds: sim.home_reds,
+                },
+            );
+
+            // Tactics hold until the next goal or the next minute the bench may react.
+            let next_review = DECISION_MINUTES
+                .iter()
+                .map(|&m| m as f64)
+                .filter(|&m| m > clock)
+                .fold(end, f64::min);
+            let (home_rate, away_rate) =
+                minute_rates(home_profile, away_profile, &sim, home_factor);
+            let total = home_rate + away_rate;
+            let wait = -(1.0 - rng.next_f64()).ln() / total;
+            if clock + wait < next_review {
+                clock += wait;
+                if rng.next_f64() * total < home_rate {
+                    sim.home_goals += 1;
+                } else {
+                    sim.away_goals += 1;
+                }
+            } else {
+                clock = next_review;
+            }
+        }
+
+        match options.tie_standing(sim.home_goals, sim.away_goals) {
+            Ordering::Greater => home_wins += 1,
+            Ordering::Equal => draws += 1,
+            Ordering::Less => away_wins += 1,
+        }
+    }
+
+    let total = SAMPLES as f64;
+    WinProbability {
+        minute: state.minute,
+        home: home.to_string(),
+        away: away.to_string(),
+        home_win: home_wins as f64 / total,
+        draw: draws as f64 / total,
+        away_win: away_wins as f64 / total,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::{FirstLeg, SimConfig};
+
+    fn state(minute: u8, home_goals: u8, away_goals: u8) -> LiveState {
+        LiveState {
+            minute: Minute {
+                base: minute,
+                added: 0,
+            },
+            home_goals,
+            away_goals,
+            home_tactic: tactic_by_key(profile_for("Arsenal").tactic),
+            away_tactic: tactic_by_key(profile_for("Inter").tactic),
+            home_reds: 0,
+            away_reds: 0,
+        }
+    }
+
+    fn options() -> MatchOptions {
+        SimConfig::default().match_options("Arsenal", "Inter", false)
+    }
+
+    fn estimate(state: &LiveState, options: MatchOptions) -> WinProbability {
+        win_probability("Arsenal", "Inter", state, options, 42)
+    }
+
+    #[test]
+    fn chances_sum_to_one() {
+        for (minute, h, a) in [(0, 0, 0), (30, 1, 0), (75, 1, 2)] {
+            let p = estimate(&state(minute, h, a), options());
+            assert!((p.home_win + p.draw + p.away_win - 1.0).abs() < 1e-9);
+            assert_eq!(p, estimate(&state(minute, h, a), options()));
+        }
+    }
+
+    #[test]
+    fn final_whistle_is_certain() {
+        let p = estimate(&state(90, 2, 1), options());
+        assert_eq!((p.home_win, p.draw, p.away_win), (1.0, 0.0, 0.0));
+        let p = estimate(&state(90, 1, 1), options());
+        assert_eq!(p.draw, 1.0);
+        // Level knockout matches still have extra time to play.
+        let knockout = MatchOptions {
+            extra_time: true,
+            ..options()
+        };
+        let p = estimate(&state(90, 1, 1), knockout);
+        assert!(p.draw < 1.0 && p.home_win > 0.0 && p.away_win > 0.0);
+    }
+
+    #[test]
+    fn second_leg_counts_the_first_leg() {
+        let second_leg = |home_goals, away_goals| MatchOptions {
+            extra_time: true,
+            first_leg: Some(FirstLeg {
+                home_goals,
+                away_goals,
+                away_goals_rule: false,
+            }),
+            ..options()
+        };
+        let level = estimate(&state(0, 0, 0), options());
+        let ahead = estimate(&state(0, 0, 0), second_leg(3, 0));
+        let behind = estimate(&state(0, 0, 0), second_leg(0, 3));
+        assert!(ahead.home_win > 0.9, "{ahead:?}");
+        assert!(ahead.home_win > level.home_win);
+        assert!(behind.away_win > 0.9, "{behind:?}");
+    }
+}